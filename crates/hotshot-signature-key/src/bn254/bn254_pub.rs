@@ -134,6 +134,10 @@ impl SignatureKey for BN254Pub {
         }
     }
 
+    fn get_stake_from_entry(&self, entry: &Self::StakeTableEntry) -> Option<U256> {
+        (entry.stake_key == self.pub_key).then_some(entry.stake_amount)
+    }
+
     fn get_public_parameter(
         stake_entries: Vec<Self::StakeTableEntry>,
         threshold: U256,
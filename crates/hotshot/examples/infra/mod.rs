@@ -44,6 +44,9 @@ pub fn load_config_from_file<TYPES: NodeType>(
     let config_toml: NetworkConfigFile =
         toml::from_str::<NetworkConfigFile>(&config_file_as_string)
             .expect("Unable to convert config file to TOML");
+    if let Err(e) = config_toml.validate() {
+        panic!("Invalid config file {config_file}: {e}");
+    }
 
     let mut config: NetworkConfig<
         TYPES::SignatureKey,
@@ -63,7 +66,10 @@ pub fn load_config_from_file<TYPES: NodeType>(
         .collect();
 
     config.config.known_nodes_with_stake = (0..config.config.total_nodes.get())
-        .map(|node_id| config.config.known_nodes[node_id].get_stake_table_entry(1u64))
+        .map(|node_id| {
+            config.config.known_nodes[node_id]
+                .get_stake_table_entry(config.stake_of(node_id as u64))
+        })
         .collect();
 
     config
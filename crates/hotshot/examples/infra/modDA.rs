@@ -203,12 +203,8 @@ pub trait RunDA<
     /// get the anchored view
     /// Note: sequencing leaf does not have state, so does not return state
     async fn initialize_state_and_hotshot(&self) -> SystemContextHandle<TYPES, NODE> {
-        let genesis_block = TYPES::BlockType::genesis();
         let initializer =
-            hotshot::HotShotInitializer::<TYPES, SequencingLeaf<TYPES>>::from_genesis(
-                genesis_block,
-            )
-            .expect("Couldn't generate genesis block");
+            hotshot::HotShotInitializer::<TYPES, SequencingLeaf<TYPES>>::from_genesis();
 
         let config = self.get_config();
 
@@ -217,7 +213,7 @@ pub trait RunDA<
             TYPES::SignatureKey::generated_from_seed_indexed(config.seed, config.node_index);
         let known_nodes = config.config.known_nodes.clone();
         let known_nodes_with_stake = config.config.known_nodes_with_stake.clone();
-        let entry = pk.get_stake_table_entry(1u64);
+        let entry = pk.get_stake_table_entry(config.stake_of(config.node_index));
 
         let da_network = self.get_da_network();
         let quorum_network = self.get_quorum_network();
@@ -225,10 +221,18 @@ pub trait RunDA<
 
         // Since we do not currently pass the election config type in the NetworkConfig, this will always be the default election config
         let quorum_election_config = config.config.election_config.clone().unwrap_or_else(|| {
-            <QuorumEx<TYPES,NODE> as ConsensusExchange<
-                TYPES,
-                Message<TYPES, NODE>,
-            >>::Membership::default_election_config(config.config.total_nodes.get() as u64)
+            match &config.stake_distribution {
+                Some(stakes) => <QuorumEx<TYPES, NODE> as ConsensusExchange<
+                    TYPES,
+                    Message<TYPES, NODE>,
+                >>::Membership::stake_weighted_election_config(stakes),
+                None => <QuorumEx<TYPES, NODE> as ConsensusExchange<
+                    TYPES,
+                    Message<TYPES, NODE>,
+                >>::Membership::default_election_config(
+                    config.config.total_nodes.get() as u64
+                ),
+            }
         });
 
         let committee_election_config = <CommitteeEx<TYPES, NODE> as ConsensusExchange<
@@ -103,10 +103,11 @@ impl Committable for SDemoBlock {
             SDemoBlock::Genesis(_) => {
                 commit::RawCommitmentBuilder::new("SDemo Genesis Comm").finalize()
             }
+            // Commits to the transactions in order, so reordering them changes the commitment.
             SDemoBlock::Normal(block) => {
                 let mut builder = commit::RawCommitmentBuilder::new("SDemo Normal Comm");
                 for txn in &block.transactions {
-                    builder = builder.u64_field("transaction", **txn);
+                    builder = builder.field("transaction", txn.commit());
                 }
                 builder.finalize()
             }
@@ -273,6 +274,20 @@ impl State for SDemoState {
     fn on_commit(&self) {}
 }
 
+impl SDemoState {
+    /// The genesis block and the state it produces when appended to the default state.
+    /// # Panics
+    /// If the genesis block cannot be appended to the default state
+    #[must_use]
+    pub fn genesis() -> (SDemoBlock, Self) {
+        let block = <SDemoBlock as TestableBlock>::genesis();
+        let state = Self::default()
+            .append(&block, &ViewNumber::genesis())
+            .expect("genesis block must apply to the default state");
+        (block, state)
+    }
+}
+
 impl TestableState for SDemoState {
     fn create_random_transaction(
         _state: Option<&Self>,
@@ -309,6 +324,10 @@ impl NodeType for SDemoTypes {
     type Transaction = SDemoTransaction;
     type ElectionConfigType = StaticElectionConfig;
     type StateType = SDemoState;
+
+    fn genesis() -> (Self::BlockType, Self::StateType) {
+        SDemoState::genesis()
+    }
 }
 
 /// The node implementation for the sequencing demo
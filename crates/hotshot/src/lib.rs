@@ -35,39 +35,46 @@ pub mod tasks;
 use crate::{
     certificate::QuorumCertificate,
     tasks::{
-        add_consensus_task, add_da_task, add_network_event_task, add_network_message_task,
-        add_view_sync_task,
+        add_consensus_task, add_da_task, add_network_message_task, add_view_sync_task,
+        register_network_tasks,
     },
     traits::{NodeImplementation, Storage},
     types::{Event, SystemContextHandle},
 };
 use async_compatibility_layer::{
     art::{async_spawn, async_spawn_local},
-    async_primitives::{broadcast::BroadcastSender, subscribable_rwlock::SubscribableRwLock},
+    async_primitives::{
+        broadcast::BroadcastSender,
+        subscribable_rwlock::{ReadView, SubscribableRwLock},
+    },
     channel::{unbounded, UnboundedReceiver, UnboundedSender},
 };
 use async_lock::{Mutex, RwLock, RwLockUpgradableReadGuard, RwLockWriteGuard};
 use async_trait::async_trait;
-use commit::{Commitment, Committable};
+use bincode::Options;
+use commit::Committable;
 use custom_debug::Debug;
 use hotshot_task::{
     event_stream::{ChannelStream, EventStream},
     task_launcher::TaskRunner,
 };
-use hotshot_task_impls::{events::SequencingHotShotEvent, network::NetworkTaskKind};
+use hotshot_task_impls::events::SequencingHotShotEvent;
 
 use hotshot_types::{
     certificate::{DACertificate, ViewSyncCertificate},
-    consensus::{BlockStore, Consensus, ConsensusMetrics, View, ViewInner, ViewQueue},
+    consensus::{
+        BlockStore, Consensus, ConsensusMetrics, Mempool, ProposalSlots, View, ViewInner,
+        ViewQueue, ViewSyncMetrics, DEFAULT_TXN_INDEX_RETENTION,
+    },
     data::{DAProposal, DeltasType, LeafType, ProposalType, QuorumProposal, SequencingLeaf},
-    error::StorageSnafu,
+    error::{KeyPairSnafu, StorageSnafu},
     message::{
         ConsensusMessageType, DataMessage, InternalTrigger, Message, MessageKind,
         ProcessedGeneralConsensusMessage, SequencingMessage,
     },
     traits::{
         consensus_api::{ConsensusSharedApi, SequencingConsensusApi},
-        election::{ConsensusExchange, Membership, SignedCertificate},
+        election::{ConsensusExchange, Membership, SignedCertificate, ViewSyncExchangeType},
         metrics::Metrics,
         network::{CommunicationChannel, NetworkError},
         node_implementation::{
@@ -77,11 +84,13 @@ use hotshot_types::{
         signature_key::SignatureKey,
         state::ConsensusTime,
         storage::StoredView,
-        State,
+        Block, State,
     },
+    verification::{SignatureCache, VerificationPool},
     vote::{ViewSyncData, VoteType},
     HotShotConfig,
 };
+use hotshot_utils::bincode::bincode_opts;
 use snafu::ResultExt;
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
@@ -136,8 +145,7 @@ pub struct SystemContextInner<TYPES: NodeType, I: NodeImplementation<TYPES>> {
 
     /// Transactions
     /// (this is shared btwn hotshot and `Consensus`)
-    transactions:
-        Arc<SubscribableRwLock<HashMap<Commitment<TYPES::Transaction>, TYPES::Transaction>>>,
+    transactions: Arc<SubscribableRwLock<Mempool<TYPES::Transaction>>>,
 
     /// The hotstuff implementation
     consensus: Arc<RwLock<Consensus<TYPES, I::Leaf>>>,
@@ -158,6 +166,33 @@ pub struct SystemContextInner<TYPES: NodeType, I: NodeImplementation<TYPES>> {
 
     /// uid for instrumentation
     id: u64,
+
+    /// Limits how many proposals are assembled at once
+    proposal_slots: ProposalSlots,
+}
+
+impl<TYPES: NodeType, I: NodeImplementation<TYPES>> SystemContextInner<TYPES, I> {
+    /// The transactions in the mempool that the high QC's leaf, which is the parent of the next
+    /// proposal, does not already include
+    async fn pending_transactions(&self) -> Vec<TYPES::Transaction> {
+        let consensus = self.consensus.read().await;
+        let parent_txns = match consensus
+            .saved_leaves
+            .get(&consensus.high_qc.leaf_commitment)
+            .map(|leaf| leaf.get_deltas().try_resolve())
+        {
+            Some(Ok(block)) => block.contained_transactions(),
+            _ => HashSet::new(),
+        };
+        drop(consensus);
+        self.transactions
+            .cloned()
+            .await
+            .iter()
+            .filter(|(txn_hash, _)| !parent_txns.contains(txn_hash))
+            .map(|(_, txn)| txn.clone())
+            .collect()
+    }
 }
 
 /// Thread safe, shared view of a `HotShot`
@@ -183,16 +218,31 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> SystemContext<TYPES, I> {
             TYPES::ElectionConfigType,
         >,
         storage: I::Storage,
-        exchanges: I::Exchanges,
+        mut exchanges: I::Exchanges,
         initializer: HotShotInitializer<TYPES, I::Leaf>,
         metrics: Box<dyn Metrics>,
     ) -> Result<Self, HotShotError<TYPES>> {
         debug!("Creating a new hotshot");
 
+        // Fail fast on a private key that does not belong to our public key, rather than having
+        // every vote we send rejected later on.
+        exchanges.validate_keys().context(KeyPairSnafu)?;
+
         let consensus_metrics = Arc::new(ConsensusMetrics::new(
             &*metrics.subgroup("consensus".to_string()),
         ));
+        exchanges
+            .view_sync_exchange_mut()
+            .set_metrics(Arc::new(ViewSyncMetrics::new(
+                &*metrics.subgroup("view_sync".to_string()),
+            )));
+        exchanges.set_verification_pool(VerificationPool::from_config(config.verification_threads));
+        exchanges.set_signature_cache(config.signature_cache_size.map(SignatureCache::new));
         let anchored_leaf = initializer.inner;
+        let anchored_qc = anchored_leaf.get_justify_qc();
+        if anchored_qc.is_genesis() {
+            exchanges.set_genesis_view(anchored_qc.view_number());
+        }
 
         // insert to storage
         storage
@@ -223,6 +273,7 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> SystemContext<TYPES, I> {
         let consensus = Consensus {
             state_map,
             cur_view: start_view,
+            start_view,
             last_decided_view: anchored_leaf.get_view_number(),
             transactions: Arc::default(),
             seen_transactions: HashSet::new(),
@@ -234,14 +285,20 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> SystemContext<TYPES, I> {
             high_qc: anchored_leaf.get_justify_qc(),
             metrics: consensus_metrics,
             invalid_qc: 0,
+            max_buffered_views: config.max_buffered_views,
+            txn_index: HashMap::new(),
+            txn_index_retention: DEFAULT_TXN_INDEX_RETENTION,
+            decide_callbacks: Vec::new(),
         };
         let consensus = Arc::new(RwLock::new(consensus));
         let txns = consensus.read().await.get_transactions();
 
         let (_send_network_lookup, recv_network_lookup) = unbounded();
+        let proposal_slots = ProposalSlots::new(config.max_inflight_proposals);
         let inner: Arc<SystemContextInner<TYPES, I>> = Arc::new(SystemContextInner {
             recv_network_lookup: Arc::new(Mutex::new(recv_network_lookup)),
             id: nonce,
+            proposal_slots,
             channel_maps: I::new_channel_maps(start_view),
             consensus,
             transactions: txns,
@@ -260,11 +317,12 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> SystemContext<TYPES, I> {
         Ok(Self { inner })
     }
 
-    /// "Starts" consensus by sending a `ViewChange` event
+    /// "Starts" consensus by sending a `ViewChange` event for the view after the start view
     pub async fn start_consensus(&self) {
+        let start_view = self.inner.consensus.read().await.start_view;
         self.inner
             .internal_event_stream
-            .publish(SequencingHotShotEvent::ViewChange(TYPES::Time::new(1)))
+            .publish(SequencingHotShotEvent::ViewChange(start_view + 1))
             .await;
 
         // ED This isn't ideal...
@@ -555,9 +613,7 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> SystemContext<TYPES, I> {
 #[async_trait]
 pub trait HotShotType<TYPES: NodeType, I: NodeImplementation<TYPES>> {
     /// Get the [`transactions`] field of [`HotShot`].
-    fn transactions(
-        &self,
-    ) -> &Arc<SubscribableRwLock<HashMap<Commitment<TYPES::Transaction>, TYPES::Transaction>>>;
+    fn transactions(&self) -> &Arc<SubscribableRwLock<Mempool<TYPES::Transaction>>>;
 
     /// Get the [`hotstuff`] field of [`HotShot`].
     fn consensus(&self) -> &Arc<RwLock<Consensus<TYPES, I::Leaf>>>;
@@ -692,9 +748,7 @@ where
             Membership = MEMBERSHIP,
         > + 'static,
 {
-    fn transactions(
-        &self,
-    ) -> &Arc<SubscribableRwLock<HashMap<Commitment<TYPES::Transaction>, TYPES::Transaction>>> {
+    fn transactions(&self) -> &Arc<SubscribableRwLock<Mempool<TYPES::Transaction>>> {
         &self.inner.transactions
     }
 
@@ -722,45 +776,42 @@ where
             storage: self.inner.storage.clone(),
         };
 
+        // Only the DA channel carries DA votes.
+        let drop_non_committee_da_votes =
+            (!self.inner.config.relay_da_votes).then(|| committee_exchange.membership().clone());
         let task_runner = add_network_message_task(
             task_runner,
             internal_event_stream.clone(),
-            quorum_exchange.clone(),
+            quorum_exchange,
+            None,
+            self.inner.config.drop_own_messages,
         )
         .await;
         let task_runner = add_network_message_task(
             task_runner,
             internal_event_stream.clone(),
             committee_exchange.clone(),
+            drop_non_committee_da_votes,
+            self.inner.config.drop_own_messages,
         )
         .await;
         let task_runner = add_network_message_task(
             task_runner,
             internal_event_stream.clone(),
-            view_sync_exchange.clone(),
+            view_sync_exchange,
+            None,
+            self.inner.config.drop_own_messages,
         )
         .await;
-        let task_runner = add_network_event_task(
-            task_runner,
+        let network_tasks = register_network_tasks(
+            &self.inner.exchanges,
             internal_event_stream.clone(),
-            quorum_exchange,
-            NetworkTaskKind::Quorum,
-        )
-        .await;
-        let task_runner = add_network_event_task(
-            task_runner,
-            internal_event_stream.clone(),
-            committee_exchange.clone(),
-            NetworkTaskKind::Committee,
-        )
-        .await;
-        let task_runner = add_network_event_task(
-            task_runner,
-            internal_event_stream.clone(),
-            view_sync_exchange.clone(),
-            NetworkTaskKind::ViewSync,
+            &task_runner.registry,
         )
         .await;
+        let task_runner = network_tasks
+            .into_iter()
+            .fold(task_runner, |task_runner, task| task.add_to(task_runner));
         let task_runner = add_consensus_task(
             task_runner,
             internal_event_stream.clone(),
@@ -813,6 +864,14 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> ConsensusSharedApi<TYPES, I:
         self.inner.config.propose_max_round_time
     }
 
+    fn propose_empty_after(&self) -> Option<Duration> {
+        self.inner.config.propose_empty_after
+    }
+
+    fn proposal_slots(&self) -> &ProposalSlots {
+        &self.inner.proposal_slots
+    }
+
     fn max_transactions(&self) -> NonZeroUsize {
         self.inner.config.max_transactions
     }
@@ -821,6 +880,19 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> ConsensusSharedApi<TYPES, I:
         self.inner.config.min_transactions
     }
 
+    async fn pending_transaction_count(&self) -> usize {
+        self.inner.pending_transactions().await.len()
+    }
+
+    async fn pending_transaction_bytes(&self) -> u64 {
+        self.inner
+            .pending_transactions()
+            .await
+            .iter()
+            .map(|txn| bincode_opts().serialized_size(txn).unwrap_or(0))
+            .sum()
+    }
+
     /// Generates and encodes a vote token
 
     async fn should_start_round(&self, _: TYPES::Time) -> bool {
@@ -883,6 +955,14 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> ConsensusSharedApi<TYPES, I:
         self.inner.config.propose_max_round_time
     }
 
+    fn propose_empty_after(&self) -> Option<Duration> {
+        self.inner.config.propose_empty_after
+    }
+
+    fn proposal_slots(&self) -> &ProposalSlots {
+        &self.inner.proposal_slots
+    }
+
     fn max_transactions(&self) -> NonZeroUsize {
         self.inner.config.max_transactions
     }
@@ -891,6 +971,28 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> ConsensusSharedApi<TYPES, I:
         self.inner.config.min_transactions
     }
 
+    async fn pending_transaction_count(&self) -> usize {
+        self.inner.pending_transactions().await.len()
+    }
+
+    async fn pending_transaction_bytes(&self) -> u64 {
+        self.inner
+            .pending_transactions()
+            .await
+            .iter()
+            .map(|txn| bincode_opts().serialized_size(txn).unwrap_or(0))
+            .sum()
+    }
+
+    /// Waits for the transactions expected to arrive within the minimum round time, bounded by
+    /// the configured minimum and maximum.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn min_transactions_for(&self, recent_rate: f64) -> usize {
+        let min = self.min_transactions();
+        let expected = (recent_rate * self.propose_min_round_time().as_secs_f64()) as usize;
+        expected.clamp(min, self.max_transactions().get().max(min))
+    }
+
     /// Generates and encodes a vote token
 
     async fn should_start_round(&self, _: TYPES::Time) -> bool {
@@ -1081,21 +1183,22 @@ pub struct HotShotInitializer<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>>
 }
 
 impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> HotShotInitializer<TYPES, LEAF> {
-    /// initialize from genesis
-    /// # Errors
-    /// If we are unable to apply the genesis block to the default state
-    pub fn from_genesis(genesis_block: TYPES::BlockType) -> Result<Self, HotShotError<TYPES>> {
-        let state = TYPES::StateType::default()
-            .append(&genesis_block, &TYPES::Time::new(0))
-            .map_err(|err| HotShotError::Misc {
-                context: err.to_string(),
-            })?;
-        let time = TYPES::Time::genesis();
-        let justify_qc = QuorumCertificate::<TYPES, LEAF>::genesis();
-
-        Ok(Self {
-            inner: LEAF::new(time, justify_qc, genesis_block, state),
-        })
+    /// initialize from the genesis block and state defined by [`NodeType::genesis`]
+    #[must_use]
+    pub fn from_genesis() -> Self {
+        Self::from_genesis_at(TYPES::Time::genesis())
+    }
+
+    /// initialize from the genesis block and state defined by [`NodeType::genesis`], placing the
+    /// genesis leaf at `view` so the chain continues from there
+    #[must_use]
+    pub fn from_genesis_at(view: TYPES::Time) -> Self {
+        let (genesis_block, state) = TYPES::genesis();
+        let justify_qc = QuorumCertificate::<TYPES, LEAF>::genesis_at(view);
+
+        Self {
+            inner: LEAF::new(view, justify_qc, genesis_block, state),
+        }
     }
 
     /// reload previous state based on most recent leaf
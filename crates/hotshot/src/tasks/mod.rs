@@ -5,18 +5,23 @@ use crate::{
     QuorumCertificate, SequencingQuorumEx, SystemContext,
 };
 use async_compatibility_layer::art::{async_sleep, async_spawn_local};
-use futures::FutureExt;
+use futures::{future::BoxFuture, FutureExt};
 use hotshot_task::{
     boxed_sync,
     event_stream::ChannelStream,
-    task::{FilterEvent, HandleEvent, HandleMessage, HotShotTaskCompleted, HotShotTaskTypes},
+    global_registry::{GlobalRegistry, HotShotTaskId},
+    task::{
+        FilterEvent, HandleEvent, HandleMessage, HotShotTaskCompleted, HotShotTaskTypes, PassType,
+    },
     task_impls::TaskBuilder,
     task_launcher::TaskRunner,
     GeneratedStream, Merge,
 };
 use hotshot_task_impls::{
-    consensus::{consensus_event_filter, ConsensusTaskTypes, SequencingConsensusTaskState},
-    da::{DATaskState, DATaskTypes},
+    consensus::{
+        consensus_event_filter, ConsensusTaskTypes, SequencingConsensusTaskState, VoteQueue,
+    },
+    da::{DATaskState, DATaskTypes, TransactionArrivalRate},
     events::SequencingHotShotEvent,
     network::{
         NetworkEventTaskState, NetworkEventTaskTypes, NetworkMessageTaskState,
@@ -34,7 +39,8 @@ use hotshot_types::{
         election::{ConsensusExchange, Membership},
         network::{CommunicationChannel, TransmitType},
         node_implementation::{
-            CommitteeEx, ExchangesType, NodeImplementation, NodeType, ViewSyncEx,
+            CommitteeEx, CommitteeMembership, ExchangesType, NodeImplementation, NodeType,
+            ViewSyncEx,
         },
         state::ConsensusTime,
         Block,
@@ -121,6 +127,10 @@ pub enum GlobalEvent {
 }
 
 /// Add the network task to handle messages and publish events.
+///
+/// If `drop_if_not_committee` is set, DA votes from senders outside that DA committee are dropped
+/// before they reach the event stream. If `drop_own_messages` is set, so are messages this node
+/// sent itself.
 /// # Panics
 /// Is unable to panic. This section here is just to satisfy clippy
 pub async fn add_network_message_task<
@@ -144,6 +154,8 @@ pub async fn add_network_message_task<
     task_runner: TaskRunner,
     event_stream: ChannelStream<SequencingHotShotEvent<TYPES, I>>,
     exchange: EXCHANGE,
+    drop_if_not_committee: Option<CommitteeMembership<TYPES, I>>,
+    drop_own_messages: bool,
 ) -> TaskRunner
 // This bound is required so that we can call the `recv_msgs` function of `CommunicationChannel`.
 where
@@ -191,9 +203,14 @@ where
         Some(boxed_sync(closure))
     }));
     let message_stream = Merge::new(broadcast_stream, direct_stream);
-    let network_state: NetworkMessageTaskState<_, _> = NetworkMessageTaskState {
-        event_stream: event_stream.clone(),
-    };
+    let mut network_state: NetworkMessageTaskState<_, _> =
+        NetworkMessageTaskState::new(event_stream.clone(), exchange.membership().chain_id());
+    if let Some(committee) = drop_if_not_committee {
+        network_state = network_state.dropping_non_committee_votes(committee);
+    }
+    if drop_own_messages {
+        network_state = network_state.dropping_own_messages(exchange.network().local_public_key());
+    }
     let registry = task_runner.registry.clone();
     let network_message_handler = HandleMessage(Arc::new(
         move |messages: either::Either<Messages<TYPES, I>, Messages<TYPES, I>>,
@@ -230,10 +247,34 @@ where
     )
 }
 
-/// Add the network task to handle events and send messages.
+/// A task that is built and registered but not yet running; add it to a [`TaskRunner`] to run it
+pub struct TaskHandle<EVENT: PassType> {
+    /// The task's id in the registry it was registered with
+    pub id: HotShotTaskId,
+    /// The task's name, for logging
+    pub name: String,
+    /// The filter the task was registered with, which decides the events it receives.
+    ///
+    /// For a network event task, calling it counts an event it lets through as pending for the
+    /// task, as publishing the event would.
+    pub filter: FilterEvent<EVENT>,
+    /// The task itself
+    pub task: BoxFuture<'static, HotShotTaskCompleted>,
+}
+
+impl<EVENT: PassType> TaskHandle<EVENT> {
+    /// Add the task to `task_runner`, which runs it once launched
+    #[must_use]
+    pub fn add_to(self, task_runner: TaskRunner) -> TaskRunner {
+        task_runner.add_task(self.id, self.name, self.task)
+    }
+}
+
+/// Build the network task of `task_kind` to handle events and send messages through `exchange`,
+/// registered with `registry`.
 /// # Panics
 /// Is unable to panic. This section here is just to satisfy clippy
-pub async fn add_network_event_task<
+pub async fn register_network_event_task<
     TYPES: NodeType,
     I: NodeImplementation<
         TYPES,
@@ -251,16 +292,18 @@ pub async fn add_network_event_task<
             Membership = MEMBERSHIP,
         > + 'static,
 >(
-    task_runner: TaskRunner,
     event_stream: ChannelStream<SequencingHotShotEvent<TYPES, I>>,
     exchange: EXCHANGE,
     task_kind: NetworkTaskKind,
-) -> TaskRunner
+    registry: &GlobalRegistry,
+) -> TaskHandle<SequencingHotShotEvent<TYPES, I>>
 // This bound is required so that we can call the `recv_msgs` function of `CommunicationChannel`.
 where
     EXCHANGE::Networking:
         CommunicationChannel<TYPES, Message<TYPES, I>, PROPOSAL, VOTE, MEMBERSHIP>,
 {
+    let network_state: NetworkEventTaskState<TYPES, I, PROPOSAL, VOTE, MEMBERSHIP, _> =
+        NetworkEventTaskState::new(exchange.network().clone(), event_stream.clone());
     let filter = NetworkEventTaskState::<
         TYPES,
         I,
@@ -268,20 +311,13 @@ where
         VOTE,
         MEMBERSHIP,
         <EXCHANGE as ConsensusExchange<_, _>>::Networking,
-    >::filter(task_kind);
-    let channel = exchange.network().clone();
-    let network_state: NetworkEventTaskState<_, _, _, _, _, _> = NetworkEventTaskState {
-        channel,
-        event_stream: event_stream.clone(),
-        view: TYPES::Time::genesis(),
-        phantom: PhantomData,
-    };
-    let registry = task_runner.registry.clone();
+    >::counted_filter(task_kind, network_state.outbound.clone());
     let network_event_handler = HandleEvent(Arc::new(
         move |event, mut state: NetworkEventTaskState<_, _, _, _, MEMBERSHIP, _>| {
             let membership = exchange.membership().clone();
             async move {
                 let completion_status = state.handle_event(event, &membership).await;
+                state.outbound.event_handled();
                 (completion_status, state)
             }
             .boxed()
@@ -291,7 +327,7 @@ where
 
     let networking_task_builder =
         TaskBuilder::<NetworkEventTaskTypes<_, _, _, _, _, _>>::new(networking_name.to_string())
-            .register_event_stream(event_stream.clone(), filter)
+            .register_event_stream(event_stream.clone(), filter.clone())
             .await
             .register_registry(&mut registry.clone())
             .await
@@ -303,11 +339,71 @@ where
     let networking_task_id = networking_task_builder.get_task_id().unwrap();
     let networking_task = NetworkEventTaskTypes::build(networking_task_builder).launch();
 
-    task_runner.add_task(
-        networking_task_id,
-        networking_name.to_string(),
-        networking_task,
-    )
+    TaskHandle {
+        id: networking_task_id,
+        name: networking_name.to_string(),
+        filter,
+        task: networking_task,
+    }
+}
+
+/// Build a network event task for each [`NetworkTaskKind`], in the order of
+/// [`NetworkTaskKind::ALL`], registered with `registry`.
+///
+/// Each task sends through the exchange of `exchanges` its kind belongs to. The exchanges are
+/// passed rather than a map from kind to channel because the kinds' channels have different
+/// types, and each task also needs its exchange's membership to broadcast.
+pub async fn register_network_tasks<
+    TYPES: NodeType,
+    I: NodeImplementation<
+        TYPES,
+        Leaf = SequencingLeaf<TYPES>,
+        ConsensusMessage = SequencingMessage<TYPES, I>,
+    >,
+>(
+    exchanges: &I::Exchanges,
+    event_stream: ChannelStream<SequencingHotShotEvent<TYPES, I>>,
+    registry: &GlobalRegistry,
+) -> Vec<TaskHandle<SequencingHotShotEvent<TYPES, I>>>
+where
+    SequencingQuorumEx<TYPES, I>: 'static,
+    CommitteeEx<TYPES, I>: 'static,
+    ViewSyncEx<TYPES, I>: 'static,
+{
+    let mut handles = Vec::with_capacity(NetworkTaskKind::ALL.len());
+    for task_kind in NetworkTaskKind::ALL {
+        let handle = match task_kind {
+            NetworkTaskKind::Quorum => {
+                register_network_event_task(
+                    event_stream.clone(),
+                    exchanges.quorum_exchange().clone(),
+                    task_kind,
+                    registry,
+                )
+                .await
+            }
+            NetworkTaskKind::Committee => {
+                register_network_event_task(
+                    event_stream.clone(),
+                    exchanges.committee_exchange().clone(),
+                    task_kind,
+                    registry,
+                )
+                .await
+            }
+            NetworkTaskKind::ViewSync => {
+                register_network_event_task(
+                    event_stream.clone(),
+                    exchanges.view_sync_exchange().clone(),
+                    task_kind,
+                    registry,
+                )
+                .await
+            }
+        };
+        handles.push(handle);
+    }
+    handles
 }
 
 /// add the consensus task
@@ -365,6 +461,9 @@ where
         current_proposal: None,
         id: handle.hotshot.inner.id,
         qc: None,
+        max_view_retries: handle.hotshot.inner.config.max_view_retries,
+        proposal_failures: HashMap::new(),
+        vote_queue: VoteQueue::new(handle.hotshot.inner.config.vote_queue_capacity),
     };
     let filter = FilterEvent(Arc::new(consensus_event_filter));
     let consensus_name = "Consensus Task";
@@ -445,6 +544,17 @@ where
         vote_collector: None,
         event_stream: event_stream.clone(),
         id: handle.hotshot.inner.id,
+        txn_arrival_rate: TransactionArrivalRate::default(),
+        proposed_block: None,
+        reuse_da_cert: None,
+        da_availability_timeout: handle.hotshot.inner.config.da_availability_timeout,
+        max_empty_fallbacks: handle
+            .hotshot
+            .inner
+            .config
+            .allow_empty_blocks
+            .then_some(handle.hotshot.inner.config.max_empty_fallbacks),
+        empty_fallbacks: 0,
     };
     let da_event_handler = HandleEvent(Arc::new(
         move |event, mut state: DATaskState<TYPES, I, HotShotSequencingConsensusApi<TYPES, I>>| {
@@ -515,6 +625,7 @@ where
         replica_task_map: HashMap::default(),
         relay_task_map: HashMap::default(),
         view_sync_timeout: Duration::new(5, 0),
+        max_relay_attempts: handle.hotshot.inner.config.max_relay_attempts,
         id: handle.hotshot.inner.id,
         last_garbage_collected_view: TYPES::Time::new(0),
     };
@@ -5,7 +5,9 @@ use hotshot_signature_key::bn254::BN254Pub;
 use hotshot_types::{
     data::LeafType,
     traits::{
-        election::{Checked, ElectionConfig, ElectionError, Membership, VoteToken},
+        election::{
+            Checked, ElectionConfig, ElectionError, Membership, VoteToken, DEFAULT_CHAIN_ID,
+        },
         node_implementation::NodeType,
         signature_key::{EncodedSignature, SignatureKey},
     },
@@ -13,7 +15,7 @@ use hotshot_types::{
 #[allow(deprecated)]
 use serde::{Deserialize, Serialize};
 use std::{marker::PhantomData, num::NonZeroU64};
-use tracing::debug;
+use tracing::{debug, error};
 
 /// Dummy implementation of [`Membership`]
 
@@ -27,6 +29,10 @@ pub struct GeneralStaticCommittee<T, LEAF: LeafType<NodeType = T>, PUBKEY: Signa
     committee_nodes: Vec<PUBKEY>,
     /// The nodes on the static committee and their stake
     committee_nodes_with_stake: Vec<PUBKEY::StakeTableEntry>,
+    /// Running total of each node's stake, if leaders are picked in proportion to stake
+    cumulative_stake: Option<Vec<u64>>,
+    /// Chain the committee votes on
+    chain_id: u64,
     /// Node type phantom
     _type_phantom: PhantomData<T>,
     /// Leaf phantom
@@ -36,6 +42,24 @@ pub struct GeneralStaticCommittee<T, LEAF: LeafType<NodeType = T>, PUBKEY: Signa
 /// static committee using a vrf kp
 pub type StaticCommittee<T, LEAF> = GeneralStaticCommittee<T, LEAF, BN254Pub>;
 
+/// The election state of a [`GeneralStaticCommittee`], as persisted across restarts
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = ""))]
+pub struct StaticCommitteeSnapshot<PUBKEY: SignatureKey> {
+    /// All the nodes participating
+    nodes: Vec<PUBKEY>,
+    /// All the nodes participating and their stake
+    nodes_with_stake: Vec<PUBKEY::StakeTableEntry>,
+    /// The nodes on the static committee
+    committee_nodes: Vec<PUBKEY>,
+    /// The nodes on the static committee and their stake
+    committee_nodes_with_stake: Vec<PUBKEY::StakeTableEntry>,
+    /// Running total of each node's stake, if leaders are picked in proportion to stake
+    cumulative_stake: Option<Vec<u64>>,
+    /// Chain the committee votes on
+    chain_id: u64,
+}
+
 impl<T, LEAF: LeafType<NodeType = T>, PUBKEY: SignatureKey>
     GeneralStaticCommittee<T, LEAF, PUBKEY>
 {
@@ -47,6 +71,8 @@ impl<T, LEAF: LeafType<NodeType = T>, PUBKEY: SignatureKey>
             nodes_with_stake: nodes_with_stake.clone(),
             committee_nodes: nodes,
             committee_nodes_with_stake: nodes_with_stake,
+            cumulative_stake: None,
+            chain_id: DEFAULT_CHAIN_ID,
             _type_phantom: PhantomData,
             _leaf_phantom: PhantomData,
         }
@@ -67,6 +93,11 @@ impl<PUBKEY: SignatureKey> VoteToken for StaticVoteToken<PUBKEY> {
     fn vote_count(&self) -> NonZeroU64 {
         NonZeroU64::new(1).unwrap()
     }
+
+    /// A static token without a signature proves nothing, so it carries no weight.
+    fn is_zero_weight(&self) -> bool {
+        self.signature.0.is_empty()
+    }
 }
 
 impl<PUBKEY: SignatureKey> Committable for StaticVoteToken<PUBKEY> {
@@ -82,14 +113,87 @@ impl<PUBKEY: SignatureKey> Committable for StaticVoteToken<PUBKEY> {
     }
 }
 
+/// Running total of `stakes`, or `None` if it does not fit in a `u64`
+fn cumulative_stake(stakes: &[u64]) -> Option<Vec<u64>> {
+    stakes
+        .iter()
+        .scan(Some(0u64), |total, stake| {
+            *total = total.and_then(|total| total.checked_add(*stake));
+            Some(*total)
+        })
+        .collect()
+}
+
 /// configuration for static committee. stub for now
 #[derive(Default, Clone, Serialize, Deserialize, core::fmt::Debug)]
 pub struct StaticElectionConfig {
     /// Number of nodes on the committee
     num_nodes: u64,
+    /// Number of nodes, taken from the front of the committee, on the DA committee
+    #[serde(default)]
+    da_committee_size: Option<usize>,
+    /// Stake of each node, used to weight leader selection
+    #[serde(default)]
+    stake_distribution: Option<Vec<u64>>,
+    /// Chain the committee votes on, so votes cannot be replayed on another chain
+    #[serde(default)]
+    chain_id: u64,
+}
+
+impl StaticElectionConfig {
+    /// Create a config for `num_nodes` nodes, the first `da_committee_size` of which form the DA
+    /// committee.
+    /// # Errors
+    /// If the DA committee would be larger than the full committee
+    pub fn new(num_nodes: u64, da_committee_size: Option<usize>) -> Result<Self, ElectionError> {
+        if let Some(da_committee_size) = da_committee_size {
+            if da_committee_size as u64 > num_nodes {
+                return Err(ElectionError::DACommitteeTooLarge {
+                    da_committee_size,
+                    num_nodes,
+                });
+            }
+        }
+        Ok(Self {
+            num_nodes,
+            da_committee_size,
+            stake_distribution: None,
+            chain_id: DEFAULT_CHAIN_ID,
+        })
+    }
+
+    /// Pick leaders in proportion to `stakes`, where node `i` holds `stakes[i]`.
+    /// # Errors
+    /// If the stakes sum to more than fits in a `u64`
+    pub fn with_stake_distribution(mut self, stakes: Vec<u64>) -> Result<Self, ElectionError> {
+        if cumulative_stake(&stakes).is_none() {
+            return Err(ElectionError::StakeOverflow);
+        }
+        self.stake_distribution = Some(stakes);
+        Ok(self)
+    }
+
+    /// Vote on the chain `chain_id` instead of [`DEFAULT_CHAIN_ID`].
+    #[must_use]
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
 }
 
-impl ElectionConfig for StaticElectionConfig {}
+impl ElectionConfig for StaticElectionConfig {
+    fn da_committee_config(&self) -> Self {
+        let num_nodes = self
+            .da_committee_size
+            .map_or(self.num_nodes, |size| (size as u64).min(self.num_nodes));
+        Self {
+            num_nodes,
+            da_committee_size: None,
+            stake_distribution: self.stake_distribution.clone(),
+            chain_id: self.chain_id,
+        }
+    }
+}
 
 impl<TYPES, LEAF: LeafType<NodeType = TYPES>, PUBKEY: SignatureKey + 'static> Membership<TYPES>
     for GeneralStaticCommittee<TYPES, LEAF, PUBKEY>
@@ -105,9 +209,17 @@ where
         self.committee_nodes_with_stake.clone()
     }
 
-    /// Index the vector of public keys with the current view number
+    /// Index the vector of public keys with the current view number, giving each node as many
+    /// consecutive views as it has stake if the election is stake weighted
     fn get_leader(&self, view_number: TYPES::Time) -> PUBKEY {
-        let index = (*view_number % self.nodes.len() as u64) as usize;
+        let index = match &self.cumulative_stake {
+            Some(cumulative_stake) => {
+                let total_stake = *cumulative_stake.last().unwrap();
+                let slot = *view_number % total_stake;
+                cumulative_stake.partition_point(|stake| *stake <= slot)
+            }
+            None => (*view_number % self.nodes.len() as u64) as usize,
+        };
         self.nodes[index].clone()
     }
 
@@ -148,7 +260,21 @@ where
     }
 
     fn default_election_config(num_nodes: u64) -> TYPES::ElectionConfigType {
-        StaticElectionConfig { num_nodes }
+        StaticElectionConfig {
+            num_nodes,
+            da_committee_size: None,
+            stake_distribution: None,
+            chain_id: DEFAULT_CHAIN_ID,
+        }
+    }
+
+    fn stake_weighted_election_config(stakes: &[u64]) -> TYPES::ElectionConfigType {
+        StaticElectionConfig {
+            num_nodes: stakes.len() as u64,
+            da_committee_size: None,
+            stake_distribution: Some(stakes.to_vec()),
+            chain_id: DEFAULT_CHAIN_ID,
+        }
     }
 
     fn create_election(
@@ -161,11 +287,50 @@ where
         committee_nodes.truncate(config.num_nodes.try_into().unwrap());
         debug!("Election Membership Size: {}", config.num_nodes);
         committee_nodes_with_stake.truncate(config.num_nodes.try_into().unwrap());
+        // Leaders only follow stake if every node has one and at least one is non-zero.
+        let cumulative_stake = config
+            .stake_distribution
+            .filter(|stakes| stakes.len() == keys.len() && stakes.iter().any(|stake| *stake > 0))
+            .and_then(|stakes| {
+                let cumulative_stake = cumulative_stake(&stakes);
+                if cumulative_stake.is_none() {
+                    error!("Stake distribution overflows; picking leaders round robin");
+                }
+                cumulative_stake
+            });
         Self {
             nodes_with_stake: keys_qc,
             nodes: keys,
             committee_nodes,
             committee_nodes_with_stake,
+            cumulative_stake,
+            chain_id: config.chain_id,
+            _type_phantom: PhantomData,
+            _leaf_phantom: PhantomData,
+        }
+    }
+
+    type Snapshot = StaticCommitteeSnapshot<PUBKEY>;
+
+    fn snapshot(&self) -> Self::Snapshot {
+        StaticCommitteeSnapshot {
+            nodes: self.nodes.clone(),
+            nodes_with_stake: self.nodes_with_stake.clone(),
+            committee_nodes: self.committee_nodes.clone(),
+            committee_nodes_with_stake: self.committee_nodes_with_stake.clone(),
+            cumulative_stake: self.cumulative_stake.clone(),
+            chain_id: self.chain_id,
+        }
+    }
+
+    fn restore(snapshot: Self::Snapshot) -> Self {
+        Self {
+            nodes: snapshot.nodes,
+            nodes_with_stake: snapshot.nodes_with_stake,
+            committee_nodes: snapshot.committee_nodes,
+            committee_nodes_with_stake: snapshot.committee_nodes_with_stake,
+            cumulative_stake: snapshot.cumulative_stake,
+            chain_id: snapshot.chain_id,
             _type_phantom: PhantomData,
             _leaf_phantom: PhantomData,
         }
@@ -183,6 +348,10 @@ where
         NonZeroU64::new(((self.committee_nodes.len() as u64) / 3) + 1).unwrap()
     }
 
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
     fn get_committee(
         &self,
         _view_number: <TYPES as NodeType>::Time,
@@ -241,6 +241,12 @@ where
 }
 
 impl<M: NetworkMsg, K: SignatureKey + 'static> Libp2pNetwork<M, K> {
+    /// The public key associated with this node
+    #[must_use]
+    pub fn public_key(&self) -> &K {
+        &self.inner.pk
+    }
+
     /// Returns when network is ready
     pub async fn wait_for_ready(&self) {
         loop {
@@ -607,15 +613,19 @@ impl<M: NetworkMsg, K: SignatureKey + 'static> ConnectedNetwork<M, K> for Libp2p
                 Ok(r) => r,
                 Err(e) => {
                     self.inner.metrics.message_failed_to_send.add(1);
-                    error!("Failed to message {:?} because could not find recipient peer id for pk {:?}", message, recipient);
-                    return Err(NetworkError::Libp2p { source: e });
+                    error!("Failed to message {:?} because could not find recipient peer id for pk {:?}: {:?}", message, recipient, e);
+                    return Err(NetworkError::Unreachable);
                 }
             }
         };
 
         if let Err(e) = self.inner.handle.lookup_pid(pid).await {
             self.inner.metrics.message_failed_to_send.add(1);
-            return Err(e.into());
+            error!(
+                "Failed to message {:?} because could not look up peer {:?}: {:?}",
+                recipient, pid, e
+            );
+            return Err(NetworkError::Unreachable);
         }
         match self.inner.handle.direct_request(pid, &message).await {
             Ok(()) => {
@@ -643,7 +653,7 @@ impl<M: NetworkMsg, K: SignatureKey + 'static> ConnectedNetwork<M, K> for Libp2p
                 Err(NetworkError::ShutDown)
             } else {
                 match transmit_type {
-                    TransmitType::Direct => {
+                    TransmitType::Direct | TransmitType::DirectWithFallbackBroadcast => {
                         let result = self
                             .inner
                             .direct_recv
@@ -800,6 +810,10 @@ where
 {
     type NETWORK = Libp2pNetwork<Message<TYPES, I>, TYPES::SignatureKey>;
 
+    fn local_public_key(&self) -> TYPES::SignatureKey {
+        self.0.public_key().clone()
+    }
+
     async fn wait_for_ready(&self) {
         self.0.wait_for_ready().await;
     }
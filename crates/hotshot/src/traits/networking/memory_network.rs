@@ -22,7 +22,7 @@ use hotshot_types::{
         election::Membership,
         metrics::{Metrics, NoMetrics},
         network::{
-            CommunicationChannel, ConnectedNetwork, ConsensusIntentEvent, NetworkMsg,
+            count_sent, CommunicationChannel, ConnectedNetwork, ConsensusIntentEvent, NetworkMsg,
             TestableChannelImplementation, TestableNetworkingImplementation, TransmitType,
             ViewMessage,
         },
@@ -91,6 +91,8 @@ enum Combo<T> {
 
 /// Internal state for a `MemoryNetwork` instance
 struct MemoryNetworkInner<M: NetworkMsg, K: SignatureKey> {
+    /// Our own key
+    pub_key: K,
     /// Input for broadcast messages
     broadcast_input: RwLock<Option<Sender<Vec<u8>>>>,
     /// Input for direct messages
@@ -245,6 +247,7 @@ impl<M: NetworkMsg, K: SignatureKey> MemoryNetwork<M, K> {
         trace!("Task spawned, creating MemoryNetwork");
         let mn = MemoryNetwork {
             inner: Arc::new(MemoryNetworkInner {
+                pub_key: pub_key.clone(),
                 broadcast_input: RwLock::new(Some(broadcast_input)),
                 direct_input: RwLock::new(Some(direct_input)),
                 broadcast_output: Mutex::new(broadcast_output),
@@ -287,6 +290,12 @@ impl<M: NetworkMsg, K: SignatureKey> MemoryNetwork<M, K> {
             Err(SendError(message))
         }
     }
+
+    /// The public key this network was attached to the group with
+    #[must_use]
+    pub fn public_key(&self) -> &K {
+        &self.inner.pub_key
+    }
 }
 
 impl<TYPES: NodeType, I: NodeImplementation<TYPES>>
@@ -414,7 +423,7 @@ impl<M: NetworkMsg, K: SignatureKey + 'static> ConnectedNetwork<M, K> for Memory
     {
         let closure = async move {
             match transmit_type {
-                TransmitType::Direct => {
+                TransmitType::Direct | TransmitType::DirectWithFallbackBroadcast => {
                     let ret = self
                         .inner
                         .direct_output
@@ -538,6 +547,10 @@ where
 {
     type NETWORK = MemoryNetwork<Message<TYPES, I>, TYPES::SignatureKey>;
 
+    fn local_public_key(&self) -> TYPES::SignatureKey {
+        self.0.public_key().clone()
+    }
+
     async fn wait_for_ready(&self) {
         self.0.wait_for_ready().await;
     }
@@ -577,6 +590,20 @@ where
         self.0.direct_message(message, recipient).await
     }
 
+    async fn direct_message_batch(
+        &self,
+        messages: Vec<(Message<TYPES, I>, TYPES::SignatureKey)>,
+    ) -> Result<usize, NetworkError> {
+        // Deliveries to different nodes are independent, so hand them all off at once.
+        let results = futures::future::join_all(
+            messages
+                .into_iter()
+                .map(|(message, recipient)| self.0.direct_message(message, recipient)),
+        )
+        .await;
+        count_sent(results)
+    }
+
     fn recv_msgs<'a, 'b>(
         &'a self,
         transmit_type: TransmitType,
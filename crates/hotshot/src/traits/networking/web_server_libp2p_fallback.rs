@@ -166,6 +166,10 @@ impl<
 {
     type NETWORK = CombinedNetworks<TYPES, I, MEMBERSHIP>;
 
+    fn local_public_key(&self) -> TYPES::SignatureKey {
+        self.network().public_key().clone()
+    }
+
     async fn wait_for_ready(&self) {
         join!(
             self.network().wait_for_ready(),
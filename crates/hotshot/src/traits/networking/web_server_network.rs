@@ -83,6 +83,12 @@ pub struct WebServerNetwork<M: NetworkMsg, KEY: SignatureKey, TYPES: NodeType> {
 }
 
 impl<M: NetworkMsg, KEY: SignatureKey, TYPES: NodeType> WebServerNetwork<M, KEY, TYPES> {
+    /// Our own key
+    #[must_use]
+    pub fn public_key(&self) -> &TYPES::SignatureKey {
+        &self.inner.own_key
+    }
+
     /// Post a message to the web server and return the result
     async fn post_message_to_web_server(&self, message: SendMsg<M>) -> Result<(), NetworkError> {
         let result: Result<(), ClientError> = self
@@ -106,7 +112,7 @@ struct Inner<M: NetworkMsg, KEY: SignatureKey, TYPES: NodeType> {
     /// Phantom data for generic types
     phantom: PhantomData<(KEY, TYPES::ElectionConfigType)>,
     /// Our own key
-    _own_key: TYPES::SignatureKey,
+    own_key: TYPES::SignatureKey,
     /// Queue for broadcasted messages
     broadcast_poll_queue: Arc<RwLock<Vec<RecvMsg<M>>>>,
     /// Queue for direct messages
@@ -472,7 +478,7 @@ impl<
             connected: AtomicBool::new(false),
             client,
             wait_between_polls,
-            _own_key: key,
+            own_key: key,
             is_da: is_da_server,
             tx_index: Arc::default(),
             proposal_task_map: Arc::default(),
@@ -528,6 +534,11 @@ impl<
     for WebCommChannel<TYPES, I, PROPOSAL, VOTE, MEMBERSHIP>
 {
     type NETWORK = WebServerNetwork<Message<TYPES, I>, TYPES::SignatureKey, TYPES>;
+
+    fn local_public_key(&self) -> TYPES::SignatureKey {
+        self.0.public_key().clone()
+    }
+
     /// Blocks until node is successfully initialized
     /// into the network
     async fn wait_for_ready(&self) {
@@ -702,7 +713,7 @@ impl<
     {
         let closure = async move {
             match transmit_type {
-                TransmitType::Direct => {
+                TransmitType::Direct | TransmitType::DirectWithFallbackBroadcast => {
                     let mut queue = self.inner.direct_poll_queue.write().await;
                     Ok(queue
                         .drain(..)
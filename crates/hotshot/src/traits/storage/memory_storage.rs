@@ -125,8 +125,8 @@ mod test {
         traits::{
             block_contents::dummy::{DummyBlock, DummyState},
             node_implementation::NodeType,
-            state::ConsensusTime,
-            Block,
+            state::{ConsensusTime, TestableBlock},
+            Block, State,
         },
     };
     use std::{fmt::Debug, hash::Hash};
@@ -155,6 +155,14 @@ mod test {
         type Transaction = <DummyBlock as Block>::Transaction;
         type ElectionConfigType = StaticElectionConfig;
         type StateType = DummyState;
+
+        fn genesis() -> (Self::BlockType, Self::StateType) {
+            let block = <DummyBlock as TestableBlock>::genesis();
+            let state = DummyState::default()
+                .append(&block, &ViewNumber::genesis())
+                .unwrap();
+            (block, state)
+        }
     }
 
     #[instrument(skip(rng))]
@@ -22,7 +22,6 @@ use hotshot_types::{
     traits::{
         election::{ConsensusExchange, QuorumExchangeType, SignedCertificate},
         node_implementation::{ExchangesType, NodeType, QuorumEx},
-        state::ConsensusTime,
         storage::Storage,
     },
 };
@@ -188,12 +187,13 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES> + 'static> SystemContextHandl
     pub async fn maybe_do_genesis_init(&self) {
         let _anchor = self.storage();
         if let Ok(anchor_leaf) = self.storage().get_anchored_view().await {
-            if anchor_leaf.view_number == TYPES::Time::genesis() {
+            if anchor_leaf.justify_qc.is_genesis() {
+                let view_number = anchor_leaf.view_number;
                 let leaf: I::Leaf = I::Leaf::from_stored_view(anchor_leaf);
-                let mut qc = QuorumCertificate::<TYPES, I::Leaf>::genesis();
+                let mut qc = QuorumCertificate::<TYPES, I::Leaf>::genesis_at(view_number);
                 qc.set_leaf_commitment(leaf.commit());
                 let event = Event {
-                    view_number: TYPES::Time::genesis(),
+                    view_number,
                     event: EventType::Decide {
                         leaf_chain: Arc::new(vec![leaf]),
                         qc: Arc::new(qc),
@@ -1,7 +1,8 @@
 use hotshot_types::{ExecutionType, HotShotConfig};
+use snafu::Snafu;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
-    num::NonZeroUsize,
+    num::{NonZeroU64, NonZeroUsize},
     time::Duration,
 };
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -64,12 +65,27 @@ pub struct NetworkConfig<KEY, ENTRY, ELECTIONCONFIG> {
     pub start_delay_seconds: u64,
     pub key_type_name: String,
     pub election_config_type_name: String,
+    /// Stake of each node, by node index; every node has a stake of 1 if unset
+    #[serde(default)]
+    pub stake_distribution: Option<Vec<u64>>,
     pub libp2p_config: Option<Libp2pConfig>,
     pub config: HotShotConfig<KEY, ENTRY, ELECTIONCONFIG>,
     pub web_server_config: Option<WebServerConfig>,
     pub da_web_server_config: Option<WebServerConfig>,
 }
 
+impl<K, ENTRY, E> NetworkConfig<K, ENTRY, E> {
+    /// The declared stake of the node at `node_index`, defaulting to 1 when no stake
+    /// distribution is configured. A validated file lists a stake for every node.
+    #[must_use]
+    pub fn stake_of(&self, node_index: u64) -> u64 {
+        usize::try_from(node_index)
+            .ok()
+            .and_then(|index| self.stake_distribution.as_ref()?.get(index).copied())
+            .unwrap_or(1)
+    }
+}
+
 impl<K, ENTRY, E> Default for NetworkConfig<K, ENTRY, E> {
     fn default() -> Self {
         Self {
@@ -78,6 +94,7 @@ impl<K, ENTRY, E> Default for NetworkConfig<K, ENTRY, E> {
             node_index: 0,
             seed: [0u8; 32],
             padding: default_padding(),
+            stake_distribution: None,
             libp2p_config: None,
             config: default_config().into(),
             start_delay_seconds: 60,
@@ -103,6 +120,9 @@ pub struct NetworkConfigFile {
     pub padding: usize,
     #[serde(default = "default_start_delay_seconds")]
     pub start_delay_seconds: u64,
+    /// Stake of each node, by node index; every node has a stake of 1 if unset
+    #[serde(default)]
+    pub stake_distribution: Option<Vec<u64>>,
     #[serde(default)]
     pub libp2p_config: Option<Libp2pConfigFile>,
     #[serde(default = "default_config")]
@@ -113,6 +133,38 @@ pub struct NetworkConfigFile {
     pub da_web_server_config: Option<WebServerConfig>,
 }
 
+/// A network configuration file that cannot be used to run a network
+#[derive(Debug, Snafu)]
+pub enum NetworkConfigError {
+    /// `stake_distribution` does not give exactly one stake per node
+    #[snafu(display("stake_distribution lists {listed} stakes for {total_nodes} nodes"))]
+    StakeDistributionLength {
+        /// Number of stakes listed
+        listed: usize,
+        /// Number of nodes in the network
+        total_nodes: usize,
+    },
+}
+
+impl NetworkConfigFile {
+    /// Check that the file describes a network that can be run.
+    ///
+    /// # Errors
+    /// Returns an error if `stake_distribution` is set but does not list a stake for every node.
+    pub fn validate(&self) -> Result<(), NetworkConfigError> {
+        let total_nodes = self.config.total_nodes.get();
+        match &self.stake_distribution {
+            Some(stakes) if stakes.len() != total_nodes => {
+                Err(NetworkConfigError::StakeDistributionLength {
+                    listed: stakes.len(),
+                    total_nodes,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 fn default_web_server_config() -> Option<WebServerConfig> {
     None
 }
@@ -125,6 +177,7 @@ impl<K, ENTRY, E> From<NetworkConfigFile> for NetworkConfig<K, ENTRY, E> {
             node_index: 0,
             seed: val.seed,
             padding: val.padding,
+            stake_distribution: val.stake_distribution,
             libp2p_config: val.libp2p_config.map(|libp2p_config| Libp2pConfig {
                 num_bootstrap_nodes: libp2p_config.num_bootstrap_nodes,
                 index_ports: libp2p_config.index_ports,
@@ -181,6 +234,46 @@ pub struct HotShotConfigFile {
     pub propose_min_round_time: Duration,
     /// The maximum amount of time a leader can wait to start a round
     pub propose_max_round_time: Duration,
+    /// Maximum number of views buffered in the consensus state map
+    #[serde(default)]
+    pub max_buffered_views: Option<NonZeroUsize>,
+    /// How long the leader waits before proposing an empty block if it could not wait for
+    /// transactions
+    #[serde(default)]
+    pub propose_empty_after: Option<Duration>,
+    /// Whether a leader that could not wait for transactions may propose an empty block
+    #[serde(default)]
+    pub allow_empty_blocks: bool,
+    /// Views in a row a leader may propose an empty block before it stops
+    #[serde(default = "default_max_empty_fallbacks")]
+    pub max_empty_fallbacks: NonZeroUsize,
+    /// Number of threads dedicated to signature verification
+    #[serde(default)]
+    pub verification_threads: Option<NonZeroUsize>,
+    /// Number of vote signature checks to cache
+    #[serde(default)]
+    pub signature_cache_size: Option<NonZeroUsize>,
+    /// Maximum number of proposals assembled at once
+    #[serde(default)]
+    pub max_inflight_proposals: Option<NonZeroUsize>,
+    /// Number of proposal retries before a view is abandoned
+    #[serde(default)]
+    pub max_view_retries: Option<NonZeroUsize>,
+    /// How long the DA leader waits for DA votes before abandoning its block
+    #[serde(default)]
+    pub da_availability_timeout: Option<Duration>,
+    /// Number of relays a view sync round tries before failing
+    #[serde(default)]
+    pub max_relay_attempts: Option<NonZeroU64>,
+    /// Whether DA votes may arrive through nodes outside the DA committee
+    #[serde(default)]
+    pub relay_da_votes: bool,
+    /// Whether nodes drop messages they sent themselves when they come back
+    #[serde(default)]
+    pub drop_own_messages: bool,
+    /// Number of votes a vote collection task lets wait for it
+    #[serde(default)]
+    pub vote_queue_capacity: Option<NonZeroUsize>,
 }
 
 impl<K, ENTRY, E> From<HotShotConfigFile> for HotShotConfig<K, ENTRY, E> {
@@ -200,6 +293,19 @@ impl<K, ENTRY, E> From<HotShotConfigFile> for HotShotConfig<K, ENTRY, E> {
             num_bootstrap: val.num_bootstrap,
             propose_min_round_time: val.propose_min_round_time,
             propose_max_round_time: val.propose_max_round_time,
+            max_buffered_views: val.max_buffered_views,
+            propose_empty_after: val.propose_empty_after,
+            allow_empty_blocks: val.allow_empty_blocks,
+            max_empty_fallbacks: val.max_empty_fallbacks,
+            verification_threads: val.verification_threads,
+            signature_cache_size: val.signature_cache_size,
+            max_inflight_proposals: val.max_inflight_proposals,
+            max_view_retries: val.max_view_retries,
+            da_availability_timeout: val.da_availability_timeout,
+            max_relay_attempts: val.max_relay_attempts,
+            relay_da_votes: val.relay_da_votes,
+            drop_own_messages: val.drop_own_messages,
+            vote_queue_capacity: val.vote_queue_capacity,
             election_config: None,
         }
     }
@@ -215,6 +321,9 @@ fn default_transactions_per_round() -> usize {
 fn default_padding() -> usize {
     100
 }
+fn default_max_empty_fallbacks() -> NonZeroUsize {
+    NonZeroUsize::new(3).unwrap()
+}
 fn default_config() -> HotShotConfigFile {
     HotShotConfigFile {
         total_nodes: NonZeroUsize::new(10).unwrap(),
@@ -227,6 +336,19 @@ fn default_config() -> HotShotConfigFile {
         start_delay: 1,
         propose_min_round_time: Duration::from_secs(0),
         propose_max_round_time: Duration::from_secs(10),
+        max_buffered_views: None,
+        propose_empty_after: None,
+        allow_empty_blocks: false,
+        max_empty_fallbacks: default_max_empty_fallbacks(),
+        verification_threads: None,
+        signature_cache_size: None,
+        max_inflight_proposals: None,
+        max_view_retries: None,
+        da_availability_timeout: None,
+        max_relay_attempts: None,
+        relay_da_votes: false,
+        drop_own_messages: false,
+        vote_queue_capacity: None,
         num_bootstrap: 5,
     }
 }
@@ -7,8 +7,7 @@ use async_lock::{RwLock, RwLockUpgradableReadGuard};
 #[cfg(async_executor_impl = "async-std")]
 use async_std::task::JoinHandle;
 use bincode::Options;
-use bitvec::prelude::*;
-use commit::Committable;
+use commit::{Commitment, Committable};
 use core::time::Duration;
 use either::{Either, Left, Right};
 use futures::FutureExt;
@@ -19,8 +18,8 @@ use hotshot_task::{
     task_impls::{HSTWithEvent, TaskBuilder},
 };
 use hotshot_types::{
-    certificate::{DACertificate, QuorumCertificate},
-    consensus::{Consensus, View},
+    certificate::{DACertificate, QuorumCertificate, TimeoutCertificate},
+    consensus::{Consensus, View, ViewStatus},
     data::{LeafType, ProposalType, QuorumProposal, SequencingLeaf},
     event::{Event, EventType},
     message::{GeneralConsensusMessage, Message, Proposal, SequencingMessage},
@@ -41,16 +40,33 @@ use snafu::Snafu;
 use std::{
     collections::{HashMap, HashSet},
     marker::PhantomData,
-    sync::Arc,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 #[cfg(async_executor_impl = "tokio")]
 use tokio::task::JoinHandle;
-use tracing::{debug, error, instrument};
+use tracing::{debug, error, instrument, warn};
 
 /// Error returned by the consensus task
 #[derive(Snafu, Debug)]
 pub struct ConsensusTaskError {}
 
+/// Why the leader could not propose in a view
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProposalFailure {
+    /// This node is not the leader of the view
+    NotLeader,
+    /// The view of the high QC is not in the state map
+    ParentViewMissing,
+    /// The view of the high QC has no proposal
+    ParentProposalMissing,
+    /// The leaf the high QC points to has not been saved
+    ParentLeafMissing,
+}
+
 /// The state for the consensus task.  Contains all of the information for the implementation
 /// of consensus
 pub struct SequencingConsensusTaskState<
@@ -129,6 +145,106 @@ pub struct SequencingConsensusTaskState<
 
     /// The most Recent QC we've formed from votes, if we've formed it.
     pub qc: Option<QuorumCertificate<TYPES, I::Leaf>>,
+
+    /// Number of times to retry a failed proposal in a view before abandoning the view;
+    /// `None` means unbounded
+    pub max_view_retries: Option<NonZeroUsize>,
+
+    /// Failed proposal attempts for each view this node has not yet moved past
+    pub proposal_failures: HashMap<TYPES::Time, usize>,
+
+    /// Votes waiting for the current vote collection task, and the votes dropped because too
+    /// many were waiting
+    pub vote_queue: VoteQueue,
+}
+
+/// Votes let into a vote collection task's channel that the task has not yet taken.
+///
+/// The channel itself is unbounded, so [`Self::filter`] bounds it: it lets a vote in only while
+/// fewer than the capacity are waiting, and drops and counts the rest. By the time that many
+/// votes are waiting the leader most likely has enough to form its certificate, so the excess is
+/// useless anyway. Clones share the counts.
+#[derive(Clone, Debug, Default)]
+pub struct VoteQueue {
+    /// Most votes let wait for the task; `None` means unbounded
+    capacity: Option<NonZeroUsize>,
+    /// Votes let in and not yet taken by the task
+    queued: Arc<AtomicUsize>,
+    /// Votes dropped because the queue was full, across every collection task
+    dropped: Arc<AtomicUsize>,
+}
+
+impl VoteQueue {
+    /// A queue letting at most `capacity` votes wait, or any number if `None`
+    #[must_use]
+    pub fn new(capacity: Option<NonZeroUsize>) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// An empty queue for the next vote collection task, still counting into these drops
+    #[must_use]
+    pub fn for_next_collector(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            queued: Arc::default(),
+            dropped: self.dropped.clone(),
+        }
+    }
+
+    /// Votes waiting for the task
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Acquire)
+    }
+
+    /// Votes dropped because the queue was full
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Count a vote into the queue, or count it as dropped if the queue is full
+    fn admit(&self) -> bool {
+        let Some(capacity) = self.capacity else {
+            self.queued.fetch_add(1, Ordering::AcqRel);
+            return true;
+        };
+        let admitted = self
+            .queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |queued| {
+                (queued < capacity.get()).then_some(queued + 1)
+            })
+            .is_ok();
+        if !admitted {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        admitted
+    }
+
+    /// Stop counting a vote once the task has taken it from its channel
+    pub fn vote_taken(&self) {
+        // An `Err` means nothing was counted, e.g. the vote bypassed `filter`.
+        let _ = self
+            .queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |queued| {
+                queued.checked_sub(1)
+            });
+    }
+
+    /// The quorum vote collection task's filter, letting votes in through this queue
+    pub fn filter<TYPES: NodeType, I: NodeImplementation<TYPES>>(
+        &self,
+    ) -> FilterEvent<SequencingHotShotEvent<TYPES, I>> {
+        let queue = self.clone();
+        FilterEvent(Arc::new(move |event| match event {
+            SequencingHotShotEvent::QuorumVoteRecv(_) => queue.admit(),
+            SequencingHotShotEvent::QuorumProposalRecv(_, _) => true,
+            _ => false,
+        }))
+    }
 }
 
 /// State for the vote collection task.  This handles the building of a QC from a votes received
@@ -150,12 +266,18 @@ pub struct VoteCollectionTaskState<
     /// Accumulator for votes
     pub accumulator:
         Either<VoteAccumulator<TYPES::VoteTokenType, I::Leaf>, QuorumCertificate<TYPES, I::Leaf>>,
+    #[allow(clippy::type_complexity)]
+    /// Accumulator for timeout votes
+    pub timeout_accumulator:
+        Either<VoteAccumulator<TYPES::VoteTokenType, TYPES::Time>, TimeoutCertificate<TYPES>>,
     /// View which this vote collection task is collecting votes in
     pub cur_view: TYPES::Time,
     /// The event stream shared by all tasks
     pub event_stream: ChannelStream<SequencingHotShotEvent<TYPES, I>>,
     /// Node id
     pub id: u64,
+    /// The votes waiting for this task
+    pub vote_queue: VoteQueue,
 }
 
 impl<TYPES: NodeType, I: NodeImplementation<TYPES, Leaf = SequencingLeaf<TYPES>>> TS
@@ -171,9 +293,12 @@ where
 {
 }
 
+/// Handle an event for the quorum vote collection task
 #[instrument(skip_all, fields(id = state.id, view = *state.cur_view), name = "Quorum Vote Collection Task", level = "error")]
-
-async fn vote_handle<TYPES: NodeType, I: NodeImplementation<TYPES, Leaf = SequencingLeaf<TYPES>>>(
+pub async fn vote_handle<
+    TYPES: NodeType,
+    I: NodeImplementation<TYPES, Leaf = SequencingLeaf<TYPES>>,
+>(
     mut state: VoteCollectionTaskState<TYPES, I>,
     event: SequencingHotShotEvent<TYPES, I>,
 ) -> (
@@ -189,6 +314,9 @@ where
         Commitment = SequencingLeaf<TYPES>,
     >,
 {
+    if matches!(event, SequencingHotShotEvent::QuorumVoteRecv(_)) {
+        state.vote_queue.vote_taken();
+    }
     // TODO ED Emit a view change event upon new proposal?
     match event {
         SequencingHotShotEvent::QuorumVoteRecv(vote) => match vote {
@@ -207,16 +335,25 @@ where
                 }
 
                 let accumulator = state.accumulator.left().unwrap();
-                match state.quorum_exchange.accumulate_vote(
-                    &vote.signature.0,
-                    &vote.signature.1,
-                    vote.leaf_commitment,
-                    vote.vote_data,
-                    vote.vote_token.clone(),
-                    state.cur_view,
-                    accumulator,
-                    None,
-                ) {
+                let exchange = state.quorum_exchange.clone();
+                let cur_view = state.cur_view;
+                let accumulated = state
+                    .quorum_exchange
+                    .verification_pool()
+                    .offload(move || {
+                        exchange.accumulate_vote(
+                            &vote.signature.0,
+                            &vote.signature.1,
+                            vote.leaf_commitment,
+                            vote.vote_data,
+                            vote.vote_token.clone(),
+                            cur_view,
+                            accumulator,
+                            None,
+                        )
+                    })
+                    .await;
+                match accumulated {
                     Either::Left(acc) => {
                         state.accumulator = Either::Left(acc);
                         return (None, state);
@@ -242,14 +379,75 @@ where
                     }
                 }
             }
-            QuorumVote::Timeout(_vote) => {
-                error!("The next leader has received an unexpected vote!");
-                return (None, state);
+            QuorumVote::Timeout(vote) => {
+                // For the case where we receive votes after we've made a certificate
+                if state.timeout_accumulator.is_right() {
+                    return (None, state);
+                }
+
+                if vote.current_view != state.cur_view {
+                    error!(
+                        "Vote view does not match! vote view is {} current view is {}",
+                        *vote.current_view, *state.cur_view
+                    );
+                    return (None, state);
+                }
+
+                let accumulator = state.timeout_accumulator.left().unwrap();
+                let exchange = state.quorum_exchange.clone();
+                let accumulated = state
+                    .quorum_exchange
+                    .verification_pool()
+                    .offload(move || exchange.accumulate_timeout_vote(&vote, accumulator))
+                    .await;
+                match accumulated {
+                    Either::Left(acc) => {
+                        state.timeout_accumulator = Either::Left(acc);
+                        return (None, state);
+                    }
+                    Either::Right(certificate) => {
+                        debug!("TimeoutCertFormed! {:?}", certificate.view_number);
+                        state
+                            .event_stream
+                            .publish(SequencingHotShotEvent::TimeoutCertFormed(
+                                certificate.clone(),
+                            ))
+                            .await;
+                        state.timeout_accumulator = Either::Right(certificate.clone());
+
+                        // No longer need to poll for votes
+                        state
+                            .quorum_exchange
+                            .network()
+                            .inject_consensus_info(ConsensusIntentEvent::CancelPollForVotes(
+                                *certificate.view_number,
+                            ))
+                            .await;
+
+                        return (Some(HotShotTaskCompleted::ShutDown), state);
+                    }
+                }
             }
             QuorumVote::No(_) => {
                 error!("The next leader has received an unexpected vote!");
             }
         },
+        // We propose the view after the one we collect votes for, so only a proposal beyond that
+        // means the network has moved on without us.
+        SequencingHotShotEvent::QuorumProposalRecv(proposal, _) => {
+            let view = proposal.data.get_view_number();
+            if view > state.cur_view + 1 && state.accumulator.is_left() {
+                warn!(
+                    "Received quorum proposal for view {:?} while collecting votes for view {:?}",
+                    view, state.cur_view
+                );
+                state
+                    .event_stream
+                    .publish(SequencingHotShotEvent::FellBehind(view))
+                    .await;
+                return (Some(HotShotTaskCompleted::ShutDown), state);
+            }
+        }
         SequencingHotShotEvent::Shutdown => {
             return (Some(HotShotTaskCompleted::ShutDown), state);
         }
@@ -287,30 +485,52 @@ where
     async fn genesis_leaf(&self) -> Option<SequencingLeaf<TYPES>> {
         let consensus = self.consensus.read().await;
 
-        let Some(genesis_view) = consensus.state_map.get(&TYPES::Time::genesis()) else {
-            error!("Couldn't find genesis view in state map.");
-            return None;
-        };
-        let Some(leaf) = genesis_view.get_leaf_commitment() else {
+        let Some(leaf) = consensus.leaf_for_view(consensus.start_view) else {
             error!(
-                ?genesis_view,
-                "Genesis view points to a view without a leaf"
+                status = ?consensus.view_status(consensus.start_view),
+                "Failed to find genesis leaf."
             );
             return None;
         };
-        let Some(leaf) = consensus.saved_leaves.get(&leaf) else {
-            error!("Failed to find genesis leaf.");
-            return None;
-        };
         Some(leaf.clone())
     }
 
+    /// Whether `cert` certifies `block_commitment`, checked on the verification pool
+    async fn is_valid_dac(
+        &self,
+        cert: &DACertificate<TYPES>,
+        block_commitment: Commitment<TYPES::BlockType>,
+    ) -> bool {
+        let exchange = self.committee_exchange.clone();
+        let cert = cert.clone();
+        self.committee_exchange
+            .verification_pool()
+            .offload(move || exchange.is_valid_cert(&cert, block_commitment))
+            .await
+    }
+
+    /// Whether `qc` certifies `leaf_commitment`, checked on the verification pool
+    async fn is_valid_qc(
+        &self,
+        qc: &QuorumCertificate<TYPES, SequencingLeaf<TYPES>>,
+        leaf_commitment: Commitment<SequencingLeaf<TYPES>>,
+    ) -> bool {
+        let exchange = self.quorum_exchange.clone();
+        let qc = qc.clone();
+        self.quorum_exchange
+            .verification_pool()
+            .offload(move || exchange.is_valid_cert(&qc, leaf_commitment))
+            .await
+    }
+
     #[instrument(skip_all, fields(id = self.id, view = *self.cur_view), name = "Consensus vote if able", level = "error")]
 
     async fn vote_if_able(&self) -> bool {
         if let Some(proposal) = &self.current_proposal {
             // ED Need to account for the genesis DA cert
-            if proposal.justify_qc.is_genesis() && proposal.view_number == TYPES::Time::new(1) {
+            if proposal.justify_qc.is_genesis()
+                && proposal.view_number == proposal.justify_qc.view_number() + 1
+            {
                 // warn!("Proposal is genesis!");
 
                 let view = TYPES::Time::new(*proposal.view_number);
@@ -379,10 +599,15 @@ where
                 }
             }
 
-            // Only vote if you have the DA cert
-            // ED Need to update the view number this is stored under?
-            if let Some(cert) = self.certs.get(&(proposal.get_view_number())) {
-                let view = cert.view_number;
+            // Only vote if you have the DA cert. A leader re-proposing a block after a failed view
+            // reuses the cert from the view it formed in, so also look for it by block commitment.
+            let view = proposal.get_view_number();
+            let cert = self.certs.get(&view).or_else(|| {
+                self.certs
+                    .values()
+                    .find(|cert| cert.block_commitment == proposal.block_commitment)
+            });
+            if let Some(cert) = cert {
                 let vote_token = self.quorum_exchange.make_vote_token(view);
                 // TODO: do some of this logic without the vote token check, only do that when voting.
                 match vote_token {
@@ -425,16 +650,14 @@ where
                             timestamp: time::OffsetDateTime::now_utc().unix_timestamp_nanos(),
                             proposer_id: self.quorum_exchange.get_leader(view).to_bytes(),
                         };
+                        let dac_valid = self.is_valid_dac(cert, proposal.block_commitment).await;
                         let message: GeneralConsensusMessage<TYPES, I>=
                         // Validate the DAC.
-                        if self
-                            .committee_exchange
-                            .is_valid_cert(cert, proposal.block_commitment)
-                        {
+                        if dac_valid {
                             self.quorum_exchange.create_yes_message(
                                 proposal.justify_qc.commit(),
                                 leaf.commit(),
-                                cert.view_number,
+                                view,
                                 vote_token)
                         } else {
                             error!("Invalid DAC in proposal! Skipping proposal. {:?} cur view is: {:?}", cert.view_number, self.cur_view );
@@ -487,6 +710,7 @@ where
             // }
             self.cur_view = new_view;
             self.current_proposal = None;
+            self.proposal_failures.retain(|view, _| *view >= new_view);
 
             // Start polling for proposals for the new view
             self.quorum_exchange
@@ -553,6 +777,27 @@ where
                     return;
                 }
 
+                // A leader may bundle the DA cert with its proposal instead of sending it separately.
+                // It must certify this proposal's block, and never replaces a cert we already hold.
+                if let Some(cert) = &proposal.data.dac {
+                    if cert.view_number != view {
+                        warn!(
+                            "Ignoring DA cert for view {:?} bundled with proposal for view {:?}",
+                            cert.view_number, view
+                        );
+                    } else if !self
+                        .is_valid_dac(cert, proposal.data.block_commitment)
+                        .await
+                    {
+                        warn!(
+                            "Ignoring invalid DA cert bundled with proposal for view {:?}",
+                            view
+                        );
+                    } else {
+                        self.certs.entry(view).or_insert_with(|| cert.clone());
+                    }
+                }
+
                 self.current_proposal = Some(proposal.data.clone());
 
                 let vote_token = self.quorum_exchange.make_vote_token(view);
@@ -603,12 +848,23 @@ where
                         };
                         let justify_qc_commitment = justify_qc.commit();
                         let leaf_commitment = leaf.commit();
+                        let exchange = self.quorum_exchange.clone();
+                        let leader_key = view_leader_key.clone();
+                        let signature = proposal.signature.clone();
+                        let signature_valid = self
+                            .quorum_exchange
+                            .verification_pool()
+                            .offload(move || {
+                                exchange.is_valid_proposal_signature(
+                                    &leader_key,
+                                    leaf_commitment,
+                                    &signature,
+                                )
+                            })
+                            .await;
 
                         // Validate the `justify_qc`.
-                        if !self
-                            .quorum_exchange
-                            .is_valid_cert(&justify_qc, parent_commitment)
-                        {
+                        if !self.is_valid_qc(&justify_qc, parent_commitment).await {
                             error!("Invalid justify_qc in proposal!. parent commitment is {:?} justify qc is {:?}", parent_commitment, justify_qc.clone());
 
                             message = self.quorum_exchange.create_no_message::<I>(
@@ -633,9 +889,7 @@ where
                             );
                         }
                         // Validate the signature.
-                        else if !view_leader_key
-                            .validate(&proposal.signature, leaf_commitment.as_ref())
-                        {
+                        else if !signature_valid {
                             error!(?proposal.signature, "Could not verify proposal.");
                             message = self.quorum_exchange.create_no_message(
                                 justify_qc_commitment,
@@ -696,6 +950,7 @@ where
                         let mut new_decide_qc = None;
                         let mut leaf_views = Vec::new();
                         let mut included_txns = HashSet::new();
+                        let mut included_txn_views = Vec::new();
                         let old_anchor_view = consensus.last_decided_view;
                         let parent_view = leaf.justify_qc.view_number;
                         let mut current_chain_length = 0usize;
@@ -744,6 +999,8 @@ where
                                     match &leaf.deltas {
                                         Left(block) => {
                                             let txns = block.contained_transactions();
+                                            included_txn_views
+                                                .push((leaf.view_number, txns.clone()));
                                             for txn in txns {
                                                 included_txns.insert(txn);
                                             }
@@ -773,15 +1030,17 @@ where
                         if high_qc.view_number > consensus.high_qc.view_number {
                             consensus.high_qc = high_qc;
                         }
-                        consensus.state_map.insert(
-                            view,
-                            View {
-                                view_inner: ViewInner::Leaf {
-                                    leaf: leaf.commit(),
+                        match consensus.insert_leaf(leaf.clone()) {
+                            Ok(()) => consensus.insert_view(
+                                view,
+                                View {
+                                    view_inner: ViewInner::Leaf {
+                                        leaf: leaf.commit(),
+                                    },
                                 },
-                            },
-                        );
-                        consensus.saved_leaves.insert(leaf.commit(), leaf.clone());
+                            ),
+                            Err(conflict) => error!("Leader equivocated: {conflict}"),
+                        }
                         if new_commit_reached {
                             consensus.locked_view = new_locked_view;
                         }
@@ -797,23 +1056,23 @@ where
                                 }
                             });
                             drop(txns);
+                            for (txn_view, txns) in included_txn_views {
+                                consensus.index_transactions(txn_view, txns);
+                            }
                             consensus
                                 .transactions
                                 .modify(|txns| {
-                                    *txns = txns
-                                        .drain()
-                                        .filter(|(txn_hash, txn)| {
-                                            if included_txns_set.contains(txn_hash) {
-                                                included_txn_count += 1;
-                                                included_txn_size += bincode_opts()
-                                                    .serialized_size(txn)
-                                                    .unwrap_or_default();
-                                                false
-                                            } else {
-                                                true
-                                            }
-                                        })
-                                        .collect();
+                                    txns.retain(|txn_hash, txn| {
+                                        if included_txns_set.contains(txn_hash) {
+                                            included_txn_count += 1;
+                                            included_txn_size += bincode_opts()
+                                                .serialized_size(txn)
+                                                .unwrap_or_default();
+                                            false
+                                        } else {
+                                            true
+                                        }
+                                    });
                                 })
                                 .await;
 
@@ -826,6 +1085,7 @@ where
                                 .outstanding_transactions_memory_size
                                 .update(-(i64::try_from(included_txn_size).unwrap_or(i64::MAX)));
 
+                            consensus.notify_decided(&leaf_views);
                             debug!("about to publish decide");
                             let decide_sent = self.output_event_stream.publish(Event {
                                 view_number: consensus.last_decided_view,
@@ -901,91 +1161,97 @@ where
                     return;
                 }
 
-                match vote {
-                    QuorumVote::Yes(vote) => {
-                        let handle_event = HandleEvent(Arc::new(move |event, state| {
-                            async move { vote_handle(state, event).await }.boxed()
-                        }));
-                        let collection_view = if let Some((collection_view, collection_task, _)) =
-                            &self.vote_collector
-                        {
-                            if vote.current_view > *collection_view {
-                                // ED I think we'd want to let that task timeout to avoid a griefing vector
-                                self.registry.shutdown_task(*collection_task).await;
-                            }
-                            *collection_view
-                        } else {
-                            TYPES::Time::new(0)
-                        };
-
-                        let acc = VoteAccumulator {
-                            total_vote_outcomes: HashMap::new(),
-                            da_vote_outcomes: HashMap::new(),
-                            yes_vote_outcomes: HashMap::new(),
-                            no_vote_outcomes: HashMap::new(),
-                            viewsync_precommit_vote_outcomes: HashMap::new(),
-                            viewsync_commit_vote_outcomes: HashMap::new(),
-                            viewsync_finalize_vote_outcomes: HashMap::new(),
-                            success_threshold: self.quorum_exchange.success_threshold(),
-                            failure_threshold: self.quorum_exchange.failure_threshold(),
-                            sig_lists: Vec::new(),
-                            signers: bitvec![0; self.quorum_exchange.total_nodes()],
-                        };
-
-                        // Todo check if we are the leader
-                        let accumulator = self.quorum_exchange.accumulate_vote(
-                            &vote.clone().signature.0,
-                            &vote.clone().signature.1,
-                            vote.clone().leaf_commitment,
-                            vote.clone().vote_data.clone(),
-                            vote.clone().vote_token.clone(),
-                            vote.clone().current_view,
-                            acc,
-                            None,
-                        );
-
-                        if vote.current_view > collection_view {
-                            let state = VoteCollectionTaskState {
-                                quorum_exchange: self.quorum_exchange.clone(),
-                                accumulator,
-                                cur_view: vote.current_view,
-                                event_stream: self.event_stream.clone(),
-                                id: self.id,
-                            };
-                            let name = "Quorum Vote Collection";
-                            let filter = FilterEvent(Arc::new(|event| {
-                                matches!(event, SequencingHotShotEvent::QuorumVoteRecv(_))
-                            }));
-
-                            let builder =
-                                TaskBuilder::<VoteCollectionTypes<TYPES, I>>::new(name.to_string())
-                                    .register_event_stream(self.event_stream.clone(), filter)
-                                    .await
-                                    .register_registry(&mut self.registry.clone())
-                                    .await
-                                    .register_state(state)
-                                    .register_event_handler(handle_event);
-                            let id = builder.get_task_id().unwrap();
-                            let stream_id = builder.get_stream_id().unwrap();
-
-                            self.vote_collector = Some((vote.current_view, id, stream_id));
-
-                            let _task = async_spawn(async move {
-                                VoteCollectionTypes::build(builder).launch().await;
-                            });
-                            debug!("Starting vote handle for view {:?}", vote.current_view);
-                        } else if let Some((_, _, stream_id)) = self.vote_collector {
-                            self.event_stream
-                                .direct_message(
-                                    stream_id,
-                                    SequencingHotShotEvent::QuorumVoteRecv(QuorumVote::Yes(vote)),
+                // Todo check if we are the leader
+                let exchange = self.quorum_exchange.clone();
+                let first_vote = vote.clone();
+                let (accumulator, timeout_accumulator) = match first_vote {
+                    QuorumVote::Yes(first_vote) => {
+                        let acc = self.quorum_exchange.empty_accumulator();
+                        let accumulator = self
+                            .quorum_exchange
+                            .verification_pool()
+                            .offload(move || {
+                                exchange.accumulate_vote(
+                                    &first_vote.signature.0,
+                                    &first_vote.signature.1,
+                                    first_vote.leaf_commitment,
+                                    first_vote.vote_data,
+                                    first_vote.vote_token,
+                                    first_vote.current_view,
+                                    acc,
+                                    None,
                                 )
-                                .await;
-                        }
+                            })
+                            .await;
+                        let timeout_accumulator =
+                            Either::Left(self.quorum_exchange.empty_timeout_accumulator());
+                        (accumulator, timeout_accumulator)
+                    }
+                    QuorumVote::Timeout(first_vote) => {
+                        let acc = self.quorum_exchange.empty_timeout_accumulator();
+                        let timeout_accumulator = self
+                            .quorum_exchange
+                            .verification_pool()
+                            .offload(move || exchange.accumulate_timeout_vote(&first_vote, acc))
+                            .await;
+                        let accumulator = Either::Left(self.quorum_exchange.empty_accumulator());
+                        (accumulator, timeout_accumulator)
                     }
-                    QuorumVote::Timeout(_) | QuorumVote::No(_) => {
+                    QuorumVote::No(_) => {
                         error!("The next leader has received an unexpected vote!");
+                        return;
                     }
+                };
+
+                let handle_event = HandleEvent(Arc::new(move |event, state| {
+                    async move { vote_handle(state, event).await }.boxed()
+                }));
+                let collection_view =
+                    if let Some((collection_view, collection_task, _)) = &self.vote_collector {
+                        if vote.current_view() > *collection_view {
+                            // ED I think we'd want to let that task timeout to avoid a griefing vector
+                            self.registry.shutdown_task(*collection_task).await;
+                        }
+                        *collection_view
+                    } else {
+                        TYPES::Time::new(0)
+                    };
+
+                if vote.current_view() > collection_view {
+                    let state = VoteCollectionTaskState {
+                        quorum_exchange: self.quorum_exchange.clone(),
+                        accumulator,
+                        timeout_accumulator,
+                        cur_view: vote.current_view(),
+                        event_stream: self.event_stream.clone(),
+                        id: self.id,
+                        vote_queue: self.vote_queue.for_next_collector(),
+                    };
+                    self.vote_queue = state.vote_queue.clone();
+                    let name = "Quorum Vote Collection";
+                    let filter = self.vote_queue.filter();
+
+                    let builder =
+                        TaskBuilder::<VoteCollectionTypes<TYPES, I>>::new(name.to_string())
+                            .register_event_stream(self.event_stream.clone(), filter)
+                            .await
+                            .register_registry(&mut self.registry.clone())
+                            .await
+                            .register_state(state)
+                            .register_event_handler(handle_event);
+                    let id = builder.get_task_id().unwrap();
+                    let stream_id = builder.get_stream_id().unwrap();
+
+                    self.vote_collector = Some((vote.current_view(), id, stream_id));
+
+                    let _task = async_spawn(async move {
+                        VoteCollectionTypes::build(builder).launch().await;
+                    });
+                    debug!("Starting vote handle for view {:?}", vote.current_view());
+                } else if let Some((_, _, stream_id)) = self.vote_collector {
+                    self.event_stream
+                        .direct_message(stream_id, SequencingHotShotEvent::QuorumVoteRecv(vote))
+                        .await;
                 }
             }
             SequencingHotShotEvent::QCFormed(qc) => {
@@ -1086,6 +1352,13 @@ where
                     "We received a timeout event in the consensus task for view {}!",
                     *view
                 );
+                // Timers are not cancelled when the view changes, so only a timeout for the
+                // current view means it stalled.
+                if view == self.cur_view {
+                    self.event_stream
+                        .publish(SequencingHotShotEvent::ViewTimeout(view))
+                        .await;
+                }
             }
             SequencingHotShotEvent::SendDABlockData(block) => {
                 // ED TODO Should make sure this is actually the most recent block
@@ -1095,46 +1368,81 @@ where
         }
     }
 
-    /// Sends a proposal if possible from the high qc we have
-    pub async fn publish_proposal_if_able(&self, qc: QuorumCertificate<TYPES, I::Leaf>) -> bool {
+    /// Sends a proposal if possible from the high qc we have.
+    ///
+    /// Once the proposal for a view has failed more than `max_view_retries` times, publishes
+    /// [`SequencingHotShotEvent::ViewAbandoned`] and stops trying to propose in that view.
+    pub async fn publish_proposal_if_able(
+        &mut self,
+        qc: QuorumCertificate<TYPES, I::Leaf>,
+    ) -> bool {
+        let view = qc.view_number + 1;
+        let failures = self.proposal_failures.get(&view).copied().unwrap_or(0);
+        if matches!(self.max_view_retries, Some(retries) if failures > retries.get()) {
+            debug!("Not proposing in abandoned view {:?}", view);
+            return false;
+        }
+        let Err(reason) = self.try_publish_proposal(qc).await else {
+            self.proposal_failures.remove(&view);
+            return true;
+        };
+        let failures = failures + 1;
+        self.proposal_failures.insert(view, failures);
+        if matches!(self.max_view_retries, Some(retries) if failures > retries.get()) {
+            warn!(
+                ?view,
+                ?reason,
+                "Abandoning view after {failures} failed proposals"
+            );
+            self.event_stream
+                .publish(SequencingHotShotEvent::ViewAbandoned(view, reason))
+                .await;
+        }
+        false
+    }
+
+    /// Sends a proposal from the high qc we have, or returns why it could not
+    async fn try_publish_proposal(
+        &self,
+        qc: QuorumCertificate<TYPES, I::Leaf>,
+    ) -> Result<(), ProposalFailure> {
         // TODO ED This should not be qc view number + 1
         if !self.quorum_exchange.is_leader(qc.view_number + 1) {
             error!(
                 "Somehow we formed a QC but are not the leader for the next view {:?}",
                 qc.view_number + 1
             );
-            return false;
+            return Err(ProposalFailure::NotLeader);
         }
 
         let consensus = self.consensus.read().await;
         let parent_view_number = &consensus.high_qc.view_number();
         let mut reached_decided = false;
 
-        let Some(parent_view) = consensus.state_map.get(parent_view_number) else {
-            // This should have been added by the replica?
-            error!("Couldn't find parent view in state map, waiting for replica to see proposal\n parent view number: {}", **parent_view_number);
-            return false;
-        };
-        // Leaf hash in view inner does not match high qc hash - Why?
-        let Some(leaf_commitment) = parent_view.get_leaf_commitment() else {
+        let Some(leaf) = consensus.leaf_for_view(*parent_view_number) else {
+            let status = consensus.view_status(*parent_view_number);
             error!(
                 ?parent_view_number,
-                ?parent_view,
-                "Parent of high QC points to a view without a proposal"
+                ?status,
+                "Couldn't find the leaf of the high QC's view"
             );
-            return false;
+            return Err(match status {
+                // This should have been added by the replica?
+                ViewStatus::Unknown => ProposalFailure::ParentViewMissing,
+                ViewStatus::DAAvailable | ViewStatus::Failed => {
+                    ProposalFailure::ParentProposalMissing
+                }
+                ViewStatus::Proposed | ViewStatus::Decided => ProposalFailure::ParentLeafMissing,
+            });
         };
-        if leaf_commitment != consensus.high_qc.leaf_commitment() {
+        // Leaf hash in view inner does not match high qc hash - Why?
+        if leaf.commit() != consensus.high_qc.leaf_commitment() {
             debug!(
                 "They don't equal: {:?}   {:?}",
-                leaf_commitment,
+                leaf.commit(),
                 consensus.high_qc.leaf_commitment()
             );
         }
-        let Some(leaf) = consensus.saved_leaves.get(&leaf_commitment) else {
-            error!("Failed to find high QC of parent.");
-            return false;
-        };
         if leaf.view_number == consensus.last_decided_view {
             reached_decided = true;
         }
@@ -1179,7 +1487,8 @@ where
         let signature = self
             .quorum_exchange
             .sign_validating_or_commitment_proposal::<I>(&leaf.commit());
-        // TODO: DA cert is sent as part of the proposal here, we should split this out so we don't have to wait for it.
+        // The DA cert is not bundled: the DA leader broadcasts it separately once it forms, and
+        // replicas match it to this proposal by view, so the proposal does not wait for it.
         let proposal = QuorumProposal {
             block_commitment,
             view_number: leaf.view_number,
@@ -1203,7 +1512,7 @@ where
                 self.quorum_exchange.public_key().clone(),
             ))
             .await;
-        true
+        Ok(())
     }
 }
 
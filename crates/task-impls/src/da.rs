@@ -1,13 +1,13 @@
 use crate::events::SequencingHotShotEvent;
 use async_compatibility_layer::{
-    art::{async_spawn, async_timeout},
+    art::{async_sleep, async_spawn, async_timeout},
     async_primitives::subscribable_rwlock::ReadView,
 };
 use async_lock::RwLock;
 use bincode::config::Options;
-use bitvec::prelude::*;
 use commit::Committable;
 use either::{Either, Left, Right};
+use ethereum_types::U256;
 use futures::FutureExt;
 use hotshot_task::{
     event_stream::{ChannelStream, EventStream},
@@ -22,7 +22,7 @@ use hotshot_types::{
     message::{CommitteeConsensusMessage, Message, Proposal, SequencingMessage},
     traits::{
         consensus_api::SequencingConsensusApi,
-        election::{CommitteeExchangeType, ConsensusExchange, Membership},
+        election::{CommitteeExchangeType, ConsensusExchange, ProposalData},
         network::{CommunicationChannel, ConsensusIntentEvent},
         node_implementation::{CommitteeEx, NodeImplementation, NodeType},
         signature_key::SignatureKey,
@@ -35,9 +35,10 @@ use hotshot_types::{
 use hotshot_utils::bincode::bincode_opts;
 use snafu::Snafu;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashSet, VecDeque},
+    num::NonZeroUsize,
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tracing::{debug, error, instrument, warn};
 
@@ -45,6 +46,82 @@ use tracing::{debug, error, instrument, warn};
 /// Error type for consensus tasks
 pub struct ConsensusTaskError {}
 
+/// Simple moving average of how quickly transactions arrive, over a fixed window.
+#[derive(Clone, Debug)]
+pub struct TransactionArrivalRate {
+    /// How far back arrivals are counted
+    window: Duration,
+    /// When each batch of transactions arrived, and how many it held
+    arrivals: VecDeque<(Instant, usize)>,
+}
+
+impl Default for TransactionArrivalRate {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+impl TransactionArrivalRate {
+    /// Create a tracker averaging over `window`
+    #[must_use]
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            arrivals: VecDeque::new(),
+        }
+    }
+
+    /// Record `count` transactions arriving at `now`
+    pub fn record(&mut self, count: usize, now: Instant) {
+        self.arrivals.push_back((now, count));
+        while let Some((arrived, _)) = self.arrivals.front() {
+            if now.duration_since(*arrived) <= self.window {
+                break;
+            }
+            self.arrivals.pop_front();
+        }
+    }
+
+    /// Transactions per second that arrived within the window ending at `now`
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn rate(&self, now: Instant) -> f64 {
+        let count: usize = self
+            .arrivals
+            .iter()
+            .filter(|(arrived, _)| now.duration_since(*arrived) <= self.window)
+            .map(|(_, count)| count)
+            .sum();
+        count as f64 / self.window.as_secs_f64()
+    }
+}
+
+/// Why the DA leader abandoned its block
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DAFailure {
+    /// No DA votes arrived before the availability timeout
+    NoVotes,
+    /// DA votes arrived, but not enough stake to reach the availability threshold
+    BelowThreshold {
+        /// The most stake gathered behind one block commitment
+        stake: U256,
+        /// The stake needed for a DA certificate
+        threshold: u64,
+    },
+}
+
+/// A block the DA leader proposed, kept so it can be re-proposed if its view fails after the
+/// block's DA cert formed
+#[derive(Clone, Debug)]
+pub struct ProposedDABlock<TYPES: NodeType> {
+    /// The view the block was proposed for
+    pub view: TYPES::Time,
+    /// The proposed block
+    pub block: TYPES::BlockType,
+    /// The block's DA cert, once one has formed
+    pub cert: Option<DACertificate<TYPES>>,
+}
+
 /// Tracks state of a DA task
 pub struct DATaskState<
     TYPES: NodeType,
@@ -85,6 +162,28 @@ pub struct DATaskState<
 
     /// This state's ID
     pub id: u64,
+
+    /// Recent transaction arrival rate, used to decide how many transactions to wait for
+    pub txn_arrival_rate: TransactionArrivalRate,
+
+    /// The block this node last proposed as DA leader, until the view it was proposed for ends
+    pub proposed_block: Option<ProposedDABlock<TYPES>>,
+
+    /// A block that already has a DA certificate from a view that failed before it was decided.
+    /// The next time this node is DA leader it re-proposes the block with this certificate
+    /// instead of collecting DA votes on a new block.
+    pub reuse_da_cert: Option<(DACertificate<TYPES>, TYPES::BlockType)>,
+
+    /// How long to wait for the DA votes on this node's block before abandoning it; `None` waits
+    /// until the view ends
+    pub da_availability_timeout: Option<Duration>,
+
+    /// How many views in a row the leader may propose an empty block because it could not wait
+    /// for transactions; `None` if it may not propose empty blocks
+    pub max_empty_fallbacks: Option<NonZeroUsize>,
+
+    /// Views in a row the leader's wait for transactions has failed
+    pub empty_fallbacks: usize,
 }
 
 /// Struct to maintain DA Vote Collection task state
@@ -125,8 +224,17 @@ where
 {
 }
 
+/// Handle an event for a DA vote collection task.
+///
+/// A DA proposal for a later view ends collection early and publishes
+/// [`SequencingHotShotEvent::FellBehind`], since the rest of the network has moved on. If the
+/// availability timeout elapses first, collection ends with
+/// [`SequencingHotShotEvent::DAUnavailable`].
 #[instrument(skip_all, fields(id = state.id, view = *state.cur_view), name = "DA Vote Collection Task", level = "error")]
-async fn vote_handle<TYPES: NodeType, I: NodeImplementation<TYPES, Leaf = SequencingLeaf<TYPES>>>(
+pub async fn vote_handle<
+    TYPES: NodeType,
+    I: NodeImplementation<TYPES, Leaf = SequencingLeaf<TYPES>>,
+>(
     mut state: DAVoteCollectionTaskState<TYPES, I>,
     event: SequencingHotShotEvent<TYPES, I>,
 ) -> (
@@ -153,19 +261,32 @@ where
             }
 
             let accumulator = state.accumulator.left().unwrap();
-            match state.committee_exchange.accumulate_vote(
-                &vote.signature.0,
-                &vote.signature.1,
-                vote.block_commitment,
-                vote.vote_data,
-                vote.vote_token.clone(),
-                state.cur_view,
-                accumulator,
-                None,
-            ) {
+            let exchange = state.committee_exchange.clone();
+            let cur_view = state.cur_view;
+            let accumulated = state
+                .committee_exchange
+                .verification_pool()
+                .offload(move || {
+                    exchange.accumulate_vote(
+                        &vote.signature.0,
+                        &vote.signature.1,
+                        vote.block_commitment,
+                        vote.vote_data,
+                        vote.vote_token.clone(),
+                        cur_view,
+                        accumulator,
+                        None,
+                    )
+                })
+                .await;
+            match accumulated {
                 Left(acc) => {
+                    debug!(
+                        "Need {} more DA stake for view {:?}",
+                        acc.remaining_stake_needed(acc.success_threshold),
+                        state.cur_view
+                    );
                     state.accumulator = Either::Left(acc);
-                    // debug!("Not enough DA votes! ");
                     return (None, state);
                 }
                 Right(dac) => {
@@ -192,6 +313,45 @@ where
                 }
             }
         }
+        SequencingHotShotEvent::DAProposalRecv(proposal, _) => {
+            let view = proposal.data.get_view_number();
+            if view > state.cur_view && state.accumulator.is_left() {
+                warn!(
+                    "Received DA proposal for view {:?} while collecting votes for view {:?}",
+                    view, state.cur_view
+                );
+                state
+                    .event_stream
+                    .publish(SequencingHotShotEvent::FellBehind(view))
+                    .await;
+                return (Some(HotShotTaskCompleted::ShutDown), state);
+            }
+        }
+        SequencingHotShotEvent::DAAvailabilityTimeout(view) if view == state.cur_view => {
+            if let Either::Left(accumulator) = &state.accumulator {
+                let stake = accumulator
+                    .da_vote_outcomes
+                    .values()
+                    .map(|(stake, _)| *stake)
+                    .max()
+                    .unwrap_or_default();
+                let reason = DAFailure::BelowThreshold {
+                    stake,
+                    threshold: accumulator.success_threshold.get(),
+                };
+                warn!("Abandoning DA block for view {:?}: {:?}", view, reason);
+                state
+                    .event_stream
+                    .publish(SequencingHotShotEvent::DAUnavailable(view, reason))
+                    .await;
+                state
+                    .committee_exchange
+                    .network()
+                    .inject_consensus_info(ConsensusIntentEvent::CancelPollForVotes(*view))
+                    .await;
+                return (Some(HotShotTaskCompleted::ShutDown), state);
+            }
+        }
         SequencingHotShotEvent::Shutdown => return (Some(HotShotTaskCompleted::ShutDown), state),
         _ => {}
     }
@@ -225,18 +385,23 @@ where
         match event {
             SequencingHotShotEvent::TransactionsRecv(transactions) => {
                 // TODO ED Add validation checks
+                self.txn_arrival_rate
+                    .record(transactions.len(), self.api.now());
 
+                let mut accepted = Vec::new();
                 let mut consensus = self.consensus.write().await;
                 consensus
                     .get_transactions()
                     .modify(|txns| {
                         for transaction in transactions {
                             let size = bincode_opts().serialized_size(&transaction).unwrap_or(0);
+                            let commitment = transaction.commit();
 
                             // If we didn't already know about this transaction, update our mempool metrics.
-                            if !consensus.seen_transactions.remove(&transaction.commit())
-                                && txns.insert(transaction.commit(), transaction).is_none()
+                            if !consensus.seen_transactions.remove(&commitment)
+                                && txns.insert(commitment, transaction).is_none()
                             {
+                                accepted.push(commitment);
                                 consensus.metrics.outstanding_transactions.update(1);
                                 consensus
                                     .metrics
@@ -249,6 +414,13 @@ where
                         }
                     })
                     .await;
+                drop(consensus);
+
+                for commitment in accepted {
+                    self.event_stream
+                        .publish(SequencingHotShotEvent::TransactionAccepted(commitment))
+                        .await;
+                }
 
                 return None;
             }
@@ -262,17 +434,22 @@ where
 
                 // Allow a DA proposal that is one view older, in case we have voted on a quorum
                 // proposal and updated the view.
-                // `self.cur_view` should be at least 1 since there is a view change before getting
-                // the `DAProposalRecv` event. Otherewise, the view number subtraction below will
-                // cause an overflow error.
-                if view < self.cur_view - 1 {
+                if matches!(self.cur_view.checked_sub(view), Some(lag) if lag > 1) {
                     warn!("Throwing away DA proposal that is more than one view older");
                     return None;
                 }
 
+                if !proposal.data.metadata_matches_block() {
+                    error!(
+                        "DA proposal metadata (txn_count {}, block_bytes {}) doesn't match its block",
+                        proposal.data.txn_count, proposal.data.block_bytes
+                    );
+                    return None;
+                }
+
                 debug!(
                     "Got a DA block with {} transactions!",
-                    proposal.data.deltas.contained_transactions().len()
+                    proposal.data.txn_count
                 );
                 let block_commitment = proposal.data.deltas.commit();
 
@@ -283,11 +460,19 @@ where
                     return None;
                 }
 
-                if !view_leader_key.validate(&proposal.signature, block_commitment.as_ref()) {
+                if !view_leader_key.validate(
+                    &proposal.signature,
+                    ProposalData::DA(block_commitment).commit().as_ref(),
+                ) {
                     error!("Could not verify proposal.");
                     return None;
                 }
 
+                if !self.committee_exchange.is_da_member(view) {
+                    debug!("We were not chosen for DA committee on {:?}", view);
+                    return None;
+                }
+
                 let vote_token = self.committee_exchange.make_vote_token(view);
                 match vote_token {
                     Err(e) => {
@@ -318,11 +503,16 @@ where
                         // Ensure this view is in the view map for garbage collection, but do not overwrite if
                         // there is already a view there: the replica task may have inserted a `Leaf` view which
                         // contains strictly more information.
-                        consensus.state_map.entry(view).or_insert(View {
-                            view_inner: ViewInner::DA {
-                                block: block_commitment,
-                            },
-                        });
+                        if !consensus.state_map.contains_key(&view) {
+                            consensus.insert_view(
+                                view,
+                                View {
+                                    view_inner: ViewInner::DA {
+                                        block: block_commitment,
+                                    },
+                                },
+                            );
+                        }
 
                         // Record the block we have promised to make available.
                         consensus.saved_blocks.insert(proposal.data.deltas);
@@ -356,29 +546,25 @@ where
                     } else {
                         TYPES::Time::new(0)
                     };
-                let acc = VoteAccumulator {
-                    total_vote_outcomes: HashMap::new(),
-                    da_vote_outcomes: HashMap::new(),
-                    yes_vote_outcomes: HashMap::new(),
-                    no_vote_outcomes: HashMap::new(),
-                    viewsync_precommit_vote_outcomes: HashMap::new(),
-                    viewsync_commit_vote_outcomes: HashMap::new(),
-                    viewsync_finalize_vote_outcomes: HashMap::new(),
-                    success_threshold: self.committee_exchange.success_threshold(),
-                    failure_threshold: self.committee_exchange.failure_threshold(),
-                    sig_lists: Vec::new(),
-                    signers: bitvec![0; self.committee_exchange.total_nodes()],
-                };
-                let accumulator = self.committee_exchange.accumulate_vote(
-                    &vote.clone().signature.0,
-                    &vote.clone().signature.1,
-                    vote.clone().block_commitment,
-                    vote.clone().vote_data.clone(),
-                    vote.clone().vote_token.clone(),
-                    vote.clone().current_view,
-                    acc,
-                    None,
-                );
+                let acc = self.committee_exchange.empty_accumulator();
+                let exchange = self.committee_exchange.clone();
+                let first_vote = vote.clone();
+                let accumulator = self
+                    .committee_exchange
+                    .verification_pool()
+                    .offload(move || {
+                        exchange.accumulate_vote(
+                            &first_vote.signature.0,
+                            &first_vote.signature.1,
+                            first_vote.block_commitment,
+                            first_vote.vote_data,
+                            first_vote.vote_token,
+                            first_vote.current_view,
+                            acc,
+                            None,
+                        )
+                    })
+                    .await;
                 if view > collection_view {
                     let state = DAVoteCollectionTaskState {
                         committee_exchange: self.committee_exchange.clone(),
@@ -389,7 +575,12 @@ where
                     };
                     let name = "DA Vote Collection";
                     let filter = FilterEvent(Arc::new(|event| {
-                        matches!(event, SequencingHotShotEvent::DAVoteRecv(_))
+                        matches!(
+                            event,
+                            SequencingHotShotEvent::DAVoteRecv(_)
+                                | SequencingHotShotEvent::DAProposalRecv(_, _)
+                                | SequencingHotShotEvent::DAAvailabilityTimeout(_)
+                        )
                     }));
                     let builder =
                         TaskBuilder::<DAVoteCollectionTypes<TYPES, I>>::new(name.to_string())
@@ -418,21 +609,19 @@ where
                     return None;
                 }
 
-                if *view - *self.cur_view > 1 {
+                if matches!(view.checked_sub(self.cur_view), Some(gap) if gap > 1) {
                     error!("View changed by more than 1 going to view {:?}", view);
                 }
                 self.cur_view = view;
+                // The view the block was proposed for ended without failing.
+                if matches!(&self.proposed_block, Some(proposed) if proposed.view < view) {
+                    self.proposed_block = None;
+                }
                 // Inject view info into network
                 // ED I think it is possible that you receive a quorum proposal, vote on it and update your view before the da leader has sent their proposal, and therefore you skip polling for this view?
 
                 // TODO ED Only poll if you are on the committee
-                let is_da = self
-                    .committee_exchange
-                    .membership()
-                    .get_committee(self.cur_view + 1)
-                    .contains(self.committee_exchange.public_key());
-
-                if is_da {
+                if self.committee_exchange.is_da_member(self.cur_view + 1) {
                     debug!("Polling for DA proposals for view {}", *self.cur_view + 1);
                     self.committee_exchange
                         .network()
@@ -458,6 +647,44 @@ where
                     // panic!("We are not the DA leader for view {}", *self.cur_view + 1);
                     return None;
                 }
+
+                if let Some((cert, block)) = self.reuse_da_cert.take() {
+                    let cert_valid = cert.view_number <= self.cur_view && {
+                        let exchange = self.committee_exchange.clone();
+                        let reused = cert.clone();
+                        let block_commitment = block.commit();
+                        self.committee_exchange
+                            .verification_pool()
+                            .offload(move || exchange.is_valid_cert(&reused, block_commitment))
+                            .await
+                    };
+                    if cert_valid {
+                        // The certificate keeps the view it formed in; replicas match it to the
+                        // proposal by block commitment.
+                        debug!(
+                            "Re-proposing block with the DA cert from view {:?} for view {:?}",
+                            cert.view_number,
+                            self.cur_view + 1
+                        );
+                        self.proposed_block = Some(ProposedDABlock {
+                            view: self.cur_view + 1,
+                            block: block.clone(),
+                            cert: Some(cert.clone()),
+                        });
+                        self.event_stream
+                            .publish(SequencingHotShotEvent::SendDABlockData(block))
+                            .await;
+                        self.event_stream
+                            .publish(SequencingHotShotEvent::DACSend(
+                                cert,
+                                self.committee_exchange.public_key().clone(),
+                            ))
+                            .await;
+                        return None;
+                    }
+                    warn!("DA cert to reuse is no longer valid, building a new block");
+                }
+
                 debug!("Polling for DA votes for view {}", *self.cur_view + 1);
 
                 // Start polling for DA votes for the "next view"
@@ -469,28 +696,18 @@ where
                 // ED Copy of parent_leaf() function from sequencing leader
 
                 let consensus = self.consensus.read().await;
-                let parent_view_number = &consensus.high_qc.view_number;
+                let parent_view_number = consensus.high_qc.view_number;
 
-                let Some(parent_view) = consensus.state_map.get(parent_view_number) else {
-                    error!(
-                        "Couldn't find high QC parent in state map. Parent view {:?}",
-                        parent_view_number
-                    );
-                    return None;
-                };
-                let Some(leaf) = parent_view.get_leaf_commitment() else {
+                let Some(leaf) = consensus.leaf_for_view(parent_view_number) else {
                     error!(
                         ?parent_view_number,
-                        ?parent_view,
-                        "Parent of high QC points to a view without a proposal"
+                        status = ?consensus.view_status(parent_view_number),
+                        "Failed to find high QC parent."
                     );
                     return None;
                 };
-                let Some(leaf) = consensus.saved_leaves.get(&leaf) else {
-                    error!("Failed to find high QC parent.");
-                    return None;
-                };
                 let parent_leaf = leaf.clone();
+                let parent_state = consensus.validated_state_for_view(parent_view_number);
 
                 // Prepare the DA Proposal
                 //         let Some(parent_leaf) = self.parent_leaf().await else {
@@ -500,55 +717,40 @@ where
 
                 drop(consensus);
 
-                let mut block = <TYPES as NodeType>::StateType::next_block(None);
-                let txns = self.wait_for_transactions(parent_leaf).await?;
+                // Held until the proposal is sent, so at most the configured number of proposals
+                // are assembled at once.
+                let _slot = self.api.proposal_slots().acquire().await;
+                let txns = self.wait_for_transactions(&parent_leaf).await;
+                self.propose_block(parent_state, txns).await;
 
+                return None;
+            }
+            SequencingHotShotEvent::DAAvailabilityTimeout(view) => {
+                // Once a vote arrives, the vote collection task for the view reports the timeout.
+                if matches!(self.vote_collector, Some((collection_view, _, _)) if collection_view == view)
+                {
+                    return None;
+                }
+                warn!("Abandoning DA block for view {:?}: no DA votes", view);
+                self.event_stream
+                    .publish(SequencingHotShotEvent::DAUnavailable(
+                        view,
+                        DAFailure::NoVotes,
+                    ))
+                    .await;
                 self.committee_exchange
                     .network()
-                    .inject_consensus_info(ConsensusIntentEvent::CancelPollForTransactions(
-                        *self.cur_view + 1,
-                    ))
+                    .inject_consensus_info(ConsensusIntentEvent::CancelPollForVotes(*view))
                     .await;
+            }
 
-                for txn in txns {
-                    if let Ok(new_block) = block.add_transaction_raw(&txn) {
-                        block = new_block;
-                        continue;
+            SequencingHotShotEvent::DACSend(cert, _) => {
+                // Our vote collection task formed the DA cert for the block we proposed.
+                if let Some(proposed) = &mut self.proposed_block {
+                    if proposed.cert.is_none() && proposed.block.commit() == cert.block_commitment {
+                        proposed.cert = Some(cert);
                     }
                 }
-
-                let signature = self.committee_exchange.sign_da_proposal(&block.commit());
-                let data: DAProposal<TYPES> = DAProposal {
-                    deltas: block.clone(),
-                    // Upon entering a new view we want to send a DA Proposal for the next view -> Is it always the case that this is cur_view + 1?
-                    view_number: self.cur_view + 1,
-                };
-                debug!("Sending DA proposal for view {:?}", data.view_number);
-
-                // let message = SequencingMessage::<TYPES, I>(Right(
-                //     CommitteeConsensusMessage::DAProposal(Proposal { data, signature }),
-                // ));
-                let message = Proposal { data, signature };
-                // Brodcast DA proposal
-                // TODO ED We should send an event to do this, but just getting it to work for now
-
-                self.event_stream
-                    .publish(SequencingHotShotEvent::SendDABlockData(block.clone()))
-                    .await;
-                // if let Err(e) = self.api.send_da_broadcast(message.clone()).await {
-                //     consensus.metrics.failed_to_send_messages.add(1);
-                //     warn!(?message, ?e, "Could not broadcast leader proposal");
-                // } else {
-                //     consensus.metrics.outgoing_broadcast_messages.add(1);
-                // }
-                self.event_stream
-                    .publish(SequencingHotShotEvent::DAProposalSend(
-                        message,
-                        self.committee_exchange.public_key().clone(),
-                    ))
-                    .await;
-
-                return None;
             }
 
             SequencingHotShotEvent::Timeout(view) => {
@@ -556,6 +758,20 @@ where
                     .network()
                     .inject_consensus_info(ConsensusIntentEvent::CancelPollForVotes(*view))
                     .await;
+
+                // The view failed after our block became available, so keep the block and its
+                // DA cert to re-propose the next time we lead.
+                match self.proposed_block.take() {
+                    Some(ProposedDABlock {
+                        view: proposed_view,
+                        block,
+                        cert: Some(cert),
+                    }) if proposed_view == view => {
+                        debug!("Keeping DA cert from failed view {:?} for reuse", view);
+                        self.reuse_da_cert = Some((cert, block));
+                    }
+                    proposed => self.proposed_block = proposed,
+                }
             }
 
             SequencingHotShotEvent::Shutdown => {
@@ -566,24 +782,137 @@ where
         None
     }
 
-    /// return None if we can't get transactions
+    /// Propose the block for the next view, extending `parent_state` if the parent leaf carries it,
+    /// from `waited`, the result of waiting for transactions.
+    ///
+    /// If the wait failed, the leader proposes an empty block after `propose_empty_after` rather
+    /// than losing the view, as long as it may propose empty blocks. Once the wait has failed more
+    /// than `max_empty_fallbacks` views in a row, the transaction channel is taken to be dead: the
+    /// leader skips its proposal and publishes [`SequencingHotShotEvent::TransactionsUnavailable`].
+    pub async fn propose_block(
+        &mut self,
+        parent_state: Option<TYPES::StateType>,
+        waited: Option<Vec<TYPES::Transaction>>,
+    ) {
+        let view = self.cur_view + 1;
+        let txns = if let Some(txns) = waited {
+            self.empty_fallbacks = 0;
+            txns
+        } else {
+            self.empty_fallbacks += 1;
+            let (Some(max_fallbacks), Some(delay)) =
+                (self.max_empty_fallbacks, self.api.propose_empty_after())
+            else {
+                error!(
+                    "Could not wait for transactions, skipping the proposal for view {}",
+                    *view
+                );
+                return;
+            };
+            if self.empty_fallbacks > max_fallbacks.get() {
+                error!(
+                    "Could not wait for transactions for {} views in a row, skipping the proposal for view {}",
+                    self.empty_fallbacks, *view
+                );
+                self.event_stream
+                    .publish(SequencingHotShotEvent::TransactionsUnavailable(view))
+                    .await;
+                return;
+            }
+            error!(
+                "Could not wait for transactions, proposing an empty block for view {} after {:?}",
+                *view, delay
+            );
+            async_sleep(delay).await;
+            Vec::new()
+        };
+
+        let mut block = <TYPES as NodeType>::StateType::next_block(parent_state);
+        self.committee_exchange
+            .network()
+            .inject_consensus_info(ConsensusIntentEvent::CancelPollForTransactions(*view))
+            .await;
+
+        for txn in txns {
+            if let Ok(new_block) = block.add_transaction_raw(&txn) {
+                block = new_block;
+                continue;
+            }
+        }
+
+        let signature = self.committee_exchange.sign_da_proposal(&block.commit());
+        // Upon entering a new view we want to send a DA Proposal for the next view -> Is it always the case that this is cur_view + 1?
+        let data: DAProposal<TYPES> = DAProposal::new(block.clone(), view);
+        debug!("Sending DA proposal for view {:?}", data.view_number);
+
+        // let message = SequencingMessage::<TYPES, I>(Right(
+        //     CommitteeConsensusMessage::DAProposal(Proposal { data, signature }),
+        // ));
+        let message = Proposal { data, signature };
+        // Brodcast DA proposal
+        // TODO ED We should send an event to do this, but just getting it to work for now
+
+        self.proposed_block = Some(ProposedDABlock {
+            view,
+            block: block.clone(),
+            cert: None,
+        });
+        self.event_stream
+            .publish(SequencingHotShotEvent::SendDABlockData(block.clone()))
+            .await;
+        // if let Err(e) = self.api.send_da_broadcast(message.clone()).await {
+        //     consensus.metrics.failed_to_send_messages.add(1);
+        //     warn!(?message, ?e, "Could not broadcast leader proposal");
+        // } else {
+        //     consensus.metrics.outgoing_broadcast_messages.add(1);
+        // }
+        self.event_stream
+            .publish(SequencingHotShotEvent::DAProposalSend(
+                message,
+                self.committee_exchange.public_key().clone(),
+            ))
+            .await;
+
+        if let Some(timeout) = self.da_availability_timeout {
+            let event_stream = self.event_stream.clone();
+            async_spawn(async move {
+                async_sleep(timeout).await;
+                event_stream
+                    .publish(SequencingHotShotEvent::DAAvailabilityTimeout(view))
+                    .await;
+            });
+        }
+    }
+
+    /// return None if we can't get transactions, otherwise at most `max_transactions` of them,
+    /// no sooner than `propose_min_round_time` after starting to wait
     #[instrument(skip_all, fields(id = self.id, view = *self.cur_view), name = "DA Vote Collection Task", level = "error")]
 
     async fn wait_for_transactions(
         &self,
-        parent_leaf: SequencingLeaf<TYPES>,
+        parent_leaf: &SequencingLeaf<TYPES>,
     ) -> Option<Vec<TYPES::Transaction>> {
-        let task_start_time = Instant::now();
-
-        // let parent_leaf = self.parent_leaf().await?;
-        let previous_used_txns = match parent_leaf.deltas {
-            Either::Left(block) => block.contained_transactions(),
-            Either::Right(_commitment) => HashSet::new(),
-        };
+        let task_start_time = self.api.now();
 
         let consensus = self.consensus.read().await;
 
+        // A parent leaf that only carries its block commitment can still be resolved from the
+        // blocks we've stored; only skip deduplication if the block is really gone.
+        let previous_used_txns = match consensus.leaf_block(parent_leaf) {
+            Some(block) => block.contained_transactions(),
+            None => {
+                warn!(
+                    "Parent leaf {:?} has no block available, not filtering its transactions",
+                    parent_leaf.commit()
+                );
+                HashSet::new()
+            }
+        };
+
         let receiver = consensus.transactions.subscribe().await;
+        let max_round_time = self
+            .api
+            .round_time_for_committee_size(self.committee_exchange.total_nodes());
 
         loop {
             let all_txns = consensus.transactions.cloned().await;
@@ -593,11 +922,14 @@ where
                 .filter(|(txn_hash, _txn)| !previous_used_txns.contains(txn_hash))
                 .collect();
 
-            let time_past = task_start_time.elapsed();
-            if unclaimed_txns.len() < self.api.min_transactions()
-                && (time_past < self.api.propose_max_round_time())
+            let time_past = self.api.now().saturating_duration_since(task_start_time);
+            if unclaimed_txns.len()
+                < self
+                    .api
+                    .min_transactions_for(self.txn_arrival_rate.rate(self.api.now()))
+                && (time_past < max_round_time)
             {
-                let duration = self.api.propose_max_round_time() - time_past;
+                let duration = max_round_time - time_past;
                 let result = async_timeout(duration, receiver.recv()).await;
                 match result {
                     Err(_) => {
@@ -616,6 +948,23 @@ where
             }
             break;
         }
+
+        // Even with transactions ready, don't propose faster than once per minimum round time.
+        // New transactions wake the wait early so the clock is re-read.
+        loop {
+            let time_past = self.api.now().saturating_duration_since(task_start_time);
+            let Some(remaining) = self.api.propose_min_round_time().checked_sub(time_past) else {
+                break;
+            };
+            if remaining.is_zero() {
+                break;
+            }
+            if let Ok(Err(e)) = async_timeout(remaining, receiver.recv()).await {
+                error!("Channel receiver error for SubscribableRwLock {:?}", e);
+                return None;
+            }
+        }
+
         let all_txns = consensus.transactions.cloned().await;
         let txns: Vec<TYPES::Transaction> = all_txns
             .iter()
@@ -626,6 +975,7 @@ where
                     Some(txn.clone())
                 }
             })
+            .take(self.api.max_transactions().get())
             .collect();
         Some(txns)
     }
@@ -640,6 +990,8 @@ where
                 | SequencingHotShotEvent::TransactionsRecv(_)
                 | SequencingHotShotEvent::Timeout(_)
                 | SequencingHotShotEvent::ViewChange(_)
+                | SequencingHotShotEvent::DAAvailabilityTimeout(_)
+                | SequencingHotShotEvent::DACSend(_, _)
         )
     }
 }
@@ -1,5 +1,6 @@
+use commit::Commitment;
 use hotshot_types::{
-    certificate::{DACertificate, QuorumCertificate},
+    certificate::{DACertificate, QuorumCertificate, TimeoutCertificate},
     data::DAProposal,
     message::Proposal,
     traits::node_implementation::{
@@ -8,7 +9,7 @@ use hotshot_types::{
     vote::{DAVote, QuorumVote, ViewSyncVote},
 };
 
-use crate::view_sync::ViewSyncPhase;
+use crate::{consensus::ProposalFailure, da::DAFailure, view_sync::ViewSyncPhase};
 
 /// All of the possible events that can be passed between Sequecning `HotShot` tasks
 #[derive(Eq, Hash, PartialEq, Debug, Clone)]
@@ -35,6 +36,8 @@ pub enum SequencingHotShotEvent<TYPES: NodeType, I: NodeImplementation<TYPES>> {
     DAVoteSend(DAVote<TYPES>),
     /// The next leader has collected enough votes to form a QC; emitted by the next leader in the consensus task; an internal event only
     QCFormed(QuorumCertificate<TYPES, I::Leaf>),
+    /// The next leader has collected enough timeout votes to form a timeout certificate; emitted by the next leader in the consensus task; an internal event only
+    TimeoutCertFormed(TimeoutCertificate<TYPES>),
     /// The DA leader has collected enough votes to form a DAC; emitted by the DA leader in the DA task; sent to the entire network via the networking task
     DACSend(DACertificate<TYPES>, TYPES::SignatureKey),
     /// The current view has changed; emitted by the replica in the consensus task or replica in the view sync task; received by almost all other tasks
@@ -54,12 +57,28 @@ pub enum SequencingHotShotEvent<TYPES: NodeType, I: NodeImplementation<TYPES>> {
     ViewSyncCertificateRecv(Proposal<ViewSyncProposalType<TYPES, I>>),
     /// Trigger the start of the view sync protocol; emitted by view sync task; internal trigger only
     ViewSyncTrigger(TYPES::Time),
+    /// A view sync round tried `max_relay_attempts` relays without forming a certificate; emitted by a replica in the view sync task
+    ViewSyncFailed(TYPES::Time),
     /// A consensus view has timed out; emitted by a replica in the consensus task; received by the view sync task; internal event only
     Timeout(TYPES::Time),
+    /// The current view's deadline elapsed before the replica left it; emitted once per stalled view by the consensus task
+    ViewTimeout(TYPES::Time),
     /// Receive transactions from the network
     TransactionsRecv(Vec<TYPES::Transaction>),
     /// Send transactions to the network
     TransactionSend(TYPES::Transaction, TYPES::SignatureKey),
+    /// A transaction was newly added to the mempool; emitted by the DA task so clients can be told their transaction was received
+    TransactionAccepted(Commitment<TYPES::Transaction>),
     /// Event to send DA block data from DA leader to next quorum leader (which should always be the same node); internal event only
     SendDABlockData(TYPES::BlockType),
+    /// A vote collection task saw a proposal for a later view and stopped collecting; emitted by a vote collection task; internal event only
+    FellBehind(TYPES::Time),
+    /// The DA availability timeout for a view elapsed; emitted by the DA leader's timer; internal event only
+    DAAvailabilityTimeout(TYPES::Time),
+    /// The DA leader abandoned its block because it did not reach the availability threshold in time; emitted by the DA task or its vote collection task
+    DAUnavailable(TYPES::Time, DAFailure),
+    /// The DA leader skipped its proposal for a view because its wait for transactions has failed more than `max_empty_fallbacks` views in a row; emitted by the DA leader in the DA task
+    TransactionsUnavailable(TYPES::Time),
+    /// The leader gave up proposing in a view after exhausting its retries; emitted by the leader in the consensus task
+    ViewAbandoned(TYPES::Time, ProposalFailure),
 }
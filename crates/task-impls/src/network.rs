@@ -1,4 +1,6 @@
 use crate::events::SequencingHotShotEvent;
+use async_compatibility_layer::art::{async_sleep, async_timeout};
+use bincode::Options;
 use either::Either::{self, Left, Right};
 use hotshot_task::{
     event_stream::{ChannelStream, EventStream},
@@ -14,17 +16,37 @@ use hotshot_types::{
     },
     traits::{
         election::Membership,
-        network::{CommunicationChannel, TransmitType},
-        node_implementation::{NodeImplementation, NodeType},
+        network::{CommunicationChannel, NetworkError, TransmitType},
+        node_implementation::{CommitteeMembership, NodeImplementation, NodeType},
+        state::ConsensusTime,
     },
     vote::VoteType,
 };
+use hotshot_utils::bincode::bincode_opts;
 use snafu::Snafu;
-use std::{marker::PhantomData, sync::Arc};
-use tracing::error;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tracing::{debug, error, warn};
+
+/// How many views behind the current one the network event task keeps per-view state for
+pub const RETAINED_VIEWS: u64 = 10;
+
+/// How long the network event task keeps retrying queued messages once it is shut down
+pub const FLUSH_DEADLINE: Duration = Duration::from_millis(500);
+
+/// How long the network event task waits between attempts to flush its queue on shutdown
+const FLUSH_RETRY_INTERVAL: Duration = Duration::from_millis(10);
 
 /// the type of network task
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum NetworkTaskKind {
     /// quorum: the normal "everyone" committee
     Quorum,
@@ -34,6 +56,67 @@ pub enum NetworkTaskKind {
     ViewSync,
 }
 
+impl NetworkTaskKind {
+    /// Every kind of network task; a node runs one network event task of each
+    pub const ALL: [NetworkTaskKind; 3] = [
+        NetworkTaskKind::Quorum,
+        NetworkTaskKind::Committee,
+        NetworkTaskKind::ViewSync,
+    ];
+
+    /// Whether the network event task of this kind handles `event`
+    #[must_use]
+    pub fn handles<TYPES: NodeType, I: NodeImplementation<TYPES>>(
+        self,
+        event: &SequencingHotShotEvent<TYPES, I>,
+    ) -> bool {
+        match self {
+            NetworkTaskKind::Quorum => matches!(
+                event,
+                SequencingHotShotEvent::QuorumProposalSend(_, _)
+                    | SequencingHotShotEvent::QuorumVoteSend(_)
+                    | SequencingHotShotEvent::Shutdown
+                    | SequencingHotShotEvent::DACSend(_, _)
+                    | SequencingHotShotEvent::ViewChange(_)
+            ),
+            NetworkTaskKind::Committee => matches!(
+                event,
+                SequencingHotShotEvent::DAProposalSend(_, _)
+                    | SequencingHotShotEvent::DAVoteSend(_)
+                    | SequencingHotShotEvent::Shutdown
+                    | SequencingHotShotEvent::ViewChange(_)
+            ),
+            NetworkTaskKind::ViewSync => matches!(
+                event,
+                SequencingHotShotEvent::ViewSyncVoteSend(_)
+                    | SequencingHotShotEvent::ViewSyncCertificateSend(_, _)
+                    | SequencingHotShotEvent::Shutdown
+                    | SequencingHotShotEvent::ViewChange(_)
+            ),
+        }
+    }
+}
+
+/// Why the network message task could not turn a message into an event
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnhandledMessage {
+    /// A known message kind that should never arrive over the network
+    Unexpected,
+    /// The placeholder [`MessageKind::_Unreachable`] kind, which decodes but carries nothing.
+    ///
+    /// A kind this node's version does not define at all, e.g. one added by a newer peer, fails
+    /// to deserialize in the transport and never reaches this task.
+    Unknown,
+}
+
+/// Receives the messages the network message task cannot turn into an event, instead of them
+/// being dropped
+pub type FallbackMessageHandler<TYPES, I> =
+    Arc<dyn Fn(UnhandledMessage, Message<TYPES, I>) + Send + Sync>;
+
+/// Sees every event the network message task publishes, just before it is published
+pub type EventObserver<TYPES, I> = Arc<dyn Fn(&SequencingHotShotEvent<TYPES, I>) + Send + Sync>;
+
 /// the network message task state
 pub struct NetworkMessageTaskState<
     TYPES: NodeType,
@@ -45,6 +128,20 @@ pub struct NetworkMessageTaskState<
 > {
     /// event stream (used for publishing)
     pub event_stream: ChannelStream<SequencingHotShotEvent<TYPES, I>>,
+    /// If set, DA votes whose sender is not on this DA committee for the vote's view are dropped.
+    /// Left unset for relay topologies, where votes may arrive through non-committee nodes.
+    pub drop_if_not_committee: Option<CommitteeMembership<TYPES, I>>,
+    /// Handler for messages that cannot be turned into an event; they are dropped if unset, which
+    /// is the default
+    pub fallback_handler: Option<FallbackMessageHandler<TYPES, I>>,
+    /// Observer of every event this task publishes, e.g. for external tracing
+    pub event_observer: Option<EventObserver<TYPES, I>>,
+    /// Chain the votes we receive must be signed for
+    pub chain_id: u64,
+    /// If set, messages sent by this key, normally this node's own
+    /// ([`CommunicationChannel::local_public_key`]), are dropped. Left unset while a leader relies
+    /// on receiving its own proposal and votes back from the network.
+    pub drop_own_messages: Option<TYPES::SignatureKey>,
 }
 
 impl<
@@ -67,39 +164,139 @@ impl<
         >,
     > NetworkMessageTaskState<TYPES, I>
 {
+    /// Create a task state publishing to `event_stream` every message it can turn into an event,
+    /// accepting votes signed for `chain_id`
+    #[must_use]
+    pub fn new(
+        event_stream: ChannelStream<SequencingHotShotEvent<TYPES, I>>,
+        chain_id: u64,
+    ) -> Self {
+        Self {
+            event_stream,
+            drop_if_not_committee: None,
+            fallback_handler: None,
+            event_observer: None,
+            chain_id,
+            drop_own_messages: None,
+        }
+    }
+
+    /// Drop DA votes whose sender is not on `committee` for the vote's view.
+    #[must_use]
+    pub fn dropping_non_committee_votes(
+        mut self,
+        committee: CommitteeMembership<TYPES, I>,
+    ) -> Self {
+        self.drop_if_not_committee = Some(committee);
+        self
+    }
+
+    /// Hand messages that cannot be turned into an event to `handler` instead of dropping them.
+    #[must_use]
+    pub fn with_fallback_handler(mut self, handler: FallbackMessageHandler<TYPES, I>) -> Self {
+        self.fallback_handler = Some(handler);
+        self
+    }
+
+    /// Show every event this task publishes to `observer` before it is published. Delivery to
+    /// the event stream is unchanged.
+    #[must_use]
+    pub fn with_event_observer(mut self, observer: EventObserver<TYPES, I>) -> Self {
+        self.event_observer = Some(observer);
+        self
+    }
+
+    /// Drop messages sent by `own_key` instead of publishing them again.
+    #[must_use]
+    pub fn dropping_own_messages(mut self, own_key: TYPES::SignatureKey) -> Self {
+        self.drop_own_messages = Some(own_key);
+        self
+    }
+
+    /// Publish `event`, showing it to the observer first
+    async fn publish(&self, event: SequencingHotShotEvent<TYPES, I>) {
+        if let Some(observer) = &self.event_observer {
+            observer(&event);
+        }
+        self.event_stream.publish(event).await;
+    }
+
     /// Handle the message.
     pub async fn handle_messages(&mut self, messages: Vec<Message<TYPES, I>>) {
         // We will send only one event for a vector of transactions.
         let mut transactions = Vec::new();
         for message in messages {
             let sender = message.sender;
+            if self.drop_own_messages.as_ref() == Some(&sender) {
+                debug!("Dropping a message this node sent itself");
+                continue;
+            }
             match message.kind {
                 MessageKind::Consensus(consensus_message) => {
                     let event = match consensus_message.0 {
                         Either::Left(general_message) => match general_message {
                             GeneralConsensusMessage::Proposal(proposal) => {
+                                if let Err(e) = proposal.data.validate_well_formed() {
+                                    warn!("Dropping malformed quorum proposal: {e}");
+                                    continue;
+                                }
                                 SequencingHotShotEvent::QuorumProposalRecv(proposal.clone(), sender)
                             }
                             GeneralConsensusMessage::Vote(vote) => {
                                 SequencingHotShotEvent::QuorumVoteRecv(vote.clone())
                             }
                             GeneralConsensusMessage::ViewSyncVote(view_sync_message) => {
+                                if !view_sync_message.is_signed_by(&sender, self.chain_id) {
+                                    warn!(
+                                        "Dropping view sync vote for round {:?} not signed by its sender",
+                                        view_sync_message.round()
+                                    );
+                                    continue;
+                                }
                                 SequencingHotShotEvent::ViewSyncVoteRecv(view_sync_message)
                             }
                             GeneralConsensusMessage::ViewSyncCertificate(view_sync_message) => {
                                 SequencingHotShotEvent::ViewSyncCertificateRecv(view_sync_message)
                             }
-                            GeneralConsensusMessage::InternalTrigger(_) => {
-                                error!("Got unexpected message type in network task!");
-                                return;
+                            GeneralConsensusMessage::InternalTrigger(trigger) => {
+                                warn!("Received an internal-only message from the network");
+                                self.fallback(
+                                    UnhandledMessage::Unexpected,
+                                    Message {
+                                        sender,
+                                        kind: MessageKind::from_consensus_message(
+                                            SequencingMessage(Left(
+                                                GeneralConsensusMessage::InternalTrigger(trigger),
+                                            )),
+                                        ),
+                                        _phantom: PhantomData,
+                                    },
+                                );
+                                continue;
                             }
                         },
                         Either::Right(committee_message) => match committee_message {
                             CommitteeConsensusMessage::DAProposal(proposal) => {
+                                if let Err(e) = proposal.data.validate_well_formed() {
+                                    warn!("Dropping malformed DA proposal: {e}");
+                                    continue;
+                                }
                                 SequencingHotShotEvent::DAProposalRecv(proposal.clone(), sender)
                             }
                             CommitteeConsensusMessage::DAVote(vote) => {
                                 // error!("DA Vote message recv {:?}", vote.current_view);
+                                if let Some(da_membership) = &self.drop_if_not_committee {
+                                    if !da_membership
+                                        .get_committee(vote.current_view)
+                                        .contains(&sender)
+                                    {
+                                        warn!(
+                                            "Dropping DA vote for view {:?} from a sender not on the DA committee",
+                                            vote.current_view
+                                        );
+                                        continue;
+                                    }
+                                }
                                 SequencingHotShotEvent::DAVoteRecv(vote.clone())
                             }
                             CommitteeConsensusMessage::DACertificate(cert) => {
@@ -111,22 +308,126 @@ impl<
                     // TODO (Keyao benchmarking) Update these event variants (similar to the
                     // `TransactionsRecv` event) so we can send one event for a vector of messages.
                     // <https://github.com/EspressoSystems/HotShot/issues/1428>
-                    self.event_stream.publish(event).await;
+                    self.publish(event).await;
                 }
                 MessageKind::Data(message) => match message {
                     hotshot_types::message::DataMessage::SubmitTransaction(transaction, _) => {
                         transactions.push(transaction);
                     }
                 },
-                MessageKind::_Unreachable(_) => unimplemented!(),
+                MessageKind::_Unreachable(phantom) => {
+                    warn!("Received a placeholder message from the network");
+                    self.fallback(
+                        UnhandledMessage::Unknown,
+                        Message {
+                            sender,
+                            kind: MessageKind::_Unreachable(phantom),
+                            _phantom: PhantomData,
+                        },
+                    );
+                }
             };
         }
         if !transactions.is_empty() {
-            self.event_stream
-                .publish(SequencingHotShotEvent::TransactionsRecv(transactions))
+            self.publish(SequencingHotShotEvent::TransactionsRecv(transactions))
                 .await;
         }
     }
+
+    /// Pass a message that could not be turned into an event to the fallback handler, if any
+    fn fallback(&self, reason: UnhandledMessage, message: Message<TYPES, I>) {
+        if let Some(handler) = &self.fallback_handler {
+            handler(reason, message);
+        }
+    }
+}
+
+/// Messages and bytes the network accepted for one destination
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SendCounts {
+    /// Number of messages sent successfully
+    pub messages: u64,
+    /// Total serialized size of the messages sent
+    pub bytes: u64,
+}
+
+impl SendCounts {
+    /// Count one more message of `bytes` bytes
+    fn record(&mut self, bytes: u64) {
+        self.messages += 1;
+        self.bytes += bytes;
+    }
+}
+
+/// What the network event task has sent, per direct recipient and in broadcasts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendMetrics<KEY: Hash + Eq> {
+    /// Direct messages, keyed by recipient
+    pub direct: HashMap<KEY, SendCounts>,
+    /// Broadcast messages
+    pub broadcast: SendCounts,
+}
+
+impl<KEY: Hash + Eq> Default for SendMetrics<KEY> {
+    fn default() -> Self {
+        Self {
+            direct: HashMap::new(),
+            broadcast: SendCounts::default(),
+        }
+    }
+}
+
+impl<KEY: Hash + Eq + Clone> SendMetrics<KEY> {
+    /// A copy of the current counters, for dashboards
+    #[must_use]
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// Number of events queued for a network event task that it has not finished handling.
+///
+/// Events are counted as the task's [`NetworkEventTaskState::counted_filter`] lets them into its
+/// channel, so this is the task's send backlog. Clones share the count, so it can be read while
+/// the task is busy sending, or shared between several network event tasks to gauge a node's
+/// whole backlog.
+#[derive(Clone, Debug, Default)]
+pub struct OutboundGauge(Arc<AtomicUsize>);
+
+impl OutboundGauge {
+    /// Events waiting on, or being handled by, the network event task
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Count an event let into the task's channel
+    fn event_queued(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Stop counting an event once the task has finished handling it
+    pub fn event_handled(&self) {
+        // An `Err` means nothing was counted, e.g. the event bypassed `counted_filter`.
+        let _ = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |pending| {
+                pending.checked_sub(1)
+            });
+    }
+}
+
+/// A message the network event task has not yet managed to send
+#[derive(Clone, Debug)]
+pub struct QueuedMessage<TYPES: NodeType, I: NodeImplementation<TYPES>> {
+    /// The message to send
+    pub message: Message<TYPES, I>,
+    /// How to send it
+    pub transmit_type: TransmitType,
+    /// Who to send it to, for direct messages
+    pub recipient: Option<TYPES::SignatureKey>,
+    /// The view the task was in when it queued the message
+    pub view: TYPES::Time,
 }
 
 /// network event task state
@@ -148,6 +449,14 @@ pub struct NetworkEventTaskState<
     pub event_stream: ChannelStream<SequencingHotShotEvent<TYPES, I>>,
     /// view number
     pub view: TYPES::Time,
+    /// Number of messages handed to the network in each of the last [`RETAINED_VIEWS`] views
+    pub messages_per_view: BTreeMap<TYPES::Time, usize>,
+    /// Messages and bytes sent to each peer and in broadcasts
+    pub send_metrics: SendMetrics<TYPES::SignatureKey>,
+    /// Events queued for this task that it has not finished handling
+    pub outbound: OutboundGauge,
+    /// Messages waiting to be sent, including ones whose send failed in a way a retry may fix
+    pub outbound_queue: VecDeque<QueuedMessage<TYPES, I>>,
     /// phantom data
     pub phantom: PhantomData<(PROPOSAL, VOTE, MEMBERSHIP)>,
     // TODO ED Need to add exchange so we can get the recipient key and our own key?
@@ -181,6 +490,25 @@ impl<
         COMMCHANNEL: CommunicationChannel<TYPES, Message<TYPES, I>, PROPOSAL, VOTE, MEMBERSHIP>,
     > NetworkEventTaskState<TYPES, I, PROPOSAL, VOTE, MEMBERSHIP, COMMCHANNEL>
 {
+    /// Create a task state sending over `channel`, starting at the genesis view with no messages
+    /// counted
+    #[must_use]
+    pub fn new(
+        channel: COMMCHANNEL,
+        event_stream: ChannelStream<SequencingHotShotEvent<TYPES, I>>,
+    ) -> Self {
+        Self {
+            channel,
+            event_stream,
+            view: TYPES::Time::genesis(),
+            messages_per_view: BTreeMap::new(),
+            send_metrics: SendMetrics::default(),
+            outbound: OutboundGauge::default(),
+            outbound_queue: VecDeque::new(),
+            phantom: PhantomData,
+        }
+    }
+
     /// Handle the given event.
     ///
     /// Returns the completion status.
@@ -207,7 +535,7 @@ impl<
                 MessageKind::<TYPES, I>::from_consensus_message(SequencingMessage(Left(
                     GeneralConsensusMessage::Vote(vote.clone()),
                 ))),
-                TransmitType::Direct,
+                TransmitType::DirectWithFallbackBroadcast,
                 Some(membership.get_leader(vote.current_view() + 1)),
             ),
 
@@ -224,7 +552,7 @@ impl<
                 MessageKind::<TYPES, I>::from_consensus_message(SequencingMessage(Right(
                     CommitteeConsensusMessage::DAVote(vote.clone()),
                 ))),
-                TransmitType::Direct,
+                TransmitType::DirectWithFallbackBroadcast,
                 Some(membership.get_leader(vote.current_view)),
             ),
             // ED NOTE: This needs to be broadcasted to all nodes, not just ones on the DA committee
@@ -256,10 +584,11 @@ impl<
                 )
             }
             SequencingHotShotEvent::ViewChange(view) => {
-                self.view = view;
+                self.reset_for_view(view);
                 return None;
             }
             SequencingHotShotEvent::Shutdown => {
+                self.shutdown_and_flush(membership, FLUSH_DEADLINE).await;
                 return Some(HotShotTaskCompleted::ShutDown);
             }
             event => {
@@ -273,64 +602,162 @@ impl<
             kind: message_kind,
             _phantom: PhantomData,
         };
-        let transmit_result = match transmit_type {
-            TransmitType::Direct => {
-                self.channel
-                    .direct_message(message, recipient.unwrap())
-                    .await
-            }
-            TransmitType::Broadcast => self.channel.broadcast_message(message, membership).await,
-        };
+        *self.messages_per_view.entry(self.view).or_default() += 1;
+        self.outbound_queue.push_back(QueuedMessage {
+            message,
+            transmit_type,
+            recipient,
+            view: self.view,
+        });
+        self.send_queued(membership).await;
+
+        None
+    }
 
-        match transmit_result {
-            Ok(()) => {}
-            Err(e) => error!("Failed to send message from network task: {:?}", e),
+    /// Try once to send each queued message, oldest first.
+    ///
+    /// A message whose send fails in a way a retry may fix goes back on the queue, to be tried
+    /// again with the next event the task handles.
+    async fn send_queued(&mut self, membership: &MEMBERSHIP) {
+        for _ in 0..self.outbound_queue.len() {
+            let Some(queued) = self.outbound_queue.pop_front() else {
+                break;
+            };
+            match self.transmit(&queued, membership).await {
+                Ok(()) => {}
+                Err(e) if e.is_retryable() => {
+                    warn!(
+                        "Failed to send message from network task, will retry: {:?}",
+                        e
+                    );
+                    self.outbound_queue.push_back(queued);
+                }
+                Err(e) => error!("Failed to send message from network task: {:?}", e),
+            }
         }
+    }
 
-        None
+    /// Send `queued` over the channel, counting it in `send_metrics` once the network accepts it
+    async fn transmit(
+        &mut self,
+        queued: &QueuedMessage<TYPES, I>,
+        membership: &MEMBERSHIP,
+    ) -> Result<(), NetworkError> {
+        let message = queued.message.clone();
+        let bytes = bincode_opts().serialized_size(&message).unwrap_or(0);
+        match queued.transmit_type {
+            TransmitType::Direct | TransmitType::DirectWithFallbackBroadcast => {
+                let recipient = queued.recipient.clone().unwrap();
+                match self
+                    .channel
+                    .direct_message(message.clone(), recipient.clone())
+                    .await
+                {
+                    Ok(()) => {
+                        self.send_metrics
+                            .direct
+                            .entry(recipient)
+                            .or_default()
+                            .record(bytes);
+                        Ok(())
+                    }
+                    Err(e)
+                        if e.is_recipient_unreachable()
+                            && matches!(
+                                queued.transmit_type,
+                                TransmitType::DirectWithFallbackBroadcast
+                            ) =>
+                    {
+                        warn!(
+                            "{:?} is unreachable ({:?}), broadcasting instead",
+                            recipient, e
+                        );
+                        let result = self.channel.broadcast_message(message, membership).await;
+                        if result.is_ok() {
+                            self.send_metrics.broadcast.record(bytes);
+                        }
+                        result
+                    }
+                    result => result,
+                }
+            }
+            TransmitType::Broadcast => {
+                let result = self.channel.broadcast_message(message, membership).await;
+                if result.is_ok() {
+                    self.send_metrics.broadcast.record(bytes);
+                }
+                result
+            }
+        }
     }
 
-    /// network filter
-    pub fn filter(task_kind: NetworkTaskKind) -> FilterEvent<SequencingHotShotEvent<TYPES, I>> {
-        match task_kind {
-            NetworkTaskKind::Quorum => FilterEvent(Arc::new(Self::quorum_filter)),
-            NetworkTaskKind::Committee => FilterEvent(Arc::new(Self::committee_filter)),
-            NetworkTaskKind::ViewSync => FilterEvent(Arc::new(Self::view_sync_filter)),
+    /// Keep retrying the queued messages for up to `deadline`, so they are not lost when the
+    /// task stops.
+    ///
+    /// The task calls this when it handles [`SequencingHotShotEvent::Shutdown`]; `Drop` cannot
+    /// await a send. Returns the number of messages left unsent.
+    pub async fn shutdown_and_flush(
+        &mut self,
+        membership: &MEMBERSHIP,
+        deadline: Duration,
+    ) -> usize {
+        let flush = async {
+            self.send_queued(membership).await;
+            while !self.outbound_queue.is_empty() {
+                async_sleep(FLUSH_RETRY_INTERVAL).await;
+                self.send_queued(membership).await;
+            }
+        };
+        // On timeout, whatever was not sent is still queued.
+        let _ = async_timeout(deadline, flush).await;
+        let unsent = self.outbound_queue.len();
+        if unsent > 0 {
+            warn!("Network task stopped with {} messages unsent", unsent);
         }
+        unsent
     }
 
-    /// quorum filter
-    fn quorum_filter(event: &SequencingHotShotEvent<TYPES, I>) -> bool {
-        matches!(
-            event,
-            SequencingHotShotEvent::QuorumProposalSend(_, _)
-                | SequencingHotShotEvent::QuorumVoteSend(_)
-                | SequencingHotShotEvent::Shutdown
-                | SequencingHotShotEvent::DACSend(_, _)
-                | SequencingHotShotEvent::ViewChange(_)
-        )
+    /// Number of events queued for this task that it has not finished handling
+    #[must_use]
+    pub fn pending_outbound(&self) -> usize {
+        self.outbound.pending()
     }
 
-    /// committee filter
-    fn committee_filter(event: &SequencingHotShotEvent<TYPES, I>) -> bool {
-        matches!(
-            event,
-            SequencingHotShotEvent::DAProposalSend(_, _)
-                | SequencingHotShotEvent::DAVoteSend(_)
-                | SequencingHotShotEvent::Shutdown
-                | SequencingHotShotEvent::ViewChange(_)
-        )
+    /// Move to `view`, dropping per-view state, including queued messages, for views more than
+    /// [`RETAINED_VIEWS`] behind it
+    pub fn reset_for_view(&mut self, view: TYPES::Time) {
+        self.view = view;
+        let oldest = TYPES::Time::new((*view).saturating_sub(RETAINED_VIEWS));
+        self.messages_per_view = self.messages_per_view.split_off(&oldest);
+        let queued = self.outbound_queue.len();
+        self.outbound_queue.retain(|queued| queued.view >= oldest);
+        if self.outbound_queue.len() < queued {
+            warn!(
+                "Dropped {} queued messages from views before {:?}",
+                queued - self.outbound_queue.len(),
+                oldest
+            );
+        }
+    }
+
+    /// network filter
+    pub fn filter(task_kind: NetworkTaskKind) -> FilterEvent<SequencingHotShotEvent<TYPES, I>> {
+        FilterEvent(Arc::new(move |event| task_kind.handles(event)))
     }
 
-    /// view sync filter
-    fn view_sync_filter(event: &SequencingHotShotEvent<TYPES, I>) -> bool {
-        matches!(
-            event,
-            SequencingHotShotEvent::ViewSyncVoteSend(_)
-                | SequencingHotShotEvent::ViewSyncCertificateSend(_, _)
-                | SequencingHotShotEvent::Shutdown
-                | SequencingHotShotEvent::ViewChange(_)
-        )
+    /// [`Self::filter`], also counting each event it lets through in `outbound`, which the task
+    /// decrements with [`OutboundGauge::event_handled`] once the event is handled
+    pub fn counted_filter(
+        task_kind: NetworkTaskKind,
+        outbound: OutboundGauge,
+    ) -> FilterEvent<SequencingHotShotEvent<TYPES, I>> {
+        FilterEvent(Arc::new(move |event| {
+            let handles = task_kind.handles(event);
+            if handles {
+                outbound.event_queued();
+            }
+            handles
+        }))
     }
 }
 
@@ -11,8 +11,8 @@ use hotshot_task::{
 };
 use hotshot_types::traits::{election::Membership, network::ConsensusIntentEvent};
 
-use bitvec::prelude::*;
 use hotshot_task::global_registry::GlobalRegistry;
+pub use hotshot_types::vote::ViewSyncPhase;
 use hotshot_types::{
     certificate::ViewSyncCertificate,
     data::SequencingLeaf,
@@ -28,20 +28,8 @@ use hotshot_types::{
     vote::{ViewSyncData, ViewSyncVote, VoteAccumulator},
 };
 use snafu::Snafu;
-use std::{collections::HashMap, sync::Arc, time::Duration};
-use tracing::{debug, error, instrument};
-#[derive(PartialEq, PartialOrd, Clone, Debug, Eq, Hash)]
-/// Phases of view sync
-pub enum ViewSyncPhase {
-    /// No phase; before the protocol has begun
-    None,
-    /// PreCommit phase
-    PreCommit,
-    /// Commit phase
-    Commit,
-    /// Finalize phase
-    Finalize,
-}
+use std::{collections::HashMap, num::NonZeroU64, sync::Arc, time::Duration};
+use tracing::{debug, error, instrument, warn};
 
 #[derive(Default)]
 /// Information about view sync sub-tasks
@@ -99,6 +87,9 @@ pub struct ViewSyncTaskState<
     /// Timeout duration for view sync rounds
     pub view_sync_timeout: Duration,
 
+    /// Number of relays a round tries before giving up; `None` means unbounded
+    pub max_relay_attempts: Option<NonZeroU64>,
+
     /// Last view we garbage collected old tasks
     pub last_garbage_collected_view: TYPES::Time,
 }
@@ -159,6 +150,8 @@ pub struct ViewSyncReplicaTaskState<
     pub phase: ViewSyncPhase,
     /// The relay index we are currently on
     pub relay: u64,
+    /// Number of relays to try before giving up on the round; `None` means unbounded
+    pub max_relay_attempts: Option<NonZeroU64>,
     /// Whether we have seen a finalized certificate
     pub finalized: bool,
     /// Whether we have already sent a view change event for `next_view`
@@ -220,6 +213,8 @@ pub struct ViewSyncRelayTaskState<
         VoteAccumulator<TYPES::VoteTokenType, ViewSyncData<TYPES>>,
         ViewSyncCertificate<TYPES>,
     >,
+    /// The phase of the last certificate this relay formed in the round
+    pub phase: ViewSyncPhase,
     /// Our node id; for logging
     pub id: u64,
 }
@@ -310,6 +305,7 @@ where
                     api: self.api.clone(),
                     event_stream: self.event_stream.clone(),
                     view_sync_timeout: self.view_sync_timeout,
+                    max_relay_attempts: self.max_relay_attempts,
                     id: self.id,
                 };
 
@@ -371,33 +367,23 @@ where
 
                 // We do not have a relay task already running, so start one
 
-                if !self
-                    .exchange
-                    .is_leader(vote_internal.round + vote_internal.relay)
-                {
+                let Some(relay_view) = vote_internal.round.checked_add(vote_internal.relay) else {
+                    error!("View sync vote relay overflows its round");
+                    return;
+                };
+                if !self.exchange.is_leader(relay_view) {
                     // TODO ED This will occur because everyone is pulling down votes for now. Will be fixed in `https://github.com/EspressoSystems/HotShot/issues/1471`
                     debug!("View sync vote sent to wrong leader");
                     return;
                 }
 
-                let accumulator = VoteAccumulator {
-                    total_vote_outcomes: HashMap::new(),
-                    da_vote_outcomes: HashMap::new(),
-                    yes_vote_outcomes: HashMap::new(),
-                    no_vote_outcomes: HashMap::new(),
-                    viewsync_precommit_vote_outcomes: HashMap::new(),
-                    viewsync_commit_vote_outcomes: HashMap::new(),
-                    viewsync_finalize_vote_outcomes: HashMap::new(),
-                    success_threshold: self.exchange.success_threshold(),
-                    failure_threshold: self.exchange.failure_threshold(),
-                    sig_lists: Vec::new(),
-                    signers: bitvec![0; self.exchange.total_nodes()],
-                };
+                let accumulator = self.exchange.empty_accumulator();
 
                 let mut relay_state = ViewSyncRelayTaskState {
                     event_stream: self.event_stream.clone(),
                     exchange: self.exchange.clone(),
                     accumulator: either::Left(accumulator),
+                    phase: ViewSyncPhase::None,
                     id: self.id,
                 };
 
@@ -519,6 +505,7 @@ where
                         api: self.api.clone(),
                         event_stream: self.event_stream.clone(),
                         view_sync_timeout: self.view_sync_timeout,
+                        max_relay_attempts: self.max_relay_attempts,
                         id: self.id,
                     };
 
@@ -642,9 +629,14 @@ where
                     return (None, self);
                 }
 
-                let relay_key = self
-                    .exchange
-                    .get_leader(certificate_internal.round + certificate_internal.relay);
+                let Some(relay_view) = certificate_internal
+                    .round
+                    .checked_add(certificate_internal.relay)
+                else {
+                    error!("View sync certificate relay overflows its round");
+                    return (None, self);
+                };
+                let relay_key = self.exchange.get_leader(relay_view);
 
                 if !relay_key.validate(&message.signature, message.data.commit().as_ref()) {
                     error!("Key does not validate for certificate sender");
@@ -652,9 +644,13 @@ where
                 }
 
                 // If certificate is not valid, return current state
+                let exchange = self.exchange.clone();
+                let round = certificate_internal.round;
                 if !self
                     .exchange
-                    .is_valid_view_sync_cert(message.data, certificate_internal.round)
+                    .verification_pool()
+                    .offload(move || exchange.is_valid_view_sync_cert(message.data, round))
+                    .await
                 {
                     error!("Not valid view sync cert!");
 
@@ -689,6 +685,7 @@ where
 
                 // The protocol has ended
                 if self.phase == ViewSyncPhase::Finalize {
+                    self.exchange.metrics().views_recovered.add(1);
                     self.exchange
                         .network()
                         .inject_consensus_info(
@@ -732,7 +729,7 @@ where
                             ViewSyncPhase::Finalize => unimplemented!(),
                         };
 
-                        if let GeneralConsensusMessage::ViewSyncVote(vote) = message {
+                        if let Some(GeneralConsensusMessage::ViewSyncVote(vote)) = message {
                             // error!("Sending vs vote {:?}", vote.clone());
 
                             self.event_stream
@@ -759,7 +756,7 @@ where
                                 ViewSyncPhase::Finalize => unimplemented!(),
                             };
                             // error!("Sending vs vote {:?}", message.clone());
-                            if let GeneralConsensusMessage::ViewSyncVote(vote) = message {
+                            if let Some(GeneralConsensusMessage::ViewSyncVote(vote)) = message {
                                 // error!("Sending vs vote {:?}", vote.clone());
 
                                 self.event_stream
@@ -818,7 +815,7 @@ where
                             vote_token.clone(),
                         );
 
-                        if let GeneralConsensusMessage::ViewSyncVote(vote) = message {
+                        if let Some(GeneralConsensusMessage::ViewSyncVote(vote)) = message {
                             debug!(
                                 "Sending precommit vote to start protocol for next view = {}",
                                 *vote.round()
@@ -863,6 +860,19 @@ where
                     && relay == self.relay
                     && last_seen_certificate == self.phase
                 {
+                    // `relay` is the last relay tried, so `relay + 1` relays have timed out
+                    if let Some(max) = self.max_relay_attempts.filter(|max| relay >= max.get() - 1)
+                    {
+                        warn!(
+                            round = ?self.next_view,
+                            "View sync failed after trying {max} relays"
+                        );
+                        self.event_stream
+                            .publish(SequencingHotShotEvent::ViewSyncFailed(self.next_view))
+                            .await;
+                        return (None, self);
+                    }
+
                     let maybe_vote_token = self
                         .exchange
                         .membership()
@@ -894,7 +904,7 @@ where
                                 ViewSyncPhase::Finalize => unimplemented!(),
                             };
 
-                            if let GeneralConsensusMessage::ViewSyncVote(vote) = message {
+                            if let Some(GeneralConsensusMessage::ViewSyncVote(vote)) = message {
                                 self.event_stream
                                     .publish(SequencingHotShotEvent::ViewSyncVoteSend(vote))
                                     .await;
@@ -973,11 +983,20 @@ where
                     *vote_internal.round, vote_internal.relay, phase
                 );
 
+                if !self.phase.accepts_vote_for(&phase) {
+                    error!(
+                        "Rejecting {:?} vote after forming the {:?} certificate",
+                        phase, self.phase
+                    );
+                    return (None, self);
+                }
+
                 // Ignore this vote if we are not the correct relay
-                if !self
-                    .exchange
-                    .is_leader(vote_internal.round + vote_internal.relay)
-                {
+                let Some(relay_view) = vote_internal.round.checked_add(vote_internal.relay) else {
+                    error!("View sync vote relay overflows its round");
+                    return (None, self);
+                };
+                if !self.exchange.is_leader(relay_view) {
                     debug!("We are not the correct relay");
                     return (None, self);
                 }
@@ -993,16 +1012,24 @@ where
                     *vote_internal.round, vote_internal.relay
                 );
 
-                let accumulator = self.exchange.accumulate_vote(
-                    &vote_internal.signature.0,
-                    &vote_internal.signature.1,
-                    view_sync_data,
-                    vote_internal.vote_data,
-                    vote_internal.vote_token.clone(),
-                    vote_internal.round,
-                    self.accumulator.left().unwrap(),
-                    Some(vote_internal.relay),
-                );
+                let exchange = self.exchange.clone();
+                let accumulator = self.accumulator.left().unwrap();
+                let accumulator = self
+                    .exchange
+                    .verification_pool()
+                    .offload(move || {
+                        exchange.accumulate_vote(
+                            &vote_internal.signature.0,
+                            &vote_internal.signature.1,
+                            view_sync_data,
+                            vote_internal.vote_data,
+                            vote_internal.vote_token.clone(),
+                            vote_internal.round,
+                            accumulator,
+                            Some(vote_internal.relay),
+                        )
+                    })
+                    .await;
 
                 self.accumulator = match accumulator {
                     Left(new_accumulator) => Either::Left(new_accumulator),
@@ -1020,21 +1047,10 @@ where
                                 self.exchange.public_key().clone(),
                             ))
                             .await;
+                        self.phase = phase.clone();
 
                         // Reset accumulator for new certificate
-                        either::Left(VoteAccumulator {
-                            total_vote_outcomes: HashMap::new(),
-                            da_vote_outcomes: HashMap::new(),
-                            yes_vote_outcomes: HashMap::new(),
-                            no_vote_outcomes: HashMap::new(),
-                            viewsync_precommit_vote_outcomes: HashMap::new(),
-                            viewsync_commit_vote_outcomes: HashMap::new(),
-                            viewsync_finalize_vote_outcomes: HashMap::new(),
-                            success_threshold: self.exchange.success_threshold(),
-                            failure_threshold: self.exchange.failure_threshold(),
-                            sig_lists: Vec::new(),
-                            signers: bitvec![0; self.exchange.total_nodes()],
-                        })
+                        either::Left(self.exchange.empty_accumulator())
                     }
                 };
 
@@ -0,0 +1,36 @@
+//! Compares serializing with options built for every call, as the code base does through
+//! [`bincode_opts`], against reusing one set of options built up front.
+//!
+//! The options are zero-sized, so both should run at the same speed and produce the same bytes.
+//!
+//! Run with `cargo bench -p hotshot-testing --bench bincode_opts`.
+
+use bincode::Options;
+use hotshot_utils::bincode::bincode_opts;
+use std::time::Instant;
+
+/// Number of values serialized per measurement
+const ITERATIONS: u32 = 1_000_000;
+
+fn main() {
+    let value: (u64, Vec<u8>, String) = (42, vec![7; 64], "vote".to_string());
+
+    let start = Instant::now();
+    let mut fresh_bytes = Vec::new();
+    for _ in 0..ITERATIONS {
+        fresh_bytes = bincode_opts().serialize(&value).unwrap();
+    }
+    let fresh = start.elapsed();
+
+    let opts = bincode_opts();
+    let start = Instant::now();
+    let mut reused_bytes = Vec::new();
+    for _ in 0..ITERATIONS {
+        reused_bytes = opts.serialize(&value).unwrap();
+    }
+    let reused = start.elapsed();
+
+    assert_eq!(fresh_bytes, reused_bytes);
+    println!("{ITERATIONS} serializations with options built per call: {fresh:?}");
+    println!("{ITERATIONS} serializations with options built once: {reused:?}");
+}
@@ -0,0 +1,118 @@
+//! Measures how quickly a DA leader accumulates DA votes up to the threshold, for committees of
+//! several sizes.
+//!
+//! Every member signs its vote through its own [`CommitteeExchange`], so the signatures are over
+//! the chain-bound vote commitment, and the leader's exchange accumulates them with
+//! `ConsensusExchange::accumulate_vote`, exactly as the DA task does.
+//!
+//! Run with `cargo bench -p hotshot-testing --bench da_accumulation`.
+
+use commit::Committable;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use either::Either;
+use hotshot::{
+    demos::sdemo::{SDemoBlock, SDemoNormalBlock},
+    traits::election::static_committee::StaticElectionConfig,
+    types::{bn254::BN254Pub, SignatureKey},
+};
+use hotshot_testing::{
+    mock_channel::MockDAComm,
+    node_types::{SequencingMemoryImpl, SequencingTestTypes, StaticMembership},
+};
+use hotshot_types::{
+    data::ViewNumber,
+    message::{CommitteeConsensusMessage, Message},
+    traits::{
+        election::{CommitteeExchange, CommitteeExchangeType, ConsensusExchange},
+        state::ConsensusTime,
+    },
+    vote::DAVote,
+};
+
+/// Committee sizes to measure
+const COMMITTEE_SIZES: [u64; 3] = [10, 50, 100];
+
+type DAExchange = CommitteeExchange<
+    SequencingTestTypes,
+    StaticMembership,
+    MockDAComm,
+    Message<SequencingTestTypes, SequencingMemoryImpl>,
+>;
+
+/// The exchange of every member of a committee of `nodes` nodes
+fn committee(nodes: u64) -> Vec<DAExchange> {
+    let keys: Vec<_> = (0..nodes)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id))
+        .collect();
+    let entries: Vec<_> = keys
+        .iter()
+        .map(|(key, _)| key.get_stake_table_entry(1u64))
+        .collect();
+    keys.iter()
+        .map(|(key, private_key)| {
+            DAExchange::create(
+                entries.clone(),
+                keys.iter().map(|(key, _)| *key).collect(),
+                StaticElectionConfig::new(nodes, None).unwrap(),
+                MockDAComm::new(*key),
+                *key,
+                key.get_stake_table_entry(1u64),
+                private_key.clone(),
+            )
+        })
+        .collect()
+}
+
+fn da_accumulation(c: &mut Criterion) {
+    let commitment = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    })
+    .commit();
+    let view = ViewNumber::new(1);
+
+    let mut group = c.benchmark_group("da_accumulation");
+    for nodes in COMMITTEE_SIZES {
+        let exchanges = committee(nodes);
+        let votes: Vec<DAVote<SequencingTestTypes>> = exchanges
+            .iter()
+            .map(|exchange| {
+                let vote_token = exchange.make_vote_token(view).unwrap().unwrap();
+                let CommitteeConsensusMessage::DAVote(vote) =
+                    exchange.create_da_message(commitment, view, vote_token)
+                else {
+                    panic!("Expected a DA vote");
+                };
+                vote
+            })
+            .collect();
+        let leader = &exchanges[0];
+
+        group.throughput(Throughput::Elements(leader.success_threshold().get()));
+        group.bench_with_input(BenchmarkId::from_parameter(nodes), &votes, |b, votes| {
+            b.iter(|| {
+                let mut accumulator = leader.empty_accumulator();
+                for vote in votes {
+                    match leader.accumulate_vote(
+                        &vote.signature.0,
+                        &vote.signature.1,
+                        vote.block_commitment,
+                        vote.vote_data.clone(),
+                        vote.vote_token.clone(),
+                        vote.current_view,
+                        accumulator,
+                        None,
+                    ) {
+                        Either::Left(next) => accumulator = next,
+                        Either::Right(certificate) => return certificate,
+                    }
+                }
+                panic!("{nodes} votes did not reach the threshold");
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, da_accumulation);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+//! Compares checking the individual signatures of a certificate inline against checking them as
+//! one batch on a [`VerificationPool`], for committees of several sizes.
+//!
+//! The certificate is checked with `check_individual_signatures`, exactly as the exchanges check
+//! certificates that carry [`AssembledSignature::Individual`] signatures.
+//!
+//! Run with `cargo bench -p hotshot-testing --bench verification_pool`.
+//!
+//! [`AssembledSignature::Individual`]: hotshot_types::certificate::AssembledSignature::Individual
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hotshot::types::{bn254::BN254Pub, SignatureKey};
+use hotshot_testing::node_types::SequencingTestTypes;
+use hotshot_types::{certificate::check_individual_signatures, verification::VerificationPool};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    thread::available_parallelism,
+};
+
+/// Committee sizes to measure
+const COMMITTEE_SIZES: [u64; 3] = [10, 50, 100];
+
+fn verification_pool(c: &mut Criterion) {
+    let data = b"verification pool benchmark";
+    let threads = available_parallelism().unwrap_or(NonZeroUsize::new(4).unwrap());
+    let pools = [
+        ("inline", VerificationPool::inline()),
+        ("pooled", VerificationPool::new(threads).unwrap()),
+    ];
+
+    let mut group = c.benchmark_group("verification_pool");
+    for nodes in COMMITTEE_SIZES {
+        let keys: Vec<_> = (0..nodes)
+            .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id))
+            .collect();
+        let stake_table: Vec<_> = keys
+            .iter()
+            .map(|(key, _)| key.get_stake_table_entry(1u64))
+            .collect();
+        let signatures: Vec<_> = keys
+            .iter()
+            .map(|(key, private_key)| (key.to_bytes(), BN254Pub::sign(private_key, data)))
+            .collect();
+        let threshold = NonZeroU64::new(nodes).unwrap();
+
+        group.throughput(Throughput::Elements(nodes));
+        for (name, pool) in &pools {
+            group.bench_with_input(
+                BenchmarkId::new(*name, nodes),
+                &signatures,
+                |b, signatures| {
+                    b.iter(|| {
+                        assert!(check_individual_signatures::<SequencingTestTypes>(
+                            pool,
+                            signatures,
+                            &stake_table,
+                            threshold,
+                            data
+                        ));
+                    });
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, verification_pool);
+criterion_main!(benches);
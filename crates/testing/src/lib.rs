@@ -27,6 +27,9 @@ pub mod node_types;
 /// task to spin nodes up and down
 pub mod spinning_task;
 
+/// a communication channel driven by the test
+pub mod mock_channel;
+
 // TODO node changer (spin up and down)
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,242 @@
+use crate::node_types::{SequencingMemoryImpl, SequencingTestTypes, StaticMembership};
+use async_trait::async_trait;
+use hotshot_task::{boxed_sync, BoxSyncFuture};
+use hotshot_types::{
+    data::{DAProposal, ProposalType},
+    message::Message,
+    traits::{
+        election::Membership,
+        network::{CommunicationChannel, ConsensusIntentEvent, NetworkError, TransmitType},
+        node_implementation::{NodeImplementation, NodeType},
+    },
+    vote::{DAVote, VoteType},
+};
+use std::{
+    collections::{HashSet, VecDeque},
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// A message sent over a [`MockCommChannel`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecordedSend<KEY, M> {
+    /// The message was broadcast
+    Broadcast(M),
+    /// The message was sent directly to the recipient
+    Direct(M, KEY),
+}
+
+/// A [`CommunicationChannel`] that is controlled by the test using it.
+///
+/// Sends are recorded instead of delivered, the next sends can be made to fail, and inbound
+/// messages are whatever the test injects. Clones share their state, so a test can keep a clone
+/// to inspect and drive the channel it handed to a task.
+#[derive(Clone, Debug)]
+pub struct MockCommChannel<
+    TYPES: NodeType,
+    I: NodeImplementation<TYPES>,
+    PROPOSAL: ProposalType<NodeType = TYPES>,
+    VOTE: VoteType<TYPES>,
+    MEMBERSHIP: Membership<TYPES>,
+> {
+    /// The key this channel reports as its own
+    public_key: TYPES::SignatureKey,
+    /// Every successful send, in order
+    sends: Arc<Mutex<Vec<RecordedSend<TYPES::SignatureKey, Message<TYPES, I>>>>>,
+    /// How many of the next sends fail
+    failing_sends: Arc<AtomicUsize>,
+    /// Recipients that direct messages cannot reach
+    unreachable: Arc<Mutex<HashSet<TYPES::SignatureKey>>>,
+    /// Injected messages not yet received, by how they were transmitted
+    inbound: Arc<Mutex<(VecDeque<Message<TYPES, I>>, VecDeque<Message<TYPES, I>>)>>,
+    /// The consensus info injected by the task using the channel
+    consensus_info: Arc<Mutex<Vec<ConsensusIntentEvent>>>,
+    /// Whether the channel has been shut down
+    shut_down: Arc<AtomicBool>,
+    /// The kinds of proposals, votes and memberships the channel is used with
+    _phantom: PhantomData<(PROPOSAL, VOTE, MEMBERSHIP)>,
+}
+
+/// A [`MockCommChannel`] standing in for the test nodes' DA channel.
+pub type MockDAComm = MockCommChannel<
+    SequencingTestTypes,
+    SequencingMemoryImpl,
+    DAProposal<SequencingTestTypes>,
+    DAVote<SequencingTestTypes>,
+    StaticMembership,
+>;
+
+impl<
+        TYPES: NodeType,
+        I: NodeImplementation<TYPES>,
+        PROPOSAL: ProposalType<NodeType = TYPES>,
+        VOTE: VoteType<TYPES>,
+        MEMBERSHIP: Membership<TYPES>,
+    > MockCommChannel<TYPES, I, PROPOSAL, VOTE, MEMBERSHIP>
+{
+    /// Create a channel for the node with `public_key`.
+    #[must_use]
+    pub fn new(public_key: TYPES::SignatureKey) -> Self {
+        Self {
+            public_key,
+            sends: Arc::default(),
+            failing_sends: Arc::default(),
+            unreachable: Arc::default(),
+            inbound: Arc::default(),
+            consensus_info: Arc::default(),
+            shut_down: Arc::default(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Make the next `n` sends fail with [`NetworkError::CouldNotDeliver`] instead of being
+    /// recorded.
+    pub fn fail_next_n_sends(&self, n: usize) {
+        self.failing_sends.store(n, Ordering::SeqCst);
+    }
+
+    /// Make direct messages to `recipient` fail with [`NetworkError::Unreachable`] instead of
+    /// being recorded.
+    ///
+    /// # Panics
+    /// If a thread panicked while holding the channel's lock
+    pub fn set_unreachable(&self, recipient: TYPES::SignatureKey) {
+        self.unreachable.lock().unwrap().insert(recipient);
+    }
+
+    /// Every successful send so far, in order.
+    ///
+    /// # Panics
+    /// If a thread panicked while holding the channel's lock
+    #[must_use]
+    pub fn recorded_sends(&self) -> Vec<RecordedSend<TYPES::SignatureKey, Message<TYPES, I>>> {
+        self.sends.lock().unwrap().clone()
+    }
+
+    /// Queue `message` to be received as if it arrived with `transmit_type`.
+    ///
+    /// # Panics
+    /// If a thread panicked while holding the channel's lock
+    pub fn inject(&self, transmit_type: TransmitType, message: Message<TYPES, I>) {
+        let mut inbound = self.inbound.lock().unwrap();
+        match transmit_type {
+            TransmitType::Direct | TransmitType::DirectWithFallbackBroadcast => {
+                inbound.0.push_back(message);
+            }
+            TransmitType::Broadcast => inbound.1.push_back(message),
+        }
+    }
+
+    /// The consensus info injected into the channel so far, in order.
+    ///
+    /// # Panics
+    /// If a thread panicked while holding the channel's lock
+    #[must_use]
+    pub fn consensus_info(&self) -> Vec<ConsensusIntentEvent> {
+        self.consensus_info.lock().unwrap().clone()
+    }
+
+    /// Record `send`, unless the channel is shut down or the send is set up to fail.
+    fn send(
+        &self,
+        send: RecordedSend<TYPES::SignatureKey, Message<TYPES, I>>,
+    ) -> Result<(), NetworkError> {
+        if self.shut_down.load(Ordering::SeqCst) {
+            return Err(NetworkError::ShutDown);
+        }
+        if self
+            .failing_sends
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Err(NetworkError::CouldNotDeliver);
+        }
+        if let RecordedSend::Direct(_, recipient) = &send {
+            if self.unreachable.lock().unwrap().contains(recipient) {
+                return Err(NetworkError::Unreachable);
+            }
+        }
+        self.sends.lock().unwrap().push(send);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<
+        TYPES: NodeType,
+        I: NodeImplementation<TYPES>,
+        PROPOSAL: ProposalType<NodeType = TYPES>,
+        VOTE: VoteType<TYPES>,
+        MEMBERSHIP: Membership<TYPES>,
+    > CommunicationChannel<TYPES, Message<TYPES, I>, PROPOSAL, VOTE, MEMBERSHIP>
+    for MockCommChannel<TYPES, I, PROPOSAL, VOTE, MEMBERSHIP>
+{
+    type NETWORK = ();
+
+    fn local_public_key(&self) -> TYPES::SignatureKey {
+        self.public_key.clone()
+    }
+
+    async fn wait_for_ready(&self) {}
+
+    async fn is_ready(&self) -> bool {
+        true
+    }
+
+    fn shut_down<'a, 'b>(&'a self) -> BoxSyncFuture<'b, ()>
+    where
+        'a: 'b,
+        Self: 'b,
+    {
+        self.shut_down.store(true, Ordering::SeqCst);
+        boxed_sync(async {})
+    }
+
+    async fn broadcast_message(
+        &self,
+        message: Message<TYPES, I>,
+        _election: &MEMBERSHIP,
+    ) -> Result<(), NetworkError> {
+        self.send(RecordedSend::Broadcast(message))
+    }
+
+    async fn direct_message(
+        &self,
+        message: Message<TYPES, I>,
+        recipient: TYPES::SignatureKey,
+    ) -> Result<(), NetworkError> {
+        self.send(RecordedSend::Direct(message, recipient))
+    }
+
+    fn recv_msgs<'a, 'b>(
+        &'a self,
+        transmit_type: TransmitType,
+    ) -> BoxSyncFuture<'b, Result<Vec<Message<TYPES, I>>, NetworkError>>
+    where
+        'a: 'b,
+        Self: 'b,
+    {
+        let result = if self.shut_down.load(Ordering::SeqCst) {
+            Err(NetworkError::ShutDown)
+        } else {
+            let mut inbound = self.inbound.lock().unwrap();
+            let queue = match transmit_type {
+                TransmitType::Direct | TransmitType::DirectWithFallbackBroadcast => &mut inbound.0,
+                TransmitType::Broadcast => &mut inbound.1,
+            };
+            Ok(queue.drain(..).collect())
+        };
+        boxed_sync(async move { result })
+    }
+
+    async fn lookup_node(&self, _pk: TYPES::SignatureKey) -> Result<(), NetworkError> {
+        Ok(())
+    }
+
+    async fn inject_consensus_info(&self, event: ConsensusIntentEvent) {
+        self.consensus_info.lock().unwrap().push(event);
+    }
+}
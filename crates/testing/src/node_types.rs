@@ -48,6 +48,10 @@ impl NodeType for SequencingTestTypes {
     type Transaction = SDemoTransaction;
     type ElectionConfigType = StaticElectionConfig;
     type StateType = SDemoState;
+
+    fn genesis() -> (Self::BlockType, Self::StateType) {
+        SDemoState::genesis()
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Hash, Eq, PartialEq)]
@@ -3,7 +3,7 @@ use either::Either;
 use hotshot_task::{event_stream::EventStream, Merge};
 use hotshot_task_impls::events::SequencingHotShotEvent;
 use std::{
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     sync::Arc,
 };
 
@@ -24,9 +24,10 @@ use hotshot_types::{
     data::{DeltasType, LeafBlock, LeafType},
     error::RoundTimedoutState,
     event::{Event, EventType},
-    traits::node_implementation::NodeType,
+    traits::{node_implementation::NodeType, signature_key::SignatureKey},
 };
 use snafu::Snafu;
+use tracing::info;
 
 use crate::{test_launcher::TaskGenerator, test_runner::Node};
 pub type StateAndBlock<S, B> = (Vec<S>, Vec<B>);
@@ -71,6 +72,24 @@ pub enum OverallSafetyTaskErr {
     InconsistentStates,
     /// mismatched blocks for a view
     InconsistentBlocks,
+    /// a view was led by a node other than the one the schedule expects
+    UnexpectedLeader {
+        /// view number
+        view: u64,
+        /// expected leader
+        expected: u64,
+        /// actual leader
+        got: u64,
+    },
+    /// a decided block held more transactions than allowed
+    BlockTooLarge {
+        /// view of the block
+        view: u64,
+        /// number of transactions in the block
+        size: u64,
+        /// maximum number of transactions allowed
+        max: u64,
+    },
 }
 
 /// Data availability task state
@@ -135,6 +154,7 @@ impl<TYPES: NodeType, I: TestableNodeImplementation<TYPES>> Default for RoundCtx
             round_results: Default::default(),
             failed_views: Default::default(),
             successful_views: Default::default(),
+            leaders: Default::default(),
         }
     }
 }
@@ -153,6 +173,8 @@ pub struct RoundCtx<TYPES: NodeType, I: TestableNodeImplementation<TYPES>> {
     pub failed_views: HashSet<TYPES::Time>,
     /// successful views
     pub successful_views: HashSet<TYPES::Time>,
+    /// view number -> id of the node that proposed the leaf decided in that view
+    pub leaders: BTreeMap<TYPES::Time, u64>,
 }
 
 impl<TYPES: NodeType, I: TestableNodeImplementation<TYPES>> RoundCtx<TYPES, I> {
@@ -178,6 +200,47 @@ impl<TYPES: NodeType, I: TestableNodeImplementation<TYPES>> RoundCtx<TYPES, I> {
             }
         }
     }
+
+    /// records the proposer of each decided leaf in the leader log
+    pub fn insert_leaders(
+        &mut self,
+        handles: &[Node<TYPES, I>],
+        leaf_chain: &[<I as NodeImplementation<TYPES>>::Leaf],
+    ) {
+        for leaf in leaf_chain {
+            let proposer_id = leaf.get_proposer_id();
+            // the genesis leaf has no proposer among the nodes
+            if let Some(node) = handles
+                .iter()
+                .find(|node| node.handle.get_public_key().to_bytes() == proposer_id)
+            {
+                self.leaders.insert(leaf.get_view_number(), node.node_id);
+            }
+        }
+    }
+
+    /// checks that every logged view was led by `expected_schedule[view % expected_schedule.len()]`
+    /// # Errors
+    /// if a view was led by another node
+    pub fn assert_leader_rotation(
+        &self,
+        expected_schedule: &[u64],
+    ) -> Result<(), OverallSafetyTaskErr> {
+        if expected_schedule.is_empty() {
+            return Ok(());
+        }
+        for (view, leader) in &self.leaders {
+            let expected = expected_schedule[(**view % expected_schedule.len() as u64) as usize];
+            if *leader != expected {
+                return Err(OverallSafetyTaskErr::UnexpectedLeader {
+                    view: **view,
+                    expected,
+                    got: *leader,
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> RoundResult<TYPES, LEAF> {
@@ -346,6 +409,11 @@ pub struct OverallSafetyPropertiesDescription {
     /// threshold calculator. Given number of live and total nodes, provide number of successes
     /// required to mark view as successful
     pub threshold_calculator: Arc<dyn Fn(usize, usize) -> usize + Send + Sync>,
+    /// if set, the id of the node expected to lead view `v` is
+    /// `expected_leader_schedule[v % expected_leader_schedule.len()]`
+    pub expected_leader_schedule: Option<Vec<u64>>,
+    /// if set, every decided block must hold at most this many transactions
+    pub max_block_size: Option<u64>,
 }
 
 impl std::fmt::Debug for OverallSafetyPropertiesDescription {
@@ -356,6 +424,8 @@ impl std::fmt::Debug for OverallSafetyPropertiesDescription {
             .field("check_state", &self.check_state)
             .field("check_block", &self.check_block)
             .field("num_failed_rounds_total", &self.num_failed_views)
+            .field("expected_leader_schedule", &self.expected_leader_schedule)
+            .field("max_block_size", &self.max_block_size)
             .finish()
     }
 }
@@ -371,6 +441,8 @@ impl Default for OverallSafetyPropertiesDescription {
             transaction_threshold: 0,
             // very strict
             threshold_calculator: Arc::new(|_num_live, num_total| 2 * num_total / 3 + 1),
+            expected_leader_schedule: None,
+            max_block_size: None,
         }
     }
 }
@@ -388,15 +460,28 @@ impl OverallSafetyPropertiesDescription {
             num_successful_views,
             threshold_calculator,
             transaction_threshold,
+            expected_leader_schedule,
+            max_block_size,
         }: Self = self;
 
         Box::new(move |mut state, mut registry, test_event_stream| {
             async move {
                 let event_handler = HandleEvent::<OverallSafetyTaskTypes<TYPES, I>>(Arc::new(
                     move |event, state| {
+                        let expected_leader_schedule = expected_leader_schedule.clone();
                         async move {
                             match event {
                                 GlobalTestEvent::ShutDown => {
+                                    info!("Leader log (view -> node): {:?}", state.ctx.leaders);
+                                    if let Some(schedule) = expected_leader_schedule {
+                                        if let Err(e) = state.ctx.assert_leader_rotation(&schedule) {
+                                            return (
+                                                Some(HotShotTaskCompleted::Error(Box::new(e))),
+                                                state,
+                                            );
+                                        }
+                                    }
+
                                     let num_incomplete_views = state.ctx.round_results.len()
                                         - state.ctx.successful_views.len()
                                         - state.ctx.failed_views.len();
@@ -452,6 +537,27 @@ impl OverallSafetyPropertiesDescription {
                                         qc,
                                         block_size: maybe_block_size,
                                     } => {
+                                        if let Some(max) = max_block_size {
+                                            for leaf in leaf_chain.iter() {
+                                                let Ok(block) = leaf.get_deltas().try_resolve() else {
+                                                    continue;
+                                                };
+                                                let size = I::txn_count(&block);
+                                                if size > max {
+                                                    return (
+                                                        Some(HotShotTaskCompleted::Error(Box::new(
+                                                            OverallSafetyTaskErr::BlockTooLarge {
+                                                                view: *leaf.get_view_number(),
+                                                                size,
+                                                                max,
+                                                            },
+                                                        ))),
+                                                        state,
+                                                    );
+                                                }
+                                            }
+                                        }
+                                        state.ctx.insert_leaders(&state.handles, &leaf_chain);
                                         let paired_up = (leaf_chain.to_vec(), (*qc).clone());
                                         match state.ctx.round_results.entry(view_number) {
                                             Entry::Occupied(mut o) => o.get_mut().insert_into_result(
@@ -2,27 +2,38 @@ use crate::{
     node_types::{SequencingMemoryImpl, SequencingTestTypes},
     test_builder::TestMetadata,
 };
+use async_trait::async_trait;
 use commit::Committable;
 use either::Right;
 use hotshot::{
     certificate::QuorumCertificate,
-    traits::{Block, NodeImplementation, TestableNodeImplementation},
+    traits::{Block, NodeImplementation},
     types::{bn254::BN254Pub, SignatureKey, SystemContextHandle},
     HotShotInitializer, HotShotSequencingConsensusApi, SystemContext,
 };
 use hotshot_task::event_stream::ChannelStream;
 use hotshot_task_impls::events::SequencingHotShotEvent;
 use hotshot_types::{
-    data::{QuorumProposal, SequencingLeaf, ViewNumber},
-    message::{Message, Proposal},
+    consensus::ProposalSlots,
+    data::{ProposalType, QuorumProposal, SequencingLeaf, ViewNumber},
+    event::Event,
+    message::{DataMessage, Message, Proposal, SequencingMessage},
     traits::{
-        consensus_api::ConsensusSharedApi,
-        election::{ConsensusExchange, Membership, SignedCertificate},
+        consensus_api::{ConsensusSharedApi, SequencingConsensusApi},
+        election::{ConsensusExchange, Membership, ProposalData, SignedCertificate},
         metrics::NoMetrics,
+        network::NetworkError,
         node_implementation::{CommitteeEx, ExchangesType, NodeType, QuorumEx},
         signature_key::EncodedSignature,
         state::ConsensusTime,
+        storage::StorageError,
     },
+    vote::VoteType,
+};
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 pub async fn build_system_handle(
@@ -42,10 +53,7 @@ pub async fn build_system_handle(
     let initializer = HotShotInitializer::<
         SequencingTestTypes,
         <SequencingMemoryImpl as NodeImplementation<SequencingTestTypes>>::Leaf,
-    >::from_genesis(<SequencingMemoryImpl as TestableNodeImplementation<
-        SequencingTestTypes,
-    >>::block_genesis())
-    .unwrap();
+    >::from_genesis();
 
     let known_nodes = config.known_nodes.clone();
     let known_nodes_with_stake = config.known_nodes_with_stake.clone();
@@ -131,7 +139,10 @@ async fn build_quorum_proposal_and_signature(
         timestamp: 0,
         proposer_id: api.public_key().to_bytes(),
     };
-    let signature = <BN254Pub as SignatureKey>::sign(private_key, leaf.commit().as_ref());
+    let signature = <BN254Pub as SignatureKey>::sign(
+        private_key,
+        ProposalData::Quorum(leaf.commit()).commit().as_ref(),
+    );
     let proposal = QuorumProposal::<SequencingTestTypes, SequencingLeaf<SequencingTestTypes>> {
         block_commitment,
         view_number: ViewNumber::new(view),
@@ -163,3 +174,195 @@ pub fn key_pair_for_id(node_id: u64) -> (<BN254Pub as SignatureKey>::PrivateKey,
     let public_key = <SequencingTestTypes as NodeType>::SignatureKey::from_private(&private_key);
     (private_key, public_key)
 }
+
+/// A [`HotShotSequencingConsensusApi`] whose clock only moves when the test advances it, and
+/// whose leaders wait for `min_transactions` transactions regardless of the arrival rate, for
+/// `propose_min_round_time` regardless of the configuration, and up to `round_time_per_node`
+/// longer per committee member, and include at most `max_transactions` transactions. Leaders
+/// that cannot wait for transactions propose an empty block after `propose_empty_after`.
+#[derive(Clone)]
+pub struct MockClockApi {
+    /// The api everything but the clock is delegated to
+    pub inner: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl>,
+    /// The time `now` reports
+    pub now: Arc<Mutex<Instant>>,
+    /// Transactions a leader waits for before proposing early
+    pub min_transactions: usize,
+    /// The soonest a leader may propose after starting to wait for transactions
+    pub propose_min_round_time: Duration,
+    /// Extra time a leader waits for transactions for each member of its committee
+    pub round_time_per_node: Duration,
+    /// Most transactions a leader includes in a block
+    pub max_transactions: NonZeroUsize,
+    /// How long a leader that cannot wait for transactions waits before proposing an empty block
+    pub propose_empty_after: Option<Duration>,
+}
+
+impl MockClockApi {
+    /// Wrap the api of `handle`, starting the clock at the current wall-clock time.
+    pub fn new(
+        handle: &SystemContextHandle<SequencingTestTypes, SequencingMemoryImpl>,
+        min_transactions: usize,
+    ) -> Self {
+        let inner = HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+        Self {
+            max_transactions: inner.max_transactions(),
+            propose_empty_after: inner.propose_empty_after(),
+            inner,
+            now: Arc::new(Mutex::new(Instant::now())),
+            min_transactions,
+            propose_min_round_time: Duration::ZERO,
+            round_time_per_node: Duration::ZERO,
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+#[async_trait]
+impl
+    ConsensusSharedApi<
+        SequencingTestTypes,
+        SequencingLeaf<SequencingTestTypes>,
+        SequencingMemoryImpl,
+    > for MockClockApi
+{
+    fn total_nodes(&self) -> NonZeroUsize {
+        self.inner.total_nodes()
+    }
+
+    fn propose_min_round_time(&self) -> Duration {
+        self.propose_min_round_time
+    }
+
+    fn propose_max_round_time(&self) -> Duration {
+        self.inner.propose_max_round_time()
+    }
+
+    fn round_time_for_committee_size(&self, committee_size: usize) -> Duration {
+        self.propose_max_round_time()
+            + self.round_time_per_node * u32::try_from(committee_size).unwrap()
+    }
+
+    fn propose_empty_after(&self) -> Option<Duration> {
+        self.propose_empty_after
+    }
+
+    fn proposal_slots(&self) -> &ProposalSlots {
+        self.inner.proposal_slots()
+    }
+
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+
+    async fn store_leaf(
+        &self,
+        old_anchor_view: ViewNumber,
+        leaf: SequencingLeaf<SequencingTestTypes>,
+    ) -> Result<(), StorageError> {
+        self.inner.store_leaf(old_anchor_view, leaf).await
+    }
+
+    fn max_transactions(&self) -> NonZeroUsize {
+        self.max_transactions
+    }
+
+    fn min_transactions(&self) -> usize {
+        self.min_transactions
+    }
+
+    async fn pending_transaction_count(&self) -> usize {
+        self.inner.pending_transaction_count().await
+    }
+
+    async fn pending_transaction_bytes(&self) -> u64 {
+        self.inner.pending_transaction_bytes().await
+    }
+
+    async fn should_start_round(&self, view_number: ViewNumber) -> bool {
+        self.inner.should_start_round(view_number).await
+    }
+
+    async fn send_event(
+        &self,
+        event: Event<SequencingTestTypes, SequencingLeaf<SequencingTestTypes>>,
+    ) {
+        self.inner.send_event(event).await;
+    }
+
+    fn public_key(&self) -> &BN254Pub {
+        self.inner.public_key()
+    }
+
+    fn private_key(&self) -> &<BN254Pub as SignatureKey>::PrivateKey {
+        self.inner.private_key()
+    }
+}
+
+#[async_trait]
+impl
+    SequencingConsensusApi<
+        SequencingTestTypes,
+        SequencingLeaf<SequencingTestTypes>,
+        SequencingMemoryImpl,
+    > for MockClockApi
+{
+    async fn send_direct_message<
+        PROPOSAL: ProposalType<NodeType = SequencingTestTypes>,
+        VOTE: VoteType<SequencingTestTypes>,
+    >(
+        &self,
+        recipient: BN254Pub,
+        message: SequencingMessage<SequencingTestTypes, SequencingMemoryImpl>,
+    ) -> Result<(), NetworkError> {
+        self.inner
+            .send_direct_message::<PROPOSAL, VOTE>(recipient, message)
+            .await
+    }
+
+    async fn send_direct_da_message<
+        PROPOSAL: ProposalType<NodeType = SequencingTestTypes>,
+        VOTE: VoteType<SequencingTestTypes>,
+    >(
+        &self,
+        recipient: BN254Pub,
+        message: SequencingMessage<SequencingTestTypes, SequencingMemoryImpl>,
+    ) -> Result<(), NetworkError> {
+        self.inner
+            .send_direct_da_message::<PROPOSAL, VOTE>(recipient, message)
+            .await
+    }
+
+    async fn send_broadcast_message<
+        PROPOSAL: ProposalType<NodeType = SequencingTestTypes>,
+        VOTE: VoteType<SequencingTestTypes>,
+    >(
+        &self,
+        message: SequencingMessage<SequencingTestTypes, SequencingMemoryImpl>,
+    ) -> Result<(), NetworkError> {
+        self.inner
+            .send_broadcast_message::<PROPOSAL, VOTE>(message)
+            .await
+    }
+
+    async fn send_da_broadcast(
+        &self,
+        message: SequencingMessage<SequencingTestTypes, SequencingMemoryImpl>,
+    ) -> Result<(), NetworkError> {
+        self.inner.send_da_broadcast(message).await
+    }
+
+    #[allow(deprecated)]
+    async fn send_transaction(
+        &self,
+        message: DataMessage<SequencingTestTypes>,
+    ) -> Result<(), NetworkError> {
+        self.inner.send_transaction(message).await
+    }
+}
@@ -12,6 +12,9 @@ use hotshot_types::{
 
 use super::completion_task::{CompletionTaskDescription, TimeBasedCompletionTaskDescription};
 use crate::{
+    node_types::{
+        SequencingLibp2pImpl, SequencingMemoryImpl, SequencingTestTypes, SequencingWebImpl,
+    },
     spinning_task::SpinningTaskDescription,
     test_launcher::{ResourceGenerators, TestLauncher},
 };
@@ -21,6 +24,17 @@ use super::{
 };
 use hotshot::{HotShotType, SystemContext};
 
+/// The network a sequencing test's nodes communicate over
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transport {
+    /// In-process memory network
+    Memory,
+    /// libp2p network
+    Libp2p,
+    /// Centralized web server
+    WebServer,
+}
+
 /// data describing how a round should be timed.
 #[derive(Clone, Debug, Copy)]
 pub struct TimingData {
@@ -61,6 +75,13 @@ pub struct TestMetadata {
     pub min_transactions: usize,
     /// timing data
     pub timing_data: TimingData,
+    /// Stake of each node; every node has a stake of 1 if unset
+    pub stake_distribution: Option<Vec<u64>>,
+    /// View of the genesis leaf; the first proposal is for the view after it
+    pub genesis_view: u64,
+    /// Seed for the randomness of the generated transaction load, so a failing run's load can be
+    /// replayed; drawn from entropy if unset. Task scheduling and network timing are not seeded.
+    pub rng_seed: Option<u64>,
 }
 
 impl Default for TimingData {
@@ -90,6 +111,8 @@ impl TestMetadata {
                 num_failed_views: 15,
                 transaction_threshold: 0,
                 threshold_calculator: Arc::new(|_active, total| (2 * total / 3 + 1)),
+                expected_leader_schedule: None,
+                max_block_size: None,
             },
             timing_data: TimingData {
                 next_view_timeout: 2000,
@@ -114,6 +137,8 @@ impl TestMetadata {
                 num_failed_views: 8,
                 transaction_threshold: 0,
                 threshold_calculator: Arc::new(|_active, total| (2 * total / 3 + 1)),
+                expected_leader_schedule: None,
+                max_block_size: None,
             },
             timing_data: TimingData {
                 start_delay: 120000,
@@ -156,6 +181,9 @@ impl Default for TestMetadata {
     fn default() -> Self {
         Self {
             timing_data: TimingData::default(),
+            stake_distribution: None,
+            genesis_view: 0,
+            rng_seed: None,
             min_transactions: 0,
             total_nodes: 5,
             start_nodes: 5,
@@ -178,6 +206,49 @@ impl Default for TestMetadata {
 }
 
 impl TestMetadata {
+    /// Run the test with one node per entry of `stakes`, each holding that stake, and pick
+    /// leaders in proportion to it.
+    #[must_use]
+    pub fn with_stake_distribution(mut self, stakes: Vec<u64>) -> Self {
+        self.total_nodes = stakes.len();
+        self.start_nodes = stakes.len();
+        self.num_bootstrap_nodes = self.num_bootstrap_nodes.min(stakes.len());
+        self.da_committee_size = self.da_committee_size.min(stakes.len());
+        self.stake_distribution = Some(stakes);
+        self
+    }
+
+    /// Draw the transaction load's randomness from `seed`, so a run's load can be replayed.
+    #[must_use]
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// Launch the test with the sequencing test types over `transport` and run it to completion
+    pub async fn run_sequencing_test(self, transport: Transport) {
+        match transport {
+            Transport::Memory => {
+                self.gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>()
+                    .launch()
+                    .run_test()
+                    .await;
+            }
+            Transport::Libp2p => {
+                self.gen_launcher::<SequencingTestTypes, SequencingLibp2pImpl>()
+                    .launch()
+                    .run_test()
+                    .await;
+            }
+            Transport::WebServer => {
+                self.gen_launcher::<SequencingTestTypes, SequencingWebImpl>()
+                    .launch()
+                    .run_test()
+                    .await;
+            }
+        }
+    }
+
     pub fn gen_launcher<TYPES: NodeType, I: TestableNodeImplementation<TYPES>>(
         self,
     ) -> TestLauncher<TYPES, I>
@@ -197,6 +268,8 @@ impl TestMetadata {
             completion_task_description,
             overall_safety_properties,
             spinning_properties,
+            stake_distribution,
+            rng_seed,
             ..
         } = self.clone();
 
@@ -209,7 +282,10 @@ impl TestMetadata {
             .collect();
         let known_nodes_with_stake: Vec<<TYPES::SignatureKey as SignatureKey>::StakeTableEntry> =
             (0..total_nodes)
-                .map(|id| known_nodes[id].get_stake_table_entry(1u64))
+                .map(|id| {
+                    let stake = stake_distribution.as_ref().map_or(1, |stakes| stakes[id]);
+                    known_nodes[id].get_stake_table_entry(stake)
+                })
                 .collect();
         // let da_committee_nodes = known_nodes[0..da_committee_size].to_vec();
         let config = HotShotConfig {
@@ -229,13 +305,37 @@ impl TestMetadata {
             // TODO do we use these fields??
             propose_min_round_time: Duration::from_millis(0),
             propose_max_round_time: Duration::from_millis(1000),
+            max_buffered_views: None,
+            propose_empty_after: None,
+            allow_empty_blocks: false,
+            max_empty_fallbacks: NonZeroUsize::new(3).unwrap(),
+            verification_threads: None,
+            signature_cache_size: None,
+            max_inflight_proposals: None,
+            max_view_retries: None,
+            da_availability_timeout: None,
+            max_relay_attempts: None,
+            relay_da_votes: false,
+            drop_own_messages: false,
+            vote_queue_capacity: None,
             // TODO what's the difference between this and the second config?
-            election_config: Some(<QuorumEx<TYPES, I> as ConsensusExchange<
-                TYPES,
-                Message<TYPES, I>,
-            >>::Membership::default_election_config(
-                total_nodes as u64
-            )),
+            election_config:
+                Some(
+                    match &stake_distribution {
+                        Some(stakes) => <QuorumEx<TYPES, I> as ConsensusExchange<
+                            TYPES,
+                            Message<TYPES, I>,
+                        >>::Membership::stake_weighted_election_config(
+                            stakes
+                        ),
+                        None => <QuorumEx<TYPES, I> as ConsensusExchange<
+                            TYPES,
+                            Message<TYPES, I>,
+                        >>::Membership::default_election_config(
+                            total_nodes as u64
+                        ),
+                    },
+                ),
         };
         let TimingData {
             next_view_timeout,
@@ -256,7 +356,7 @@ impl TestMetadata {
                 a.propose_max_round_time = propose_max_round_time;
             };
 
-        let txn_task_generator = txn_description.build();
+        let txn_task_generator = txn_description.build(rng_seed);
         let completion_task_generator = completion_task_description.build_and_launch();
         let overall_safety_task_generator = overall_safety_properties.build();
         let spinning_task_generator = spinning_properties.build();
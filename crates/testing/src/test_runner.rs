@@ -19,6 +19,7 @@ use hotshot_types::{
         network::CommunicationChannel,
         node_implementation::{ExchangesType, NodeType, QuorumCommChannel, QuorumEx},
         signature_key::SignatureKey,
+        state::ConsensusTime,
     },
     HotShotConfig,
 };
@@ -207,8 +208,9 @@ where
             let node_id = self.next_node_id;
             let storage = (self.launcher.resource_generator.storage)(node_id);
             let config = self.launcher.resource_generator.config.clone();
-            let initializer =
-                HotShotInitializer::<TYPES, I::Leaf>::from_genesis(I::block_genesis()).unwrap();
+            let initializer = HotShotInitializer::<TYPES, I::Leaf>::from_genesis_at(
+                TYPES::Time::new(self.launcher.metadata.genesis_view),
+            );
             let networks = (self.launcher.resource_generator.channel_generator)(node_id);
             let hotshot = self
                 .add_node_with_config(networks, storage, initializer, config)
@@ -255,7 +257,10 @@ where
         // Generate key pair for certificate aggregation
         let private_key = TYPES::SignatureKey::generated_from_seed_indexed([0u8; 32], node_id).1;
         let public_key = TYPES::SignatureKey::from_private(&private_key);
-        let entry = public_key.get_stake_table_entry(1u64);
+        let entry = known_nodes_with_stake
+            .get(node_id as usize)
+            .cloned()
+            .unwrap_or_else(|| public_key.get_stake_table_entry(1u64));
         let quorum_election_config = config.election_config.clone().unwrap_or_else(|| {
             <QuorumEx<TYPES,I> as ConsensusExchange<
                 TYPES,
@@ -13,9 +13,15 @@ use hotshot_types::{
     message::SequencingMessage,
     traits::node_implementation::{NodeImplementation, NodeType},
 };
-use rand::thread_rng;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use snafu::Snafu;
-use std::{sync::Arc, time::Duration};
+use std::{
+    fmt::Debug,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use super::{test_launcher::TaskGenerator, GlobalTestEvent};
 
@@ -47,20 +53,65 @@ pub type TxnTaskTypes<TYPES, I> = HSTWithEventAndMessage<
     TxnTask<TYPES, I>,
 >;
 
+/// Produces the transaction load of a test: when each burst of transactions is submitted and how
+/// large the transactions in it are
+pub trait TxnGenerator: Send + Sync + Debug {
+    /// How long to wait before submitting the next burst
+    fn next_delay(&self, rng: &mut dyn RngCore) -> Duration;
+
+    /// The padding, in bytes, of each transaction in the next burst
+    fn next_burst(&self, rng: &mut dyn RngCore) -> Vec<u64>;
+}
+
+/// Submits a burst of a random number of randomly padded transactions every `interval`
+#[derive(Clone, Debug)]
+pub struct BurstyTxnGenerator {
+    /// time between bursts
+    pub interval: Duration,
+    /// number of transactions in each burst
+    pub burst_size: RangeInclusive<usize>,
+    /// bytes of padding on each transaction
+    pub padding: RangeInclusive<u64>,
+}
+
+impl TxnGenerator for BurstyTxnGenerator {
+    fn next_delay(&self, _rng: &mut dyn RngCore) -> Duration {
+        self.interval
+    }
+
+    fn next_burst(&self, rng: &mut dyn RngCore) -> Vec<u64> {
+        let size = rng.gen_range(self.burst_size.clone());
+        (0..size)
+            .map(|_| rng.gen_range(self.padding.clone()))
+            .collect()
+    }
+}
+
+/// The random number generator the transaction task draws its load from: seeded from
+/// `rng_seed` if set, so two runs with the same seed submit the same load, else from entropy
+#[must_use]
+pub fn load_rng(rng_seed: Option<u64>) -> ChaCha20Rng {
+    rng_seed.map_or_else(ChaCha20Rng::from_entropy, ChaCha20Rng::seed_from_u64)
+}
+
 /// build the transaction task
 #[derive(Clone, Debug)]
 pub enum TxnTaskDescription {
     /// submit transactions in a round robin style using
     /// every `Duration` seconds
     RoundRobinTimeBased(Duration),
-    /// TODO
-    DistributionBased, // others?
+    /// submit the load produced by a [`TxnGenerator`], spreading each burst over the nodes in a
+    /// round robin style
+    Generated(Arc<dyn TxnGenerator>),
 }
 
 impl TxnTaskDescription {
-    /// build a task
+    /// build a task, drawing its randomness from `rng_seed` if set so the load can be replayed
+    /// # Panics
+    /// If a previous submission panicked while drawing random numbers
     pub fn build<TYPES: NodeType, I: TestableNodeImplementation<TYPES>>(
         self,
+        rng_seed: Option<u64>,
     ) -> TaskGenerator<TxnTask<TYPES, I>>
     where
         TYPES: NodeType,
@@ -69,12 +120,12 @@ impl TxnTaskDescription {
         Box::new(move |state, mut registry, test_event_stream| {
             async move {
                 // consistency check
-                match self {
-                    TxnTaskDescription::RoundRobinTimeBased(_) => {
-                        assert!(state.next_node_idx.is_some())
-                    }
-                    TxnTaskDescription::DistributionBased => assert!(state.next_node_idx.is_none()),
-                }
+                assert!(state.next_node_idx.is_some());
+                let rng = Arc::new(Mutex::new(load_rng(rng_seed)));
+                let burst_generator = match &self {
+                    TxnTaskDescription::RoundRobinTimeBased(_) => None,
+                    TxnTaskDescription::Generated(generator) => Some(generator.clone()),
+                };
                 // TODO we'll possibly want multiple criterion including:
                 // - certain number of txns committed
                 // - anchor of certain depth
@@ -90,10 +141,22 @@ impl TxnTaskDescription {
                         }
                         .boxed()
                     }));
+                let message_rng = rng.clone();
                 let message_handler =
                     HandleMessage::<TxnTaskTypes<TYPES, I>>(Arc::new(move |_, mut state| {
+                        let rng = message_rng.clone();
+                        // a single transaction without padding, unless a generator says otherwise
+                        let paddings = burst_generator
+                            .as_ref()
+                            .map_or_else(|| vec![0], |g| g.next_burst(&mut *rng.lock().unwrap()));
                         async move {
-                            if let Some(idx) = state.next_node_idx {
+                            for padding in paddings {
+                                let Some(idx) = state.next_node_idx else {
+                                    // TODO make an issue
+                                    // in the case that this is random
+                                    // which I haven't implemented yet
+                                    unimplemented!()
+                                };
                                 // submit to idx handle
                                 // increment state
                                 state.next_node_idx = Some((idx + 1) % state.handles.len());
@@ -110,22 +173,17 @@ impl TxnTaskDescription {
                                         let leaf = node.handle.get_decided_leaf().await;
                                         let txn = I::leaf_create_random_transaction(
                                             &leaf,
-                                            &mut thread_rng(),
-                                            0,
+                                            &mut *rng.lock().unwrap(),
+                                            padding,
                                         );
                                         node.handle
                                             .submit_transaction(txn.clone())
                                             .await
                                             .expect("Could not send transaction");
-                                        (None, state)
                                     }
                                 }
-                            } else {
-                                // TODO make an issue
-                                // in the case that this is random
-                                // which I haven't implemented yet
-                                unimplemented!()
                             }
+                            (None, state)
                         }
                         .boxed()
                     }));
@@ -138,7 +196,15 @@ impl TxnTaskDescription {
                             Some(boxed_sync(fut))
                         }))
                     }
-                    TxnTaskDescription::DistributionBased => unimplemented!(),
+                    TxnTaskDescription::Generated(generator) => {
+                        GeneratedStream::new(Arc::new(move || {
+                            let duration = generator.next_delay(&mut *rng.lock().unwrap());
+                            let fut = async move {
+                                async_sleep(duration).await;
+                            };
+                            Some(boxed_sync(fut))
+                        }))
+                    }
                 };
                 let builder = TaskBuilder::<TxnTaskTypes<TYPES, I>>::new(
                     "Test Transaction Submission Task".to_string(),
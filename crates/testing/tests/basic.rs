@@ -5,18 +5,30 @@
 )]
 #[cfg_attr(async_executor_impl = "async-std", async_std::test)]
 async fn test_success() {
-    use hotshot_testing::{
-        node_types::{SequencingMemoryImpl, SequencingTestTypes},
-        test_builder::TestMetadata,
-    };
+    use hotshot_testing::test_builder::{TestMetadata, Transport};
 
     async_compatibility_layer::logging::setup_logging();
     async_compatibility_layer::logging::setup_backtrace();
-    let metadata = TestMetadata::default();
-    metadata
-        .gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>()
-        .launch()
-        .run_test()
+    TestMetadata::default()
+        .run_sequencing_test(Transport::Memory)
+        .await;
+}
+
+/// Test that a committee whose nodes hold different stakes still decides views.
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_success_with_weighted_stake() {
+    use hotshot_testing::test_builder::{TestMetadata, Transport};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+    TestMetadata::default()
+        .with_stake_distribution(vec![1, 3, 2, 5, 1])
+        .run_sequencing_test(Transport::Memory)
         .await;
 }
 
@@ -168,3 +180,132 @@ async fn test_with_failures_f() {
         .run_test()
         .await;
 }
+
+/// Test that leadership rotates through the nodes in order.
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_leader_rotation() {
+    use hotshot_testing::{
+        node_types::{SequencingMemoryImpl, SequencingTestTypes},
+        test_builder::TestMetadata,
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+    let mut metadata = TestMetadata::default();
+    // The static committee picks the leader of view `v` as node `v % total_nodes`.
+    metadata.overall_safety_properties.expected_leader_schedule =
+        Some((0..metadata.total_nodes as u64).collect());
+    metadata
+        .gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>()
+        .launch()
+        .run_test()
+        .await;
+}
+
+/// Test that blocks stay within the transaction cap under bursty load.
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_bursty_transactions_respect_block_cap() {
+    use hotshot_testing::{
+        node_types::{SequencingMemoryImpl, SequencingTestTypes},
+        test_builder::TestMetadata,
+        txn_task::{BurstyTxnGenerator, TxnTaskDescription},
+    };
+    use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+
+    const MAX_BLOCK_SIZE: usize = 5;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+    let mut metadata = TestMetadata {
+        txn_description: TxnTaskDescription::Generated(Arc::new(BurstyTxnGenerator {
+            interval: Duration::from_millis(200),
+            burst_size: 0..=4 * MAX_BLOCK_SIZE,
+            padding: 0..=1024,
+        })),
+        ..TestMetadata::default()
+    };
+    metadata.overall_safety_properties.max_block_size = Some(MAX_BLOCK_SIZE as u64);
+    metadata
+        .gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>()
+        .modify_default_config(|config| {
+            config.max_transactions = NonZeroUsize::new(MAX_BLOCK_SIZE).unwrap();
+        })
+        .launch()
+        .run_test()
+        .await;
+}
+
+/// Test that a run with a seeded transaction load makes progress.
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_seeded_transaction_load() {
+    use hotshot_testing::{
+        node_types::{SequencingMemoryImpl, SequencingTestTypes},
+        test_builder::TestMetadata,
+        txn_task::{BurstyTxnGenerator, TxnTaskDescription},
+    };
+    use std::{sync::Arc, time::Duration};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+    let metadata = TestMetadata {
+        txn_description: TxnTaskDescription::Generated(Arc::new(BurstyTxnGenerator {
+            interval: Duration::from_millis(200),
+            burst_size: 0..=10,
+            padding: 0..=1024,
+        })),
+        ..TestMetadata::default()
+    }
+    .with_rng_seed(2170);
+    metadata
+        .gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>()
+        .launch()
+        .run_test()
+        .await;
+}
+
+/// Test that two transaction loads drawn from the same seed are identical.
+#[cfg(test)]
+#[test]
+fn test_seeded_transaction_load_replays() {
+    use hotshot::demos::sdemo::SDemoState;
+    use hotshot_testing::txn_task::{load_rng, BurstyTxnGenerator, TxnGenerator};
+    use hotshot_types::traits::state::TestableState;
+    use std::time::Duration;
+
+    let generator = BurstyTxnGenerator {
+        interval: Duration::from_millis(200),
+        burst_size: 0..=10,
+        padding: 0..=1024,
+    };
+    let mut first = load_rng(Some(2170));
+    let mut second = load_rng(Some(2170));
+    for _ in 0..20 {
+        assert_eq!(
+            generator.next_delay(&mut first),
+            generator.next_delay(&mut second)
+        );
+        let burst = generator.next_burst(&mut first);
+        assert_eq!(burst, generator.next_burst(&mut second));
+        for padding in burst {
+            assert_eq!(
+                SDemoState::create_random_transaction(None, &mut first, padding),
+                SDemoState::create_random_transaction(None, &mut second, padding)
+            );
+        }
+    }
+}
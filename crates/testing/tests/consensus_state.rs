@@ -0,0 +1,689 @@
+use commit::Committable;
+use either::Either::Left;
+use hotshot::{
+    demos::sdemo::{SDemoBlock, SDemoNormalBlock, SDemoTransaction},
+    HotShotSequencingConsensusApi,
+};
+use hotshot_testing::{
+    node_types::{SequencingMemoryImpl, SequencingTestTypes},
+    task_helpers::build_system_handle,
+};
+use hotshot_types::{
+    consensus::{View, ViewInner, ViewStatus},
+    data::{SequencingLeaf, ViewNumber},
+    traits::{consensus_api::ConsensusSharedApi, state::ConsensusTime},
+};
+use std::num::NonZeroUsize;
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_validated_state_for_view() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+
+    // The genesis view always has a leaf, but sequencing leaves do not carry their state.
+    let genesis_leaf = consensus.get_decided_leaf();
+    assert_eq!(
+        consensus.leaf_for_view(ViewNumber::genesis()),
+        Some(&genesis_leaf)
+    );
+    assert_eq!(
+        consensus.validated_state_for_view(ViewNumber::genesis()),
+        None
+    );
+
+    // A failed view has no leaf and therefore no state.
+    consensus.state_map.insert(
+        ViewNumber::new(1),
+        View {
+            view_inner: ViewInner::Failed,
+        },
+    );
+    assert!(consensus.leaf_for_view(ViewNumber::new(1)).is_none());
+    assert!(consensus
+        .validated_state_for_view(ViewNumber::new(1))
+        .is_none());
+
+    // Neither does a view we know nothing about.
+    assert!(consensus.leaf_for_view(ViewNumber::new(2)).is_none());
+    assert!(consensus
+        .validated_state_for_view(ViewNumber::new(2))
+        .is_none());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_max_buffered_views() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+    consensus.max_buffered_views = NonZeroUsize::new(3);
+    consensus.cur_view = ViewNumber::new(6);
+
+    for view in 1..=5 {
+        consensus.insert_view(
+            ViewNumber::new(view),
+            View {
+                view_inner: ViewInner::Failed,
+            },
+        );
+    }
+
+    // The decided genesis view is kept, the oldest failed views are evicted.
+    let views: Vec<_> = consensus.state_map.keys().map(|view| **view).collect();
+    assert_eq!(views, vec![0, 4, 5]);
+
+    // Views at or after the current view are never evicted, even past the cap.
+    for view in 6..=8 {
+        consensus.insert_view(
+            ViewNumber::new(view),
+            View {
+                view_inner: ViewInner::Failed,
+            },
+        );
+    }
+    let views: Vec<_> = consensus.state_map.keys().map(|view| **view).collect();
+    assert_eq!(views, vec![0, 6, 7, 8]);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_pending_transaction_count() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    assert_eq!(api.pending_transaction_count().await, 0);
+    assert_eq!(api.pending_transaction_bytes().await, 0);
+
+    let txns: Vec<_> = (0..5)
+        .map(|id| SDemoTransaction {
+            id,
+            padding: vec![0; 16],
+        })
+        .collect();
+    let consensus_lock = handle.get_consensus();
+    consensus_lock
+        .read()
+        .await
+        .get_transactions()
+        .modify(|mempool| {
+            for txn in &txns {
+                mempool.insert(txn.commit(), txn.clone());
+            }
+        })
+        .await;
+    assert_eq!(api.pending_transaction_count().await, 5);
+    let all_bytes = api.pending_transaction_bytes().await;
+    assert!(all_bytes > 0);
+
+    // Once the parent of the next proposal includes two of them, only the other three are pending.
+    {
+        let mut consensus = consensus_lock.write().await;
+        let parent = consensus.saved_leaves[&consensus.high_qc.leaf_commitment].clone();
+        let leaf = SequencingLeaf {
+            view_number: ViewNumber::new(1),
+            height: parent.height + 1,
+            justify_qc: consensus.high_qc.clone(),
+            parent_commitment: parent.commit(),
+            deltas: Left(SDemoBlock::Normal(SDemoNormalBlock {
+                previous_state: (),
+                transactions: txns[..2].to_vec(),
+            })),
+            rejected: Vec::new(),
+            timestamp: 0,
+            proposer_id: parent.proposer_id.clone(),
+        };
+        consensus.high_qc.leaf_commitment = leaf.commit();
+        consensus.saved_leaves.insert(leaf.commit(), leaf);
+    }
+    assert_eq!(api.pending_transaction_count().await, 3);
+    let pending_bytes = api.pending_transaction_bytes().await;
+    assert!(pending_bytes > 0 && pending_bytes < all_bytes);
+}
+
+#[cfg(test)]
+#[test]
+fn test_view_number_checked_sub() {
+    let view = ViewNumber::new(5);
+    assert_eq!(view.checked_sub(ViewNumber::new(2)), Some(3));
+    assert_eq!(view.checked_sub(view), Some(0));
+    // A view from the future does not underflow.
+    assert_eq!(view.checked_sub(ViewNumber::new(6)), None);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_finalized_view() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+
+    // Only the genesis leaf is finalized at first.
+    assert_eq!(consensus.finalized_view(), ViewNumber::genesis());
+
+    // Once views 2 to 4 form a three-chain on top of view 1, the consensus task decides view 1.
+    for view in 1..=4 {
+        consensus.insert_view(
+            ViewNumber::new(view),
+            View {
+                view_inner: ViewInner::Failed,
+            },
+        );
+    }
+    consensus.last_decided_view = ViewNumber::new(1);
+    assert_eq!(consensus.finalized_view(), ViewNumber::new(1));
+
+    // Garbage collection never evicts the finalized view.
+    consensus.max_buffered_views = NonZeroUsize::new(2);
+    consensus.cur_view = ViewNumber::new(5);
+    consensus.insert_view(
+        ViewNumber::new(5),
+        View {
+            view_inner: ViewInner::Failed,
+        },
+    );
+    assert!(consensus.state_map.contains_key(&ViewNumber::new(1)));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_proposal_slots_serialize_assembly() {
+    use async_compatibility_layer::art::async_sleep;
+    use futures::future::join_all;
+    use hotshot_types::consensus::ProposalSlots;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    // Run two overlapping assemblies and return the most that were ever in flight together.
+    async fn max_concurrent(slots: ProposalSlots) -> usize {
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        join_all((0..2).map(|_| async {
+            let _slot = slots.acquire().await;
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            max_in_flight.fetch_max(now, Ordering::SeqCst);
+            async_sleep(Duration::from_millis(100)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        }))
+        .await;
+        max_in_flight.load(Ordering::SeqCst)
+    }
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    assert_eq!(
+        max_concurrent(ProposalSlots::new(NonZeroUsize::new(1))).await,
+        1
+    );
+    assert_eq!(max_concurrent(ProposalSlots::new(None)).await, 2);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_insert_leaf_conflict() {
+    use hotshot_types::consensus::InsertConflict;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+    let parent = consensus.saved_leaves[&consensus.high_qc.leaf_commitment].clone();
+    let leaf_with = |txn_id| SequencingLeaf {
+        view_number: ViewNumber::new(1),
+        height: parent.height + 1,
+        justify_qc: consensus.high_qc.clone(),
+        parent_commitment: parent.commit(),
+        deltas: Left(SDemoBlock::Normal(SDemoNormalBlock {
+            previous_state: (),
+            transactions: vec![SDemoTransaction {
+                id: txn_id,
+                padding: Vec::new(),
+            }],
+        })),
+        rejected: Vec::new(),
+        timestamp: 0,
+        proposer_id: parent.proposer_id.clone(),
+    };
+    let first = leaf_with(0);
+    let second = leaf_with(1);
+
+    assert_eq!(consensus.insert_leaf(first.clone()), Ok(()));
+    // Storing the same leaf again is not a conflict.
+    assert_eq!(consensus.insert_leaf(first.clone()), Ok(()));
+    assert_eq!(
+        consensus.insert_leaf(second.clone()),
+        Err(InsertConflict {
+            view: ViewNumber::new(1),
+            existing: first.commit(),
+            new: second.commit(),
+        })
+    );
+    assert!(consensus.saved_leaves.contains_key(&first.commit()));
+    assert!(!consensus.saved_leaves.contains_key(&second.commit()));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_leaf_block_loads_stored_block() {
+    use either::Either::Right;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+    let parent = consensus.saved_leaves[&consensus.high_qc.leaf_commitment].clone();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: vec![SDemoTransaction {
+            id: 0,
+            padding: Vec::new(),
+        }],
+    });
+    // A leaf that only references its block by commitment.
+    let leaf = SequencingLeaf {
+        view_number: ViewNumber::new(1),
+        height: parent.height + 1,
+        justify_qc: consensus.high_qc.clone(),
+        parent_commitment: parent.commit(),
+        deltas: Right(block.commit()),
+        rejected: Vec::new(),
+        timestamp: 0,
+        proposer_id: parent.proposer_id.clone(),
+    };
+
+    assert_eq!(consensus.leaf_block(&leaf), None);
+    consensus.saved_blocks.insert(block.clone());
+    assert_eq!(consensus.leaf_block(&leaf), Some(block.clone()));
+
+    // A leaf carrying its full block resolves without the store.
+    consensus.saved_blocks.remove(block.commit());
+    let full = SequencingLeaf {
+        deltas: Left(block.clone()),
+        ..leaf
+    };
+    assert_eq!(consensus.leaf_block(&full), Some(block));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_view_status() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+    let view = ViewNumber::new(1);
+    let parent = consensus.saved_leaves[&consensus.high_qc.leaf_commitment].clone();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let leaf = SequencingLeaf {
+        view_number: view,
+        height: parent.height + 1,
+        justify_qc: consensus.high_qc.clone(),
+        parent_commitment: parent.commit(),
+        deltas: Left(block.clone()),
+        rejected: Vec::new(),
+        timestamp: 0,
+        proposer_id: parent.proposer_id.clone(),
+    };
+
+    assert_eq!(consensus.view_status(view), ViewStatus::Unknown);
+
+    consensus.state_map.insert(
+        view,
+        View {
+            view_inner: ViewInner::DA {
+                block: block.commit(),
+            },
+        },
+    );
+    assert_eq!(consensus.view_status(view), ViewStatus::DAAvailable);
+
+    consensus.state_map.insert(
+        view,
+        View {
+            view_inner: ViewInner::Leaf {
+                leaf: leaf.commit(),
+            },
+        },
+    );
+    consensus.saved_leaves.insert(leaf.commit(), leaf);
+    assert_eq!(consensus.view_status(view), ViewStatus::Proposed);
+
+    consensus.last_decided_view = view;
+    assert_eq!(consensus.view_status(view), ViewStatus::Decided);
+
+    let failed = ViewNumber::new(2);
+    consensus.state_map.insert(
+        failed,
+        View {
+            view_inner: ViewInner::Failed,
+        },
+    );
+    assert_eq!(consensus.view_status(failed), ViewStatus::Failed);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_inclusion_view() {
+    use hotshot_types::traits::block_contents::Block;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+
+    let txns: Vec<_> = (0..4)
+        .map(|id| SDemoTransaction {
+            id,
+            padding: vec![0; 16],
+        })
+        .collect();
+    let first = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: txns[..2].to_vec(),
+    });
+    let second = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: txns[2..3].to_vec(),
+    });
+    consensus.index_transactions(ViewNumber::new(1), first.contained_transactions());
+    consensus.index_transactions(ViewNumber::new(2), second.contained_transactions());
+
+    assert_eq!(
+        consensus.inclusion_view(&txns[0].commit()),
+        Some(ViewNumber::new(1))
+    );
+    assert_eq!(
+        consensus.inclusion_view(&txns[1].commit()),
+        Some(ViewNumber::new(1))
+    );
+    assert_eq!(
+        consensus.inclusion_view(&txns[2].commit()),
+        Some(ViewNumber::new(2))
+    );
+    assert_eq!(consensus.inclusion_view(&txns[3].commit()), None);
+
+    // Garbage collection forgets transactions decided more than `txn_index_retention` views ago.
+    consensus.txn_index_retention = 1;
+    consensus
+        .collect_garbage(ViewNumber::genesis(), ViewNumber::new(3))
+        .await;
+    assert_eq!(consensus.inclusion_view(&txns[0].commit()), None);
+    assert_eq!(
+        consensus.inclusion_view(&txns[2].commit()),
+        Some(ViewNumber::new(2))
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_qc_justifies_child() {
+    use hotshot::demos::sdemo::SDemoState;
+    use hotshot_types::{
+        certificate::{AssembledSignature, QuorumCertificate},
+        data::LeafType,
+        traits::election::SignedCertificate,
+    };
+
+    let leaf_at = |view, justify_qc| {
+        SequencingLeaf::<SequencingTestTypes>::new(
+            ViewNumber::new(view),
+            justify_qc,
+            SDemoBlock::Normal(SDemoNormalBlock {
+                previous_state: (),
+                transactions: Vec::new(),
+            }),
+            SDemoState::default(),
+        )
+    };
+
+    let parent = leaf_at(1, QuorumCertificate::genesis());
+    let parent_qc = QuorumCertificate {
+        leaf_commitment: parent.commit(),
+        view_number: ViewNumber::new(1),
+        signatures: AssembledSignature::Genesis(),
+        is_genesis: false,
+    };
+    let child = leaf_at(2, parent_qc.clone());
+    let unrelated = leaf_at(2, QuorumCertificate::genesis());
+
+    assert!(parent_qc.justifies(&child));
+    assert!(!parent_qc.justifies(&unrelated));
+    assert!(!parent_qc.justifies(&parent));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_install_synced_view() {
+    use either::Either;
+    use hotshot::types::{bn254::BN254Pub, SignatureKey};
+    use hotshot_testing::task_helpers::key_pair_for_id;
+    use hotshot_types::{
+        certificate::{AssembledSignature, QuorumCertificate},
+        consensus::SyncError,
+        traits::{
+            election::{ConsensusExchange, Membership, VoteData},
+            node_implementation::ExchangesType,
+        },
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let quorum_exchange = handle.hotshot.inner.exchanges.quorum_exchange().clone();
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+    let view = ViewNumber::new(3);
+    let parent = consensus.saved_leaves[&consensus.high_qc.leaf_commitment].clone();
+    let leaf = SequencingLeaf {
+        view_number: view,
+        height: parent.height + 1,
+        justify_qc: consensus.high_qc.clone(),
+        parent_commitment: parent.commit(),
+        deltas: Left(SDemoBlock::Normal(SDemoNormalBlock {
+            previous_state: (),
+            transactions: Vec::new(),
+        })),
+        rejected: Vec::new(),
+        timestamp: 0,
+        proposer_id: parent.proposer_id.clone(),
+    };
+    // A QC the quorum signed for `leaf_commitment` in the synced view.
+    let signed_qc = |leaf_commitment| {
+        let vote_data = VoteData::Yes(leaf_commitment);
+        let mut accumulator = quorum_exchange.empty_accumulator();
+        for id in 0..quorum_exchange.total_nodes() as u64 {
+            let (private_key, public_key) = key_pair_for_id(id);
+            let signature = BN254Pub::sign(&private_key, vote_data.commit().as_ref());
+            let vote_token = quorum_exchange
+                .membership()
+                .make_vote_token(view, &private_key)
+                .unwrap()
+                .unwrap();
+            match quorum_exchange.accumulate_vote(
+                &public_key.to_bytes(),
+                &signature,
+                leaf_commitment,
+                vote_data.clone(),
+                vote_token,
+                view,
+                accumulator,
+                None,
+            ) {
+                Either::Left(acc) => accumulator = acc,
+                Either::Right(qc) => return qc,
+            }
+        }
+        panic!("The quorum should have formed a QC");
+    };
+    let qc = signed_qc(leaf.commit());
+
+    // A QC for some other leaf does not justify the synced one.
+    assert_eq!(
+        consensus.install_synced_view(view, leaf.clone(), signed_qc(parent.commit()), |qc| {
+            quorum_exchange.is_valid_cert(qc, leaf.commit())
+        }),
+        Err(SyncError::UnjustifiedLeaf {
+            leaf: leaf.commit(),
+            certified: parent.commit(),
+        })
+    );
+
+    // Nor does one that names the synced leaf without valid signatures over it, whether they are
+    // missing or were collected for another leaf.
+    let unsigned_qc = QuorumCertificate {
+        signatures: AssembledSignature::Genesis(),
+        is_genesis: false,
+        ..qc.clone()
+    };
+    let forged_qc = QuorumCertificate {
+        leaf_commitment: leaf.commit(),
+        ..signed_qc(parent.commit())
+    };
+    for invalid_qc in [unsigned_qc, forged_qc] {
+        assert_eq!(
+            consensus.install_synced_view(view, leaf.clone(), invalid_qc, |qc| {
+                quorum_exchange.is_valid_cert(qc, leaf.commit())
+            }),
+            Err(SyncError::InvalidCertificate { view })
+        );
+        assert_eq!(consensus.view_status(view), ViewStatus::Unknown);
+        assert!(!consensus.saved_leaves.contains_key(&leaf.commit()));
+        assert_ne!(consensus.high_qc.view_number, view);
+    }
+
+    assert_eq!(
+        consensus.install_synced_view(view, leaf.clone(), qc.clone(), |qc| {
+            quorum_exchange.is_valid_cert(qc, leaf.commit())
+        }),
+        Ok(())
+    );
+    assert_eq!(consensus.view_status(view), ViewStatus::Proposed);
+
+    // The next proposal's parent is found through the high QC, as the leader would look it up.
+    assert_eq!(consensus.high_qc, qc);
+    let parent_commitment = consensus.state_map[&consensus.high_qc.view_number]
+        .get_leaf_commitment()
+        .unwrap();
+    assert_eq!(consensus.saved_leaves.get(&parent_commitment), Some(&leaf));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_on_decide_callbacks() {
+    use hotshot::demos::sdemo::SDemoState;
+    use hotshot_types::{certificate::QuorumCertificate, traits::election::SignedCertificate};
+    use std::sync::{Arc, Mutex};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let consensus_lock = handle.get_consensus();
+    let mut consensus = consensus_lock.write().await;
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    for callback_id in 0..2 {
+        let calls = calls.clone();
+        consensus.register_on_decide(Arc::new(
+            move |leaf: &SequencingLeaf<SequencingTestTypes>| {
+                calls.lock().unwrap().push((callback_id, *leaf.view_number));
+            },
+        ));
+    }
+
+    // A decide hands over its leaf chain newest first.
+    let leaf_chain: Vec<_> = (1..=3)
+        .rev()
+        .map(|view| {
+            SequencingLeaf::<SequencingTestTypes>::new(
+                ViewNumber::new(view),
+                QuorumCertificate::genesis(),
+                SDemoBlock::Normal(SDemoNormalBlock {
+                    previous_state: (),
+                    transactions: Vec::new(),
+                }),
+                SDemoState::default(),
+            )
+        })
+        .collect();
+    consensus.notify_decided(&leaf_chain);
+
+    // Every callback sees each of the three leaves once, in ascending view order.
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![(0, 1), (1, 1), (0, 2), (1, 2), (0, 3), (1, 3)]
+    );
+}
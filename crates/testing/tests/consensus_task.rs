@@ -1,12 +1,17 @@
+use async_compatibility_layer::art::async_spawn;
 use commit::Committable;
 use either::Right;
 use hotshot::{
+    demos::sdemo::{SDemoBlock, SDemoNormalBlock},
     tasks::add_consensus_task,
     types::{SignatureKey, SystemContextHandle},
     HotShotSequencingConsensusApi,
 };
-use hotshot_task::event_stream::ChannelStream;
-use hotshot_task_impls::events::SequencingHotShotEvent;
+use hotshot_task::{event_stream::ChannelStream, global_registry::GlobalRegistry};
+use hotshot_task_impls::{
+    consensus::{SequencingConsensusTaskState, VoteQueue},
+    events::SequencingHotShotEvent,
+};
 use hotshot_testing::{
     node_types::{SequencingMemoryImpl, SequencingTestTypes},
     task_helpers::{build_quorum_proposal, key_pair_for_id},
@@ -21,7 +26,7 @@ use hotshot_types::{
     },
 };
 
-use std::collections::HashMap;
+use std::{collections::HashMap, marker::PhantomData};
 
 async fn build_vote(
     handle: &SystemContextHandle<SequencingTestTypes, SequencingMemoryImpl>,
@@ -79,6 +84,49 @@ async fn build_vote(
     )
 }
 
+/// A consensus task state for node `id` with `handle` in `cur_view`, publishing to
+/// `event_stream`, for driving the task directly rather than through the harness.
+fn build_consensus_state(
+    handle: &SystemContextHandle<SequencingTestTypes, SequencingMemoryImpl>,
+    id: u64,
+    event_stream: ChannelStream<SequencingHotShotEvent<SequencingTestTypes, SequencingMemoryImpl>>,
+    cur_view: ViewNumber,
+) -> SequencingConsensusTaskState<
+    SequencingTestTypes,
+    SequencingMemoryImpl,
+    HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl>,
+> {
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    SequencingConsensusTaskState {
+        registry: GlobalRegistry::new(),
+        consensus: handle.get_consensus(),
+        timeout: 1000,
+        cur_view,
+        block: SDemoBlock::Normal(SDemoNormalBlock {
+            previous_state: (),
+            transactions: Vec::new(),
+        }),
+        quorum_exchange: api.inner.exchanges.quorum_exchange().clone().into(),
+        committee_exchange: api.inner.exchanges.committee_exchange().clone().into(),
+        api,
+        _pd: PhantomData,
+        vote_collector: None,
+        timeout_task: async_spawn(async move {}),
+        event_stream,
+        output_event_stream: ChannelStream::new(),
+        certs: HashMap::new(),
+        current_proposal: None,
+        id,
+        qc: None,
+        max_view_retries: None,
+        proposal_failures: HashMap::new(),
+        vote_queue: VoteQueue::default(),
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(
     async_executor_impl = "tokio",
@@ -169,3 +217,501 @@ async fn test_consensus_vote() {
 
     run_harness(input, output, None, build_fn).await;
 }
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_leader_abandons_view_after_retries() {
+    use futures::{FutureExt, StreamExt};
+    use hotshot_task::{event_stream::EventStream, task::FilterEvent};
+    use hotshot_task_impls::consensus::ProposalFailure;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        certificate::{AssembledSignature, QuorumCertificate},
+        data::fake_commitment,
+    };
+    use std::{num::NonZeroUsize, sync::Arc};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 2 leads view 2, but the high QC's view 1 never made it into the state map.
+    let handle = build_system_handle(2).await.0;
+    let qc = QuorumCertificate {
+        leaf_commitment: fake_commitment(),
+        view_number: ViewNumber::new(1),
+        signatures: AssembledSignature::Genesis(),
+        is_genesis: false,
+    };
+    handle.get_consensus().write().await.high_qc = qc.clone();
+
+    let event_stream = ChannelStream::new();
+    let (mut abandoned, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::ViewAbandoned(..))
+        })))
+        .await;
+    let mut state = build_consensus_state(&handle, 2, event_stream, ViewNumber::new(1));
+    state.max_view_retries = NonZeroUsize::new(2);
+
+    // The first attempt and both retries fail; only the last failure abandons the view.
+    for _ in 0..2 {
+        assert!(!state.publish_proposal_if_able(qc.clone()).await);
+        assert_eq!(abandoned.next().now_or_never().flatten(), None);
+    }
+    assert!(!state.publish_proposal_if_able(qc.clone()).await);
+    assert_eq!(
+        abandoned.next().await,
+        Some(SequencingHotShotEvent::ViewAbandoned(
+            ViewNumber::new(2),
+            ProposalFailure::ParentViewMissing
+        ))
+    );
+
+    // An abandoned view is not attempted again.
+    assert!(!state.publish_proposal_if_able(qc).await);
+    assert_eq!(abandoned.next().now_or_never().flatten(), None);
+    assert_eq!(state.proposal_failures[&ViewNumber::new(2)], 3);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_proposal_and_dac_correlated_by_view() {
+    use either::Either;
+    use futures::{FutureExt, Stream, StreamExt};
+    use hotshot::types::bn254::BN254Pub;
+    use hotshot_task::{event_stream::EventStream, task::FilterEvent};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        certificate::{DACertificate, QuorumCertificate},
+        message::Proposal,
+        traits::election::{Membership, VoteData},
+        vote::VoteType,
+    };
+    use std::sync::Arc;
+
+    /// The view of the next quorum vote sent
+    async fn next_vote_view<S>(votes: &mut S) -> ViewNumber
+    where
+        S: Stream<Item = SequencingHotShotEvent<SequencingTestTypes, SequencingMemoryImpl>> + Unpin,
+    {
+        match votes.next().await {
+            Some(SequencingHotShotEvent::QuorumVoteSend(vote)) => vote.current_view(),
+            event => panic!("Expected a quorum vote, got {event:?}"),
+        }
+    }
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 2 is a replica; node 3 leads views 3, 13 and 23.
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let (leader_private_key, leader_key) = key_pair_for_id(3);
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let block_commitment = block.commit();
+
+    let dac_over = |view: ViewNumber,
+                    block_commitment: commit::Commitment<SDemoBlock>|
+     -> DACertificate<SequencingTestTypes> {
+        let mut accumulator = committee_exchange.empty_accumulator();
+        for node_id in 0..committee_exchange.total_nodes() as u64 {
+            let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], node_id);
+            let Ok(Some(token)) = committee_exchange
+                .membership()
+                .make_vote_token(view, &private_key)
+            else {
+                continue;
+            };
+            let vote_data = VoteData::DA(block_commitment);
+            let signature = BN254Pub::sign(&private_key, vote_data.commit().as_ref());
+            match committee_exchange.accumulate_vote(
+                &key.to_bytes(),
+                &signature,
+                block_commitment,
+                vote_data,
+                token,
+                view,
+                accumulator,
+                None,
+            ) {
+                Either::Left(next) => accumulator = next,
+                Either::Right(dac) => return dac,
+            }
+        }
+        panic!("The DA committee should form a certificate");
+    };
+    let dac_for = |view: ViewNumber| dac_over(view, block_commitment);
+    let proposal_over =
+        |view: ViewNumber,
+         block_commitment: commit::Commitment<SDemoBlock>,
+         dac: Option<DACertificate<SequencingTestTypes>>| Proposal {
+            data: QuorumProposal {
+                block_commitment,
+                view_number: view,
+                height: 1,
+                justify_qc: QuorumCertificate::genesis(),
+                timeout_certificate: None,
+                proposer_id: leader_key.to_bytes(),
+                dac,
+            },
+            signature: BN254Pub::sign(&leader_private_key, b"quorum proposal"),
+        };
+    let proposal_for = |view: ViewNumber, dac: Option<DACertificate<SequencingTestTypes>>| {
+        proposal_over(view, block_commitment, dac)
+    };
+    let block_with = |id| {
+        SDemoBlock::Normal(SDemoNormalBlock {
+            previous_state: (),
+            transactions: vec![hotshot::demos::sdemo::SDemoTransaction {
+                id,
+                padding: Vec::new(),
+            }],
+        })
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut votes, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::QuorumVoteSend(_))
+        })))
+        .await;
+    let mut state = build_consensus_state(&handle, 2, event_stream, ViewNumber::new(3));
+
+    // The proposal arrives first and the replica waits for the DA cert before voting.
+    let view = ViewNumber::new(3);
+    state
+        .handle_event(SequencingHotShotEvent::QuorumProposalRecv(
+            proposal_for(view, None),
+            leader_key,
+        ))
+        .await;
+    assert_eq!(votes.next().now_or_never().flatten(), None);
+    state
+        .handle_event(SequencingHotShotEvent::DACRecv(dac_for(view)))
+        .await;
+    assert_eq!(next_vote_view(&mut votes).await, view);
+
+    // The DA cert arrives first and is matched to the later proposal for its view.
+    let view = ViewNumber::new(13);
+    state
+        .handle_event(SequencingHotShotEvent::DACRecv(dac_for(view)))
+        .await;
+    assert_eq!(votes.next().now_or_never().flatten(), None);
+    state
+        .handle_event(SequencingHotShotEvent::QuorumProposalRecv(
+            proposal_for(view, None),
+            leader_key,
+        ))
+        .await;
+    assert_eq!(next_vote_view(&mut votes).await, view);
+
+    // A DA cert bundled with the proposal is used directly.
+    let view = ViewNumber::new(23);
+    state
+        .handle_event(SequencingHotShotEvent::QuorumProposalRecv(
+            proposal_for(view, Some(dac_for(view))),
+            leader_key,
+        ))
+        .await;
+    assert_eq!(next_vote_view(&mut votes).await, view);
+
+    // A bundled DA cert for a different block is ignored; the replica waits for a valid one.
+    let view = ViewNumber::new(33);
+    let proposed_block = block_with(1).commit();
+    state
+        .handle_event(SequencingHotShotEvent::QuorumProposalRecv(
+            proposal_over(
+                view,
+                proposed_block,
+                Some(dac_over(view, block_with(2).commit())),
+            ),
+            leader_key,
+        ))
+        .await;
+    assert_eq!(votes.next().now_or_never().flatten(), None);
+    state
+        .handle_event(SequencingHotShotEvent::DACRecv(dac_over(
+            view,
+            proposed_block,
+        )))
+        .await;
+    assert_eq!(next_vote_view(&mut votes).await, view);
+
+    // A block re-proposed after a failed view comes with the DA cert from the view it was first
+    // proposed in, and is matched to the proposal by block commitment.
+    let view = ViewNumber::new(43);
+    let reproposed_block = block_with(3).commit();
+    state
+        .handle_event(SequencingHotShotEvent::QuorumProposalRecv(
+            proposal_over(view, reproposed_block, None),
+            leader_key,
+        ))
+        .await;
+    assert_eq!(votes.next().now_or_never().flatten(), None);
+    state
+        .handle_event(SequencingHotShotEvent::DACRecv(dac_over(
+            ViewNumber::new(41),
+            reproposed_block,
+        )))
+        .await;
+    assert_eq!(next_vote_view(&mut votes).await, view);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_stalled_view_emits_one_view_timeout() {
+    use futures::{FutureExt, StreamExt};
+    use hotshot_task::{event_stream::EventStream, task::FilterEvent};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use std::sync::Arc;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let event_stream = ChannelStream::new();
+    let (mut timeouts, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(
+                event,
+                SequencingHotShotEvent::Timeout(_) | SequencingHotShotEvent::ViewTimeout(_)
+            )
+        })))
+        .await;
+    let mut state = build_consensus_state(&handle, 2, event_stream, ViewNumber::new(0));
+    state.timeout = 10;
+
+    // Entering views 1 and 2 starts a timer for each; view 2 then stalls.
+    for view in [1, 2] {
+        state
+            .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(view)))
+            .await;
+    }
+    for _ in 0..2 {
+        let Some(timeout) = timeouts.next().await else {
+            panic!("timer did not fire");
+        };
+        state.handle_event(timeout).await;
+    }
+
+    assert_eq!(
+        timeouts.next().await,
+        Some(SequencingHotShotEvent::ViewTimeout(ViewNumber::new(2)))
+    );
+    assert_eq!(timeouts.next().now_or_never().flatten(), None);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_timeout_votes_form_timeout_certificate() {
+    use either::Left;
+    use futures::StreamExt;
+    use hotshot::types::bn254::BN254Pub;
+    use hotshot_task::{
+        event_stream::EventStream,
+        task::{FilterEvent, HotShotTaskCompleted},
+    };
+    use hotshot_task_impls::consensus::{vote_handle, VoteCollectionTaskState};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        certificate::{AssembledSignatureKind, QuorumCertificate},
+        traits::election::{Membership, VoteData},
+        vote::{QuorumVote, TimeoutVote},
+    };
+    use std::sync::Arc;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let quorum_exchange = api.inner.exchanges.quorum_exchange().clone();
+    let view = ViewNumber::new(2);
+    let vote_data = VoteData::Timeout(view.commit());
+    let timeout_vote = |id| {
+        let (private_key, public_key) = key_pair_for_id(id);
+        let signature = BN254Pub::sign(&private_key, vote_data.commit().as_ref());
+        let vote_token = quorum_exchange
+            .membership()
+            .make_vote_token(view, &private_key)
+            .unwrap()
+            .unwrap();
+        SequencingHotShotEvent::QuorumVoteRecv(QuorumVote::Timeout(TimeoutVote {
+            high_qc: QuorumCertificate::genesis(),
+            signature: (public_key.to_bytes(), signature),
+            current_view: view,
+            vote_token,
+            vote_data: vote_data.clone(),
+        }))
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream.subscribe(FilterEvent::default()).await;
+    let mut state = VoteCollectionTaskState {
+        quorum_exchange: Arc::new(quorum_exchange.clone()),
+        accumulator: Left(quorum_exchange.empty_accumulator()),
+        timeout_accumulator: Left(quorum_exchange.empty_timeout_accumulator()),
+        cur_view: view,
+        event_stream: event_stream.clone(),
+        id: 2,
+        vote_queue: VoteQueue::default(),
+    };
+
+    // Short of the success threshold, collection goes on.
+    let threshold = quorum_exchange.success_threshold().get();
+    for id in 0..threshold - 1 {
+        let (result, next) = vote_handle(state, timeout_vote(id)).await;
+        assert!(result.is_none());
+        state = next;
+    }
+
+    // The vote reaching it forms the timeout certificate for the view.
+    let (result, state) = vote_handle(state, timeout_vote(threshold - 1)).await;
+    assert!(matches!(result, Some(HotShotTaskCompleted::ShutDown)));
+    let Some(SequencingHotShotEvent::TimeoutCertFormed(certificate)) = events.next().await else {
+        panic!("Expected a timeout certificate");
+    };
+    assert_eq!(certificate.view_number, view);
+    assert_eq!(
+        certificate.signatures.kind(),
+        AssembledSignatureKind::Timeout
+    );
+    assert!(quorum_exchange.is_valid_timeout_cert(&certificate));
+    assert_eq!(state.timeout_accumulator.right(), Some(certificate));
+    assert!(state.accumulator.is_left());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_vote_flood_past_capacity_forms_qc() {
+    use either::Left;
+    use futures::StreamExt;
+    use hotshot::types::bn254::BN254Pub;
+    use hotshot_task::{
+        event_stream::EventStream,
+        task::{FilterEvent, HotShotTaskCompleted},
+    };
+    use hotshot_task_impls::consensus::{vote_handle, VoteCollectionTaskState};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        certificate::QuorumCertificate,
+        traits::election::{Membership, VoteData},
+        vote::{QuorumVote, YesOrNoVote},
+    };
+    use std::{num::NonZeroUsize, sync::Arc};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let quorum_exchange = api.inner.exchanges.quorum_exchange().clone();
+    let view = ViewNumber::new(1);
+    let leaf_commitment = handle.get_decided_leaf().await.commit();
+    let vote_data = VoteData::Yes(leaf_commitment);
+    let yes_vote = |id| {
+        let (private_key, public_key) = key_pair_for_id(id);
+        let signature = BN254Pub::sign(&private_key, vote_data.commit().as_ref());
+        let vote_token = quorum_exchange
+            .membership()
+            .make_vote_token(view, &private_key)
+            .unwrap()
+            .unwrap();
+        SequencingHotShotEvent::QuorumVoteRecv(QuorumVote::Yes(YesOrNoVote {
+            justify_qc_commitment: QuorumCertificate::genesis().commit(),
+            signature: (public_key.to_bytes(), signature),
+            leaf_commitment,
+            current_view: view,
+            vote_token,
+            vote_data: vote_data.clone(),
+        }))
+    };
+
+    // The collector lets in only as many votes as it needs for a QC.
+    let threshold = quorum_exchange.success_threshold().get() as usize;
+    let total = quorum_exchange.total_nodes();
+    assert!(total > threshold);
+    let vote_queue = VoteQueue::new(NonZeroUsize::new(threshold));
+    let event_stream = ChannelStream::new();
+    let (mut votes, _) = event_stream.subscribe(vote_queue.filter()).await;
+    let (mut certificates, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::QCFormed(_))
+        })))
+        .await;
+
+    // A flood of every node's vote, twice over, only ever holds `threshold` of them.
+    for id in (0..total as u64).chain(0..total as u64) {
+        event_stream.publish(yes_vote(id)).await;
+        assert!(vote_queue.queued() <= threshold);
+    }
+    assert_eq!(vote_queue.queued(), threshold);
+    assert_eq!(vote_queue.dropped(), 2 * total - threshold);
+
+    // The votes that got in still form the QC.
+    let mut state = VoteCollectionTaskState {
+        quorum_exchange: Arc::new(quorum_exchange.clone()),
+        accumulator: Left(quorum_exchange.empty_accumulator()),
+        timeout_accumulator: Left(quorum_exchange.empty_timeout_accumulator()),
+        cur_view: view,
+        event_stream: event_stream.clone(),
+        id: 2,
+        vote_queue: vote_queue.clone(),
+    };
+    for taken in 1..=threshold {
+        let Some(vote) = votes.next().await else {
+            panic!("Expected a queued vote");
+        };
+        let (result, next) = vote_handle(state, vote).await;
+        state = next;
+        assert_eq!(vote_queue.queued(), threshold - taken);
+        assert_eq!(
+            matches!(result, Some(HotShotTaskCompleted::ShutDown)),
+            taken == threshold
+        );
+    }
+    let Some(SequencingHotShotEvent::QCFormed(qc)) = certificates.next().await else {
+        panic!("Expected a QC");
+    };
+    assert_eq!(qc.view_number, view);
+    assert!(quorum_exchange.is_valid_cert(&qc, leaf_commitment));
+    assert_eq!(state.accumulator.right(), Some(qc));
+
+    // Taking votes made room again.
+    event_stream.publish(yes_vote(0)).await;
+    assert_eq!(vote_queue.queued(), 1);
+    assert_eq!(vote_queue.dropped(), 2 * total - threshold);
+}
@@ -1,6 +1,10 @@
 use commit::Committable;
-use hotshot::HotShotSequencingConsensusApi;
-use hotshot_task_impls::events::SequencingHotShotEvent;
+use hotshot::{types::SystemContextHandle, HotShotSequencingConsensusApi};
+use hotshot_task::{event_stream::ChannelStream, global_registry::GlobalRegistry};
+use hotshot_task_impls::{
+    da::{DATaskState, TransactionArrivalRate},
+    events::SequencingHotShotEvent,
+};
 use hotshot_testing::node_types::{SequencingMemoryImpl, SequencingTestTypes};
 use hotshot_types::{
     data::{DAProposal, ViewNumber},
@@ -9,7 +13,39 @@ use hotshot_types::{
         node_implementation::ExchangesType, state::ConsensusTime,
     },
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
+
+/// Build the DA task state of node `id`, publishing to `event_stream`.
+fn build_da_state(
+    handle: &SystemContextHandle<SequencingTestTypes, SequencingMemoryImpl>,
+    id: u64,
+    event_stream: ChannelStream<SequencingHotShotEvent<SequencingTestTypes, SequencingMemoryImpl>>,
+    cur_view: ViewNumber,
+) -> DATaskState<
+    SequencingTestTypes,
+    SequencingMemoryImpl,
+    HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl>,
+> {
+    let api = HotShotSequencingConsensusApi {
+        inner: handle.hotshot.inner.clone(),
+    };
+    DATaskState {
+        registry: GlobalRegistry::new(),
+        committee_exchange: Arc::new(api.inner.exchanges.committee_exchange().clone()),
+        api,
+        consensus: handle.hotshot.get_consensus(),
+        cur_view,
+        vote_collector: None,
+        event_stream,
+        id,
+        txn_arrival_rate: TransactionArrivalRate::default(),
+        proposed_block: None,
+        reuse_da_cert: None,
+        da_availability_timeout: None,
+        max_empty_fallbacks: None,
+        empty_fallbacks: 0,
+    }
+}
 
 #[cfg_attr(
     async_executor_impl = "tokio",
@@ -45,10 +81,7 @@ async fn test_da_task() {
     });
     let block_commitment = block.commit();
     let signature = committee_exchange.sign_da_proposal(&block_commitment);
-    let proposal = DAProposal {
-        deltas: block.clone(),
-        view_number: ViewNumber::new(2),
-    };
+    let proposal = DAProposal::new(block.clone(), ViewNumber::new(2));
     let message = Proposal {
         data: proposal,
         signature,
@@ -90,3 +123,1153 @@ async fn test_da_task() {
 
     run_harness(input, output, None, build_fn).await;
 }
+
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_task_rejects_mismatched_metadata() {
+    use hotshot::{
+        demos::sdemo::{SDemoBlock, SDemoNormalBlock},
+        tasks::add_da_task,
+    };
+    use hotshot_task_impls::harness::run_harness;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{message::Proposal, traits::election::CommitteeExchangeType};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Build the API for node 2.
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let pub_key = *api.public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let signature = committee_exchange.sign_da_proposal(&block.commit());
+    let message = Proposal {
+        data: DAProposal::new(block.clone(), ViewNumber::new(2)),
+        signature: signature.clone(),
+    };
+
+    // Same block and signature, but the metadata claims a transaction the block doesn't have.
+    let mut bad_proposal = DAProposal::new(block.clone(), ViewNumber::new(2));
+    bad_proposal.txn_count += 1;
+    let bad_message = Proposal {
+        data: bad_proposal,
+        signature,
+    };
+
+    let mut input = Vec::new();
+    let mut output = HashMap::new();
+
+    input.push(SequencingHotShotEvent::ViewChange(ViewNumber::new(1)));
+    input.push(SequencingHotShotEvent::ViewChange(ViewNumber::new(2)));
+    input.push(SequencingHotShotEvent::DAProposalRecv(
+        bad_message.clone(),
+        pub_key,
+    ));
+    input.push(SequencingHotShotEvent::Shutdown);
+
+    // No `DAVoteSend` is produced for the mismatched proposal.
+    output.insert(SequencingHotShotEvent::ViewChange(ViewNumber::new(1)), 1);
+    output.insert(SequencingHotShotEvent::SendDABlockData(block), 1);
+    output.insert(SequencingHotShotEvent::DAProposalSend(message, pub_key), 1);
+    output.insert(
+        SequencingHotShotEvent::DAProposalRecv(bad_message, pub_key),
+        1,
+    );
+    output.insert(SequencingHotShotEvent::ViewChange(ViewNumber::new(2)), 1);
+    output.insert(SequencingHotShotEvent::Shutdown, 1);
+
+    let build_fn = |task_runner, event_stream| {
+        add_da_task(task_runner, event_stream, committee_exchange, handle)
+    };
+
+    run_harness(input, output, None, build_fn).await;
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_min_transactions_follows_arrival_rate() {
+    use hotshot::HotShotInitializer;
+    use hotshot_task_impls::da::TransactionArrivalRate;
+    use hotshot_testing::test_builder::TestMetadata;
+    use std::time::{Duration, Instant};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let launcher = TestMetadata::default_multiple_rounds()
+        .gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>()
+        .modify_default_config(|config| {
+            config.min_transactions = 1;
+            config.propose_min_round_time = Duration::from_secs(1);
+        });
+    let networks = (launcher.resource_generator.channel_generator)(0);
+    let storage = (launcher.resource_generator.storage)(0);
+    let config = launcher.resource_generator.config.clone();
+    let mut runner = launcher.launch();
+    let hotshot = runner
+        .add_node_with_config(
+            networks,
+            storage,
+            HotShotInitializer::from_genesis(),
+            config,
+        )
+        .await;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: hotshot.inner.clone(),
+        };
+
+    let now = Instant::now();
+    let mut idle = TransactionArrivalRate::new(Duration::from_secs(10));
+    idle.record(10, now);
+    let mut busy = TransactionArrivalRate::new(Duration::from_secs(10));
+    busy.record(1000, now);
+
+    let idle_min = api.min_transactions_for(idle.rate(now));
+    let busy_min = api.min_transactions_for(busy.rate(now));
+    assert_eq!(idle_min, 1);
+    assert_eq!(busy_min, 100);
+
+    // Once the burst falls out of the window the leader goes back to the configured minimum.
+    let later = now + Duration::from_secs(11);
+    assert_eq!(api.min_transactions_for(busy.rate(later)), 1);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_vote_collection_stops_on_future_proposal() {
+    use either::Either;
+    use futures::StreamExt;
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_task::{
+        event_stream::EventStream,
+        task::{FilterEvent, HotShotTaskCompleted},
+    };
+    use hotshot_task_impls::da::{vote_handle, DAVoteCollectionTaskState};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        message::{CommitteeConsensusMessage, Proposal},
+        traits::election::CommitteeExchangeType,
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let pub_key = *api.public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let view = ViewNumber::new(2);
+    let proposal_for = |view| Proposal {
+        data: DAProposal::new(block.clone(), view),
+        signature: committee_exchange.sign_da_proposal(&block.commit()),
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream.subscribe(FilterEvent::default()).await;
+    let state = DAVoteCollectionTaskState {
+        committee_exchange: Arc::new(committee_exchange.clone()),
+        accumulator: Either::Left(committee_exchange.empty_accumulator()),
+        cur_view: view,
+        event_stream: event_stream.clone(),
+        id: 2,
+    };
+
+    // One vote is not enough to finish collection.
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let CommitteeConsensusMessage::DAVote(vote) =
+        committee_exchange.create_da_message(block.commit(), view, vote_token)
+    else {
+        panic!("Expected a DA vote");
+    };
+    let (result, state) = vote_handle(state, SequencingHotShotEvent::DAVoteRecv(vote)).await;
+    assert!(result.is_none());
+
+    // A proposal for the view being collected is not a sign of falling behind.
+    let (result, state) = vote_handle(
+        state,
+        SequencingHotShotEvent::DAProposalRecv(proposal_for(view), pub_key),
+    )
+    .await;
+    assert!(result.is_none());
+
+    // A proposal for a later view ends collection early.
+    let (result, _) = vote_handle(
+        state,
+        SequencingHotShotEvent::DAProposalRecv(proposal_for(ViewNumber::new(4)), pub_key),
+    )
+    .await;
+    assert!(matches!(result, Some(HotShotTaskCompleted::ShutDown)));
+    assert_eq!(
+        events.next().await,
+        Some(SequencingHotShotEvent::FellBehind(ViewNumber::new(4)))
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_da_proposal_build_rejects_block_that_does_not_append() {
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoError, SDemoNormalBlock, SDemoState};
+    use hotshot_types::traits::State;
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let parent_state = SDemoState::default()
+        .append(&block, &ViewNumber::new(3))
+        .unwrap();
+
+    // A block for a view after the parent's extends it.
+    let proposal =
+        DAProposal::<SequencingTestTypes>::build(&parent_state, block.clone(), ViewNumber::new(4))
+            .unwrap();
+    assert_eq!(proposal, DAProposal::new(block.clone(), ViewNumber::new(4)));
+
+    // A block that does not extend the parent state never becomes a proposal.
+    let error = DAProposal::<SequencingTestTypes>::build(&parent_state, block, ViewNumber::new(2))
+        .unwrap_err();
+    assert_eq!(error.view_number, ViewNumber::new(2));
+    assert!(matches!(error.source, SDemoError::InvalidBlock));
+}
+
+#[cfg(test)]
+#[test]
+fn test_da_proposal_validate_well_formed() {
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock, SDemoTransaction};
+    use hotshot_types::data::{ProposalError, ProposalType};
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: vec![SDemoTransaction {
+            id: 0,
+            padding: vec![0; 8],
+        }],
+    });
+    let proposal = DAProposal::<SequencingTestTypes>::new(block, ViewNumber::new(2));
+    assert_eq!(proposal.validate_well_formed(), Ok(()));
+
+    let mut wrong_count = proposal.clone();
+    wrong_count.txn_count += 1;
+    assert_eq!(
+        wrong_count.validate_well_formed(),
+        Err(ProposalError::BlockMetadataMismatch)
+    );
+
+    let mut wrong_size = proposal;
+    wrong_size.block_bytes = 0;
+    assert_eq!(
+        wrong_size.validate_well_formed(),
+        Err(ProposalError::BlockMetadataMismatch)
+    );
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_transaction_accepted_once() {
+    use futures::{FutureExt, StreamExt};
+    use hotshot::{demos::sdemo::SDemoTransaction, tasks::add_da_task};
+    use hotshot_task::{event_stream::EventStream, task::FilterEvent};
+    use hotshot_task_impls::harness::run_harness;
+    use hotshot_testing::task_helpers::build_system_handle;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let transaction = SDemoTransaction {
+        id: 7,
+        padding: vec![0; 8],
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut accepted, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::TransactionAccepted(_))
+        })))
+        .await;
+
+    // The same transaction arrives twice in one batch and again in a later batch.
+    let mut input = Vec::new();
+    let mut output = HashMap::new();
+    input.push(SequencingHotShotEvent::TransactionsRecv(vec![
+        transaction.clone(),
+        transaction.clone(),
+    ]));
+    input.push(SequencingHotShotEvent::TransactionsRecv(vec![
+        transaction.clone()
+    ]));
+    input.push(SequencingHotShotEvent::Shutdown);
+
+    for event in input.clone() {
+        output.insert(event, 1);
+    }
+    output.insert(
+        SequencingHotShotEvent::TransactionAccepted(transaction.commit()),
+        1,
+    );
+
+    let build_fn = |task_runner, event_stream| {
+        add_da_task(task_runner, event_stream, committee_exchange, handle)
+    };
+    run_harness(input, output, Some(event_stream), build_fn).await;
+
+    assert_eq!(
+        accepted.next().await,
+        Some(SequencingHotShotEvent::TransactionAccepted(
+            transaction.commit()
+        ))
+    );
+    assert_eq!(accepted.next().now_or_never().flatten(), None);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_leader_reuses_cert_after_failed_view() {
+    use either::Either;
+    use futures::{FutureExt, StreamExt};
+    use hotshot::{
+        demos::sdemo::{SDemoBlock, SDemoTransaction},
+        types::{bn254::BN254Pub, SignatureKey},
+    };
+    use hotshot_task::{event_stream::EventStream, task::FilterEvent};
+    use hotshot_testing::task_helpers::{build_system_handle, MockClockApi};
+    use hotshot_types::traits::election::{Membership, VoteData};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 3 is the DA leader for views 3 and 13.
+    let handle = build_system_handle(3).await.0;
+    let api = MockClockApi::new(&handle, 0);
+    let committee_exchange = api.inner.inner.exchanges.committee_exchange().clone();
+    let pub_key = *api.public_key();
+    let transactions = handle.get_consensus().read().await.get_transactions();
+    transactions
+        .modify(|txns| {
+            let txn = SDemoTransaction {
+                id: 3,
+                padding: vec![0; 8],
+            };
+            txns.insert(txn.commit(), txn);
+        })
+        .await;
+
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(
+                event,
+                SequencingHotShotEvent::SendDABlockData(_)
+                    | SequencingHotShotEvent::DACSend(_, _)
+                    | SequencingHotShotEvent::DAProposalSend(_, _)
+            )
+        })))
+        .await;
+    let mut state = DATaskState {
+        registry: GlobalRegistry::new(),
+        committee_exchange: Arc::new(committee_exchange.clone()),
+        api: api.clone(),
+        consensus: handle.hotshot.get_consensus(),
+        cur_view: ViewNumber::new(1),
+        vote_collector: None,
+        event_stream,
+        id: 3,
+        txn_arrival_rate: TransactionArrivalRate::default(),
+        proposed_block: None,
+        reuse_da_cert: None,
+        da_availability_timeout: None,
+        max_empty_fallbacks: None,
+        empty_fallbacks: 0,
+    };
+
+    // Entering view 2, the leader proposes its block for view 3.
+    let failed_view = ViewNumber::new(3);
+    state
+        .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(2)))
+        .await;
+    let Some(SequencingHotShotEvent::SendDABlockData(block)) = events.next().await else {
+        panic!("Leader did not build a block");
+    };
+    let Some(SequencingHotShotEvent::DAProposalSend(proposal, _)) = events.next().await else {
+        panic!("Leader did not propose its block");
+    };
+    assert_eq!(proposal.data.view_number, failed_view);
+    let SDemoBlock::Normal(normal_block) = &block else {
+        panic!("Leader proposed a genesis block");
+    };
+    assert_eq!(normal_block.transactions.len(), 1);
+
+    // The DA committee certifies the block, and the leader's vote collection task reports the cert.
+    let vote_data = VoteData::DA(block.commit());
+    let mut accumulator = committee_exchange.empty_accumulator();
+    let mut cert = None;
+    for id in 0..committee_exchange.total_nodes() as u64 {
+        let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], id);
+        let signature = BN254Pub::sign(&private_key, vote_data.commit().as_ref());
+        let token = committee_exchange
+            .membership()
+            .make_vote_token(failed_view, &private_key)
+            .unwrap()
+            .unwrap();
+        match committee_exchange.accumulate_vote(
+            &key.to_bytes(),
+            &signature,
+            block.commit(),
+            vote_data.clone(),
+            token,
+            failed_view,
+            accumulator,
+            None,
+        ) {
+            Either::Left(acc) => accumulator = acc,
+            Either::Right(formed) => {
+                cert = Some(formed);
+                break;
+            }
+        }
+    }
+    let cert = cert.expect("The committee should have formed a DA cert");
+    state
+        .handle_event(SequencingHotShotEvent::DACSend(cert.clone(), pub_key))
+        .await;
+    assert!(state.reuse_da_cert.is_none());
+
+    // The view then times out before the quorum decides it, so the block and cert are kept.
+    state
+        .handle_event(SequencingHotShotEvent::Timeout(failed_view))
+        .await;
+    assert_eq!(state.reuse_da_cert, Some((cert.clone(), block.clone())));
+
+    // Next time it leads, the leader re-proposes the same block for view 13 under the cert from
+    // the failed view, without sending a DA proposal to collect votes again.
+    assert!(state
+        .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(12)))
+        .await
+        .is_none());
+    assert_eq!(
+        events.next().await,
+        Some(SequencingHotShotEvent::SendDABlockData(block))
+    );
+    assert_eq!(
+        events.next().await,
+        Some(SequencingHotShotEvent::DACSend(cert, pub_key))
+    );
+    assert!(events.next().now_or_never().flatten().is_none());
+    assert!(state.reuse_da_cert.is_none());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_unavailable_after_timeout() {
+    use either::Either;
+    use futures::StreamExt;
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_task::{
+        event_stream::EventStream,
+        task::{FilterEvent, HotShotTaskCompleted},
+    };
+    use hotshot_task_impls::da::{vote_handle, DAFailure, DAVoteCollectionTaskState};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        message::CommitteeConsensusMessage, traits::election::CommitteeExchangeType,
+    };
+    use std::time::Duration;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 2 is the DA leader for view 2.
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let view = ViewNumber::new(2);
+
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(
+                event,
+                SequencingHotShotEvent::DAAvailabilityTimeout(_)
+                    | SequencingHotShotEvent::DAUnavailable(_, _)
+            )
+        })))
+        .await;
+
+    // No DA votes arrive after the proposal, so the DA task abandons the block itself.
+    let mut state = build_da_state(&handle, 2, event_stream.clone(), ViewNumber::new(0));
+    state.da_availability_timeout = Some(Duration::from_millis(10));
+    assert!(state
+        .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(1)))
+        .await
+        .is_none());
+    let timeout = events.next().await.unwrap();
+    assert_eq!(timeout, SequencingHotShotEvent::DAAvailabilityTimeout(view));
+    assert!(state.handle_event(timeout).await.is_none());
+    assert_eq!(
+        events.next().await,
+        Some(SequencingHotShotEvent::DAUnavailable(
+            view,
+            DAFailure::NoVotes
+        ))
+    );
+
+    // A single vote stalls below the threshold, so the vote collection task abandons it.
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let collection = DAVoteCollectionTaskState {
+        committee_exchange: Arc::new(committee_exchange.clone()),
+        accumulator: Either::Left(committee_exchange.empty_accumulator()),
+        cur_view: view,
+        event_stream: event_stream.clone(),
+        id: 2,
+    };
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let CommitteeConsensusMessage::DAVote(vote) =
+        committee_exchange.create_da_message(block.commit(), view, vote_token)
+    else {
+        panic!("Expected a DA vote");
+    };
+    let (result, collection) =
+        vote_handle(collection, SequencingHotShotEvent::DAVoteRecv(vote)).await;
+    assert!(result.is_none());
+
+    // A timeout for another view does not end collection.
+    let (result, collection) = vote_handle(
+        collection,
+        SequencingHotShotEvent::DAAvailabilityTimeout(ViewNumber::new(3)),
+    )
+    .await;
+    assert!(result.is_none());
+
+    let (result, _) = vote_handle(
+        collection,
+        SequencingHotShotEvent::DAAvailabilityTimeout(view),
+    )
+    .await;
+    assert!(matches!(result, Some(HotShotTaskCompleted::ShutDown)));
+    assert_eq!(
+        events.next().await,
+        Some(SequencingHotShotEvent::DAUnavailable(
+            view,
+            DAFailure::BelowThreshold {
+                stake: 1.into(),
+                threshold: committee_exchange.success_threshold().get(),
+            }
+        ))
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_block_commitment_covers_order() {
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock, SDemoTransaction};
+
+    let block_of = |ids: &[u64]| {
+        SDemoBlock::Normal(SDemoNormalBlock {
+            previous_state: (),
+            transactions: ids.iter().copied().map(SDemoTransaction::new).collect(),
+        })
+    };
+
+    assert_eq!(block_of(&[1, 2, 3]).commit(), block_of(&[1, 2, 3]).commit());
+    assert_ne!(block_of(&[1, 2, 3]).commit(), block_of(&[3, 2, 1]).commit());
+    assert_ne!(block_of(&[1, 2, 3]).commit(), block_of(&[1, 3, 2]).commit());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_leader_follows_injected_clock() {
+    use async_compatibility_layer::art::{async_sleep, async_spawn, async_timeout};
+    use futures::{FutureExt, StreamExt};
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoTransaction};
+    use hotshot_task::task::FilterEvent;
+    use hotshot_testing::task_helpers::{build_system_handle, MockClockApi};
+    use std::time::Duration;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 2 is the DA leader for view 2, and waits for 3 transactions before proposing early.
+    let handle = build_system_handle(2).await.0;
+    let api = MockClockApi::new(&handle, 3);
+    let max_round_time = api.propose_max_round_time();
+    let transactions = handle.get_consensus().read().await.get_transactions();
+    let add_transaction = |id| {
+        let transactions = transactions.clone();
+        async move {
+            transactions
+                .modify(|txns| {
+                    let txn = SDemoTransaction {
+                        id,
+                        padding: Vec::new(),
+                    };
+                    txns.insert(txn.commit(), txn);
+                })
+                .await;
+        }
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut proposals, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::DAProposalSend(_, _))
+        })))
+        .await;
+    let mut state = DATaskState {
+        registry: GlobalRegistry::new(),
+        committee_exchange: Arc::new(api.inner.inner.exchanges.committee_exchange().clone()),
+        api: api.clone(),
+        consensus: handle.hotshot.get_consensus(),
+        cur_view: ViewNumber::new(0),
+        vote_collector: None,
+        event_stream,
+        id: 2,
+        txn_arrival_rate: TransactionArrivalRate::default(),
+        proposed_block: None,
+        reuse_da_cert: None,
+        da_availability_timeout: None,
+        max_empty_fallbacks: None,
+        empty_fallbacks: 0,
+    };
+    async_spawn(async move {
+        state
+            .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(1)))
+            .await;
+    });
+
+    // Short of the minimum and with the clock stopped, the leader keeps waiting.
+    async_sleep(Duration::from_millis(100)).await;
+    add_transaction(0).await;
+    async_sleep(Duration::from_millis(100)).await;
+    assert!(proposals.next().now_or_never().is_none());
+
+    // Once the clock passes the round time, the next wake-up proposes what has arrived, long
+    // before the real round time is up.
+    api.advance(max_round_time);
+    add_transaction(1).await;
+    let Ok(Some(SequencingHotShotEvent::DAProposalSend(proposal, _))) =
+        async_timeout(max_round_time / 2, proposals.next()).await
+    else {
+        panic!("Leader did not propose once the round time passed");
+    };
+    let SDemoBlock::Normal(block) = proposal.data.deltas else {
+        panic!("Leader proposed a genesis block");
+    };
+    assert_eq!(block.transactions.len(), 2);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_leader_includes_oldest_transactions_first() {
+    use futures::StreamExt;
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoTransaction};
+    use hotshot_task::task::FilterEvent;
+    use hotshot_testing::task_helpers::{build_system_handle, MockClockApi};
+    use std::num::NonZeroUsize;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 2 is the DA leader for views 2 and 12, and includes 4 transactions per block.
+    let handle = build_system_handle(2).await.0;
+    let mut api = MockClockApi::new(&handle, 4);
+    api.max_transactions = NonZeroUsize::new(4).unwrap();
+    let transactions = handle.get_consensus().read().await.get_transactions();
+    let txn = |id| SDemoTransaction {
+        id,
+        padding: Vec::new(),
+    };
+    let add_transactions = |ids: std::ops::Range<u64>| {
+        let transactions = transactions.clone();
+        async move {
+            transactions
+                .modify(|txns| {
+                    for id in ids {
+                        txns.insert(txn(id).commit(), txn(id));
+                    }
+                })
+                .await;
+        }
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut proposals, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::DAProposalSend(_, _))
+        })))
+        .await;
+    let mut state = DATaskState {
+        registry: GlobalRegistry::new(),
+        committee_exchange: Arc::new(api.inner.inner.exchanges.committee_exchange().clone()),
+        api: api.clone(),
+        consensus: handle.hotshot.get_consensus(),
+        cur_view: ViewNumber::new(0),
+        vote_collector: None,
+        event_stream,
+        id: 2,
+        txn_arrival_rate: TransactionArrivalRate::default(),
+        proposed_block: None,
+        reuse_da_cert: None,
+        da_availability_timeout: None,
+        max_empty_fallbacks: None,
+        empty_fallbacks: 0,
+    };
+    let block_ids = |event| {
+        let Some(SequencingHotShotEvent::DAProposalSend(proposal, _)) = event else {
+            panic!("Leader did not propose");
+        };
+        let SDemoBlock::Normal(block) = proposal.data.deltas else {
+            panic!("Leader proposed a genesis block");
+        };
+        block
+            .transactions
+            .iter()
+            .map(|txn| txn.id)
+            .collect::<Vec<_>>()
+    };
+
+    // The first block takes the oldest four of the eight pending transactions.
+    add_transactions(0..8).await;
+    state
+        .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(1)))
+        .await;
+    assert_eq!(block_ids(proposals.next().await), vec![0, 1, 2, 3]);
+
+    // Once those are decided, the remaining older transactions still go ahead of newer arrivals.
+    transactions
+        .modify(|txns| txns.retain(|_, txn| txn.id >= 4))
+        .await;
+    add_transactions(8..16).await;
+    state
+        .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(11)))
+        .await;
+    assert_eq!(block_ids(proposals.next().await), vec![4, 5, 6, 7]);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_is_da_member() {
+    use hotshot::{
+        traits::election::static_committee::StaticElectionConfig,
+        types::{bn254::BN254Pub, SignatureKey},
+    };
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::traits::{
+        election::{CommitteeExchangeType, ElectionConfig, Membership},
+        node_implementation::{CommitteeEx, SequencingQuorumEx},
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let keys: Vec<BN254Pub> = (0..10)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id).0)
+        .collect();
+    let entries: Vec<_> = keys
+        .iter()
+        .map(|key| key.get_stake_table_entry(1u64))
+        .collect();
+    // A DA committee of the first four of ten nodes.
+    let config = StaticElectionConfig::new(10, Some(4)).unwrap();
+    let exchanges_for = |id: u64| {
+        let (public_key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], id);
+        let quorum = SequencingQuorumEx::<SequencingTestTypes, SequencingMemoryImpl>::create(
+            entries.clone(),
+            keys.clone(),
+            config.clone(),
+            api.inner.exchanges.quorum_exchange().network().clone(),
+            public_key,
+            public_key.get_stake_table_entry(1u64),
+            private_key.clone(),
+        );
+        let committee = CommitteeEx::<SequencingTestTypes, SequencingMemoryImpl>::create(
+            entries.clone(),
+            keys.clone(),
+            config.da_committee_config(),
+            api.inner.exchanges.committee_exchange().network().clone(),
+            public_key,
+            public_key.get_stake_table_entry(1u64),
+            private_key,
+        );
+        (quorum, committee)
+    };
+
+    let view = ViewNumber::new(1);
+    let (quorum, committee) = exchanges_for(2);
+    assert!(committee.is_da_member(view));
+    assert!(quorum
+        .membership()
+        .get_committee(view)
+        .contains(quorum.public_key()));
+
+    // Node 6 is in the quorum but not on the DA committee.
+    let (quorum, committee) = exchanges_for(6);
+    assert!(!committee.is_da_member(view));
+    assert!(quorum
+        .membership()
+        .get_committee(view)
+        .contains(quorum.public_key()));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_leader_waits_for_min_round_time() {
+    use async_compatibility_layer::art::{async_sleep, async_spawn, async_timeout};
+    use futures::{FutureExt, StreamExt};
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoTransaction};
+    use hotshot_task::task::FilterEvent;
+    use hotshot_testing::task_helpers::{build_system_handle, MockClockApi};
+    use std::time::Duration;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 2 is the DA leader for view 2. It needs a single transaction, but may not propose
+    // until ten seconds of its clock have passed.
+    let handle = build_system_handle(2).await.0;
+    let mut api = MockClockApi::new(&handle, 1);
+    api.propose_min_round_time = Duration::from_secs(10);
+    let transactions = handle.get_consensus().read().await.get_transactions();
+    let add_transaction = |id| {
+        let transactions = transactions.clone();
+        async move {
+            transactions
+                .modify(|txns| {
+                    let txn = SDemoTransaction {
+                        id,
+                        padding: Vec::new(),
+                    };
+                    txns.insert(txn.commit(), txn);
+                })
+                .await;
+        }
+    };
+
+    // The mempool is already full when the view starts.
+    for id in 0..3 {
+        add_transaction(id).await;
+    }
+
+    let event_stream = ChannelStream::new();
+    let (mut proposals, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::DAProposalSend(_, _))
+        })))
+        .await;
+    let mut state = DATaskState {
+        registry: GlobalRegistry::new(),
+        committee_exchange: Arc::new(api.inner.inner.exchanges.committee_exchange().clone()),
+        api: api.clone(),
+        consensus: handle.hotshot.get_consensus(),
+        cur_view: ViewNumber::new(0),
+        vote_collector: None,
+        event_stream,
+        id: 2,
+        txn_arrival_rate: TransactionArrivalRate::default(),
+        proposed_block: None,
+        reuse_da_cert: None,
+        da_availability_timeout: None,
+        max_empty_fallbacks: None,
+        empty_fallbacks: 0,
+    };
+    async_spawn(async move {
+        state
+            .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(1)))
+            .await;
+    });
+
+    // Enough transactions are ready, but the minimum round time has not passed.
+    async_sleep(Duration::from_millis(200)).await;
+    add_transaction(3).await;
+    async_sleep(Duration::from_millis(200)).await;
+    assert!(proposals.next().now_or_never().is_none());
+
+    // Once the clock passes the minimum round time, the next wake-up proposes.
+    api.advance(Duration::from_secs(10));
+    add_transaction(4).await;
+    let Ok(Some(SequencingHotShotEvent::DAProposalSend(proposal, _))) =
+        async_timeout(Duration::from_secs(5), proposals.next()).await
+    else {
+        panic!("Leader did not propose once the minimum round time passed");
+    };
+    let SDemoBlock::Normal(block) = proposal.data.deltas else {
+        panic!("Leader proposed a genesis block");
+    };
+    assert_eq!(block.transactions.len(), 5);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_leader_deadline_grows_with_committee() {
+    use async_compatibility_layer::art::{async_sleep, async_spawn, async_timeout};
+    use futures::{FutureExt, StreamExt};
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoTransaction};
+    use hotshot_task::task::FilterEvent;
+    use hotshot_testing::task_helpers::{build_system_handle, MockClockApi};
+    use std::time::Duration;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 2 is the DA leader for view 2, waits for 3 transactions before proposing early, and
+    // allows a second more per committee member.
+    let handle = build_system_handle(2).await.0;
+    let mut api = MockClockApi::new(&handle, 3);
+    api.round_time_per_node = Duration::from_secs(1);
+    let max_round_time = api.propose_max_round_time();
+    let committee_size = api.inner.inner.exchanges.committee_exchange().total_nodes();
+
+    // The default deadline is flat, the mock's grows with the committee.
+    assert_eq!(
+        api.inner.round_time_for_committee_size(committee_size * 2),
+        api.inner.round_time_for_committee_size(committee_size)
+    );
+    assert!(
+        api.round_time_for_committee_size(committee_size * 2)
+            > api.round_time_for_committee_size(committee_size)
+    );
+
+    let transactions = handle.get_consensus().read().await.get_transactions();
+    let add_transaction = |id| {
+        let transactions = transactions.clone();
+        async move {
+            transactions
+                .modify(|txns| {
+                    let txn = SDemoTransaction {
+                        id,
+                        padding: Vec::new(),
+                    };
+                    txns.insert(txn.commit(), txn);
+                })
+                .await;
+        }
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut proposals, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::DAProposalSend(_, _))
+        })))
+        .await;
+    let mut state = DATaskState {
+        registry: GlobalRegistry::new(),
+        committee_exchange: Arc::new(api.inner.inner.exchanges.committee_exchange().clone()),
+        api: api.clone(),
+        consensus: handle.hotshot.get_consensus(),
+        cur_view: ViewNumber::new(0),
+        vote_collector: None,
+        event_stream,
+        id: 2,
+        txn_arrival_rate: TransactionArrivalRate::default(),
+        proposed_block: None,
+        reuse_da_cert: None,
+        da_availability_timeout: None,
+        max_empty_fallbacks: None,
+        empty_fallbacks: 0,
+    };
+    async_spawn(async move {
+        state
+            .handle_event(SequencingHotShotEvent::ViewChange(ViewNumber::new(1)))
+            .await;
+    });
+
+    // Past the flat round time the leader still waits for the larger committee.
+    async_sleep(Duration::from_millis(100)).await;
+    api.advance(max_round_time);
+    add_transaction(0).await;
+    async_sleep(Duration::from_millis(200)).await;
+    assert!(proposals.next().now_or_never().is_none());
+
+    // Once the committee's deadline passes, the next wake-up proposes what has arrived.
+    api.advance(api.round_time_per_node * u32::try_from(committee_size).unwrap());
+    add_transaction(1).await;
+    let Ok(Some(SequencingHotShotEvent::DAProposalSend(proposal, _))) =
+        async_timeout(Duration::from_secs(5), proposals.next()).await
+    else {
+        panic!("Leader did not propose once the committee's round time passed");
+    };
+    let SDemoBlock::Normal(block) = proposal.data.deltas else {
+        panic!("Leader proposed a genesis block");
+    };
+    assert_eq!(block.transactions.len(), 2);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_da_leader_falls_back_to_empty_blocks() {
+    use futures::{FutureExt, StreamExt};
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoTransaction};
+    use hotshot_task::task::FilterEvent;
+    use hotshot_testing::task_helpers::{build_system_handle, MockClockApi};
+    use std::{
+        num::NonZeroUsize,
+        time::{Duration, Instant},
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    // Node 2 is the DA leader for view 2.
+    let handle = build_system_handle(2).await.0;
+    let delay = Duration::from_millis(100);
+    let mut api = MockClockApi::new(&handle, 0);
+    api.propose_empty_after = Some(delay);
+    let txn = SDemoTransaction {
+        id: 0,
+        padding: Vec::new(),
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut proposals, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::DAProposalSend(_, _))
+        })))
+        .await;
+    let (mut unavailable, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::TransactionsUnavailable(_))
+        })))
+        .await;
+    let mut state = DATaskState {
+        registry: GlobalRegistry::new(),
+        committee_exchange: Arc::new(api.inner.inner.exchanges.committee_exchange().clone()),
+        api,
+        consensus: handle.hotshot.get_consensus(),
+        cur_view: ViewNumber::new(1),
+        vote_collector: None,
+        event_stream,
+        id: 2,
+        txn_arrival_rate: TransactionArrivalRate::default(),
+        proposed_block: None,
+        reuse_da_cert: None,
+        da_availability_timeout: None,
+        max_empty_fallbacks: None,
+        empty_fallbacks: 0,
+    };
+    // The transactions in a proposal for view 2.
+    let proposed_txns = |event: Option<
+        Option<SequencingHotShotEvent<SequencingTestTypes, SequencingMemoryImpl>>,
+    >| {
+        let Some(Some(SequencingHotShotEvent::DAProposalSend(proposal, _))) = event else {
+            panic!("Leader did not propose a block");
+        };
+        assert_eq!(proposal.data.view_number, ViewNumber::new(2));
+        let SDemoBlock::Normal(block) = proposal.data.deltas else {
+            panic!("Leader proposed a genesis block");
+        };
+        block.transactions
+    };
+
+    // Without empty blocks allowed, a leader whose wait for transactions failed skips its
+    // proposal.
+    state.propose_block(None, None).await;
+    assert!(proposals.next().now_or_never().is_none());
+
+    // With them, it proposes an empty block once the delay has passed, for as many views in a
+    // row as it may.
+    state.max_empty_fallbacks = NonZeroUsize::new(2);
+    state.empty_fallbacks = 0;
+    for _ in 0..2 {
+        let start = Instant::now();
+        state.propose_block(None, None).await;
+        assert!(start.elapsed() >= delay);
+        assert!(proposed_txns(proposals.next().now_or_never()).is_empty());
+    }
+
+    // Past that the transaction channel is taken to be dead: the leader skips its proposal and
+    // reports it.
+    state.propose_block(None, None).await;
+    assert!(proposals.next().now_or_never().is_none());
+    let Some(Some(SequencingHotShotEvent::TransactionsUnavailable(view))) =
+        unavailable.next().now_or_never()
+    else {
+        panic!("Leader did not report its transactions unavailable");
+    };
+    assert_eq!(view, ViewNumber::new(2));
+
+    // Transactions arriving again end the run of failures.
+    state.propose_block(None, Some(vec![txn.clone()])).await;
+    assert_eq!(proposed_txns(proposals.next().now_or_never()), vec![txn]);
+    assert_eq!(state.empty_fallbacks, 0);
+    state.propose_block(None, None).await;
+    assert!(proposed_txns(proposals.next().now_or_never()).is_empty());
+    assert!(unavailable.next().now_or_never().is_none());
+}
@@ -0,0 +1,112 @@
+use commit::Committable;
+use hotshot::demos::sdemo::SDemoState;
+use hotshot_testing::{node_types::SequencingTestTypes, task_helpers::build_system_handle};
+use hotshot_types::{
+    data::{LeafType, ViewNumber},
+    traits::{
+        node_implementation::NodeType,
+        state::{ConsensusTime, TestableBlock},
+        Block, State,
+    },
+};
+
+#[cfg(test)]
+#[test]
+fn test_genesis_consistent() {
+    let (block, state) = SequencingTestTypes::genesis();
+
+    assert_eq!(block.txn_count(), 0);
+    assert!(block.contained_transactions().is_empty());
+
+    // The genesis state is exactly the default state with the genesis block appended.
+    let expected = SDemoState::default()
+        .append(&block, &ViewNumber::genesis())
+        .unwrap();
+    assert_eq!(state, expected);
+    assert_eq!(state.commit(), expected.commit());
+
+    // Every caller gets the same pair.
+    let (block2, state2) = SequencingTestTypes::genesis();
+    assert_eq!(block.commit(), block2.commit());
+    assert_eq!(state, state2);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_initializer_uses_node_genesis() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let leaf = handle.get_decided_leaf().await;
+    let (block, _) = SequencingTestTypes::genesis();
+
+    assert_eq!(leaf.get_view_number(), ViewNumber::genesis());
+    assert_eq!(leaf.get_deltas_commitment(), block.commit());
+}
+
+/// Start the chain at view 1000, as when continuing from a snapshot, and make progress from there.
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_start_at_nonzero_view() {
+    use hotshot_testing::{node_types::SequencingMemoryImpl, test_builder::TestMetadata};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+    let metadata = TestMetadata {
+        genesis_view: 1000,
+        ..TestMetadata::default()
+    };
+    metadata
+        .gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>()
+        .launch()
+        .run_test()
+        .await;
+}
+
+/// A genesis certificate carries no signatures, so it is only accepted at the genesis view.
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_genesis_cert_only_valid_at_genesis_view() {
+    use hotshot::HotShotSequencingConsensusApi;
+    use hotshot_testing::node_types::SequencingMemoryImpl;
+    use hotshot_types::{
+        certificate::QuorumCertificate,
+        data::SequencingLeaf,
+        traits::{
+            election::{ConsensusExchange, SignedCertificate},
+            node_implementation::ExchangesType,
+        },
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let quorum_exchange = api.inner.exchanges.quorum_exchange().clone();
+    assert_eq!(quorum_exchange.genesis_view(), ViewNumber::genesis());
+
+    type TestQc = QuorumCertificate<SequencingTestTypes, SequencingLeaf<SequencingTestTypes>>;
+
+    let genesis = TestQc::genesis();
+    assert!(quorum_exchange.is_valid_cert(&genesis, genesis.leaf_commitment()));
+
+    let later = TestQc::genesis_at(ViewNumber::new(5));
+    assert!(!quorum_exchange.is_valid_cert(&later, later.leaf_commitment()));
+}
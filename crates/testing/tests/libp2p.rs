@@ -2,9 +2,8 @@ use std::time::Duration;
 
 use hotshot_testing::{
     completion_task::{CompletionTaskDescription, TimeBasedCompletionTaskDescription},
-    node_types::{SequencingLibp2pImpl, SequencingTestTypes},
     overall_safety_task::OverallSafetyPropertiesDescription,
-    test_builder::TestMetadata,
+    test_builder::{TestMetadata, Transport},
 };
 use tracing::instrument;
 
@@ -30,12 +29,7 @@ async fn libp2p_network() {
         ),
         ..TestMetadata::default_multiple_rounds()
     };
-
-    metadata
-        .gen_launcher::<SequencingTestTypes, SequencingLibp2pImpl>()
-        .launch()
-        .run_test()
-        .await
+    metadata.run_sequencing_test(Transport::Libp2p).await;
 }
 
 /// stress test for libp2p
@@ -49,10 +43,7 @@ async fn libp2p_network() {
 async fn test_stress_libp2p_network() {
     async_compatibility_layer::logging::setup_logging();
     async_compatibility_layer::logging::setup_backtrace();
-    let metadata = TestMetadata::default_stress();
-    metadata
-        .gen_launcher::<SequencingTestTypes, SequencingLibp2pImpl>()
-        .launch()
-        .run_test()
-        .await
+    TestMetadata::default_stress()
+        .run_sequencing_test(Transport::Libp2p)
+        .await;
 }
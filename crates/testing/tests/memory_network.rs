@@ -0,0 +1,89 @@
+use hotshot::{
+    demos::sdemo::SDemoTransaction,
+    traits::implementations::{MasterMap, MemoryNetwork},
+    types::{bn254::BN254Pub, SignatureKey},
+};
+use hotshot_testing::node_types::StaticMemoryQuorumComm;
+use hotshot_types::{
+    data::ViewNumber,
+    message::{DataMessage, Message, MessageKind},
+    traits::{
+        metrics::NoMetrics,
+        network::{CommunicationChannel, TransmitType},
+        state::ConsensusTime,
+    },
+};
+use std::{marker::PhantomData, sync::Arc};
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_local_public_key() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let master_map = MasterMap::new();
+    let keys: Vec<BN254Pub> = (0..2)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id).0)
+        .collect();
+    for key in keys {
+        let network = MemoryNetwork::new(key, NoMetrics::boxed(), master_map.clone(), None);
+        let channel = StaticMemoryQuorumComm::new(Arc::new(network));
+        assert_eq!(channel.local_public_key(), key);
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_direct_message_batch() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let master_map = MasterMap::new();
+    let keys: Vec<BN254Pub> = (0..4)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id).0)
+        .collect();
+    let channels: Vec<_> = keys
+        .iter()
+        .map(|key| {
+            let network = MemoryNetwork::new(*key, NoMetrics::boxed(), master_map.clone(), None);
+            StaticMemoryQuorumComm::new(Arc::new(network))
+        })
+        .collect();
+
+    let messages: Vec<_> = keys[1..]
+        .iter()
+        .zip(0u64..)
+        .map(|(recipient, id)| {
+            let message = Message {
+                sender: keys[0],
+                kind: MessageKind::Data(DataMessage::SubmitTransaction(
+                    SDemoTransaction {
+                        id,
+                        padding: Vec::new(),
+                    },
+                    ViewNumber::new(1),
+                )),
+                _phantom: PhantomData,
+            };
+            (message, *recipient)
+        })
+        .collect();
+    let expected: Vec<_> = messages
+        .iter()
+        .map(|(message, _)| message.clone())
+        .collect();
+
+    assert_eq!(channels[0].direct_message_batch(messages).await.unwrap(), 3);
+    for (channel, message) in channels[1..].iter().zip(expected) {
+        let received = channel.recv_msgs(TransmitType::Direct).await.unwrap();
+        assert_eq!(received, vec![message]);
+    }
+}
@@ -0,0 +1,181 @@
+use either::Either::{Left, Right};
+use hotshot::{demos::sdemo::SDemoTransaction, types::SignatureKey};
+use hotshot_testing::node_types::{SequencingMemoryImpl, SequencingTestTypes};
+use hotshot_types::{
+    certificate::{AssembledSignature, DACertificate},
+    data::{fake_commitment, ViewNumber},
+    message::{
+        ConsensusMessageType, DataMessage, GeneralConsensusMessage, InternalTrigger, Message,
+        MessageKind, MessagePriority, MessagePurpose, ProcessedCommitteeConsensusMessage,
+        ProcessedGeneralConsensusMessage, ProcessedSequencingMessage, SequencingMessage,
+    },
+    traits::{node_implementation::NodeType, state::ConsensusTime},
+};
+use std::marker::PhantomData;
+
+#[cfg(test)]
+#[test]
+fn test_message_priority() {
+    for (purpose, priority) in [
+        (MessagePurpose::ViewSyncVote, MessagePriority::High),
+        (MessagePurpose::ViewSyncProposal, MessagePriority::High),
+        (MessagePurpose::Internal, MessagePriority::High),
+        (MessagePurpose::Proposal, MessagePriority::Medium),
+        (MessagePurpose::Vote, MessagePriority::Medium),
+        (MessagePurpose::DAC, MessagePriority::Medium),
+        (MessagePurpose::Data, MessagePriority::Low),
+    ] {
+        assert_eq!(purpose.priority(), priority);
+    }
+    assert!(MessagePriority::High > MessagePriority::Medium);
+    assert!(MessagePriority::Medium > MessagePriority::Low);
+
+    let sender =
+        <SequencingTestTypes as NodeType>::SignatureKey::generated_from_seed_indexed([0u8; 32], 0)
+            .0;
+    let view = ViewNumber::new(1);
+    let message = |kind| Message::<SequencingTestTypes, SequencingMemoryImpl> {
+        sender,
+        kind,
+        _phantom: PhantomData,
+    };
+
+    let transaction = message(MessageKind::Data(DataMessage::SubmitTransaction(
+        SDemoTransaction {
+            id: 0,
+            padding: Vec::new(),
+        },
+        view,
+    )));
+    assert_eq!(transaction.priority(), MessagePriority::Low);
+
+    let timeout = message(MessageKind::from_consensus_message(SequencingMessage(
+        Left(GeneralConsensusMessage::InternalTrigger(
+            InternalTrigger::Timeout(view),
+        )),
+    )));
+    assert_eq!(timeout.priority(), MessagePriority::High);
+
+    // A peer can send this variant, so it must not panic.
+    let unreachable = message(MessageKind::_Unreachable(PhantomData));
+    assert_eq!(unreachable.priority(), MessagePriority::Low);
+}
+
+#[cfg(test)]
+#[test]
+fn test_processed_message_view_number() {
+    let sender =
+        <SequencingTestTypes as NodeType>::SignatureKey::generated_from_seed_indexed([0u8; 32], 0)
+            .0;
+    let timeout: ProcessedGeneralConsensusMessage<SequencingTestTypes, SequencingMemoryImpl> =
+        ProcessedGeneralConsensusMessage::InternalTrigger(InternalTrigger::Timeout(
+            ViewNumber::new(3),
+        ));
+    let certificate = ProcessedCommitteeConsensusMessage::<SequencingTestTypes>::DACertificate(
+        DACertificate {
+            view_number: ViewNumber::new(5),
+            block_commitment: fake_commitment(),
+            signatures: AssembledSignature::Genesis(),
+        },
+        sender,
+    );
+
+    assert_eq!(timeout.view_number(), ViewNumber::new(3));
+    assert_eq!(certificate.view_number(), ViewNumber::new(5));
+
+    // Same answer as converting back to the unprocessed message.
+    let processed: ProcessedSequencingMessage<SequencingTestTypes, SequencingMemoryImpl> =
+        Left(timeout.clone());
+    assert_eq!(
+        SequencingMessage::from(processed).view_number(),
+        timeout.view_number()
+    );
+    let processed: ProcessedSequencingMessage<SequencingTestTypes, SequencingMemoryImpl> =
+        Right(certificate.clone());
+    assert_eq!(
+        SequencingMessage::from(processed).view_number(),
+        certificate.view_number()
+    );
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_processed_view_sync_messages() {
+    use hotshot::HotShotSequencingConsensusApi;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        certificate::{ViewSyncCertificate, ViewSyncCertificateInternal},
+        message::Proposal,
+        traits::{
+            election::{ConsensusExchange, ViewSyncExchangeType},
+            node_implementation::ExchangesType,
+            signature_key::EncodedSignature,
+        },
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let view_sync_exchange = api.inner.exchanges.view_sync_exchange().clone();
+    let sender = *view_sync_exchange.public_key();
+    let view = ViewNumber::new(2);
+    let vote_token = || view_sync_exchange.make_vote_token(view).unwrap().unwrap();
+    let internal = || ViewSyncCertificateInternal {
+        relay: 0,
+        round: view,
+        signatures: AssembledSignature::Genesis(),
+    };
+
+    let votes: Vec<GeneralConsensusMessage<SequencingTestTypes, SequencingMemoryImpl>> = vec![
+        view_sync_exchange
+            .create_precommit_message(view, 0, vote_token())
+            .unwrap(),
+        view_sync_exchange
+            .create_commit_message(view, 0, vote_token())
+            .unwrap(),
+        view_sync_exchange
+            .create_finalize_message(view, 0, vote_token())
+            .unwrap(),
+    ];
+    let certificates = [
+        ViewSyncCertificate::PreCommit(internal()),
+        ViewSyncCertificate::Commit(internal()),
+        ViewSyncCertificate::Finalize(internal()),
+    ]
+    .into_iter()
+    .map(|certificate| {
+        GeneralConsensusMessage::ViewSyncCertificate(Proposal {
+            data: certificate,
+            signature: EncodedSignature(Vec::new()),
+        })
+    });
+
+    // Every view sync message is carried across unchanged, and reports the view it was sent in.
+    for message in votes.into_iter().chain(certificates) {
+        let processed = ProcessedGeneralConsensusMessage::new(message.clone(), sender);
+        match (&message, &processed) {
+            (
+                GeneralConsensusMessage::ViewSyncVote(vote),
+                ProcessedGeneralConsensusMessage::ViewSyncVote(processed_vote),
+            ) => assert_eq!(vote, processed_vote),
+            (
+                GeneralConsensusMessage::ViewSyncCertificate(certificate),
+                ProcessedGeneralConsensusMessage::ViewSyncCertificate(processed_certificate),
+            ) => assert_eq!(certificate, processed_certificate),
+            _ => panic!("View sync message changed kind when processed: {processed:?}"),
+        }
+        assert_eq!(processed.view_number(), view);
+        let converted: ProcessedSequencingMessage<SequencingTestTypes, SequencingMemoryImpl> =
+            Left(processed);
+        assert_eq!(SequencingMessage::from(converted).view_number(), view);
+    }
+}
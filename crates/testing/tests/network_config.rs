@@ -0,0 +1,212 @@
+use hotshot::{
+    traits::election::static_committee::StaticElectionConfig,
+    types::{bn254::BN254Pub, SignatureKey},
+};
+use hotshot_orchestrator::config::{NetworkConfig, NetworkConfigError, NetworkConfigFile};
+use hotshot_testing::node_types::{SequencingTestTypes, StaticMembership};
+use hotshot_types::{
+    data::ViewNumber,
+    traits::{election::Membership, state::ConsensusTime},
+};
+use hotshot_utils::bincode::bincode_opts;
+use std::collections::HashMap;
+
+type TestNetworkConfig =
+    NetworkConfig<BN254Pub, <BN254Pub as SignatureKey>::StakeTableEntry, StaticElectionConfig>;
+
+/// One declared stake for each of the default file's 10 nodes
+const TEN_NODE_STAKES: &str = "stake_distribution = [1, 2, 4, 1, 1, 1, 1, 1, 1, 1]\n";
+
+fn load(file: &str) -> TestNetworkConfig {
+    let file: NetworkConfigFile = toml::from_str(file).unwrap();
+    file.validate().unwrap();
+    // Writing the file back out must not lose the declared stakes
+    let reloaded: NetworkConfigFile = toml::from_str(&toml::to_string(&file).unwrap()).unwrap();
+    assert_eq!(reloaded.stake_distribution, file.stake_distribution);
+    file.into()
+}
+
+#[cfg(test)]
+#[test]
+fn test_network_config_declared_stake_round_trip() {
+    use bincode::Options;
+
+    // Files written before stake could be declared still load, with every node at stake 1
+    let config = load("rounds = 10\n");
+    assert_eq!(config.stake_distribution, None);
+    assert!((0..4).all(|node_index| config.stake_of(node_index) == 1));
+
+    let config = load(&format!("rounds = 10\n{TEN_NODE_STAKES}"));
+    assert_eq!(
+        config.stake_distribution,
+        Some(vec![1, 2, 4, 1, 1, 1, 1, 1, 1, 1])
+    );
+    assert_eq!(
+        (0..4)
+            .map(|node_index| config.stake_of(node_index))
+            .collect::<Vec<_>>(),
+        vec![1, 2, 4, 1]
+    );
+
+    // The orchestrator hands the same stakes out to every node
+    let bytes = bincode_opts().serialize(&config).unwrap();
+    let received: TestNetworkConfig = bincode_opts().deserialize(&bytes).unwrap();
+    assert_eq!(received.stake_distribution, config.stake_distribution);
+}
+
+#[cfg(test)]
+#[test]
+fn test_network_config_declared_stake_weights_committee() {
+    let config = load(TEN_NODE_STAKES);
+    let stakes = config.stake_distribution.clone().unwrap();
+
+    let known_nodes: Vec<BN254Pub> = (0..stakes.len() as u64)
+        .map(|node_index| {
+            let private_key = BN254Pub::generated_from_seed_indexed(config.seed, node_index).1;
+            BN254Pub::from_private(&private_key)
+        })
+        .collect();
+    let known_nodes_with_stake = known_nodes
+        .iter()
+        .zip(0u64..)
+        .map(|(key, node_index)| key.get_stake_table_entry(config.stake_of(node_index)))
+        .collect();
+    let membership = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        known_nodes_with_stake,
+        known_nodes.clone(),
+        <StaticMembership as Membership<SequencingTestTypes>>::stake_weighted_election_config(
+            &stakes,
+        ),
+    );
+
+    let mut leader_counts: HashMap<BN254Pub, u64> = HashMap::new();
+    for view in 0..700 {
+        let leader = <StaticMembership as Membership<SequencingTestTypes>>::get_leader(
+            &membership,
+            ViewNumber::new(view),
+        );
+        *leader_counts.entry(leader).or_default() += 1;
+    }
+
+    // Each node leads in proportion to the stake declared for it in the file
+    let counts: Vec<u64> = known_nodes
+        .iter()
+        .map(|key| leader_counts.get(key).copied().unwrap_or(0))
+        .collect();
+    assert_eq!(counts, vec![50, 100, 200, 50, 50, 50, 50, 50, 50, 50]);
+}
+
+#[cfg(test)]
+#[test]
+fn test_network_config_rejects_stake_per_node_mismatch() {
+    // The default file has 10 nodes
+    for stakes in ["[1, 2, 4]", "[1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]", "[]"] {
+        let file: NetworkConfigFile =
+            toml::from_str(&format!("stake_distribution = {stakes}\n")).unwrap();
+        assert!(matches!(
+            file.validate(),
+            Err(NetworkConfigError::StakeDistributionLength {
+                total_nodes: 10,
+                ..
+            })
+        ));
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_config_declared_stake_forms_certificate() {
+    use commit::Committable;
+    use either::Either;
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_testing::{node_types::SequencingMemoryImpl, task_helpers::build_system_handle};
+    use hotshot_types::{
+        message::Message,
+        traits::{
+            election::{ConsensusExchange, VoteData},
+            node_implementation::{CommitteeEx, ExchangesType},
+        },
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let config = load(TEN_NODE_STAKES);
+    let keys: Vec<_> = (0..config.config.total_nodes.get() as u64)
+        .map(|node_index| BN254Pub::generated_from_seed_indexed(config.seed, node_index))
+        .collect();
+    let entries: Vec<_> = keys
+        .iter()
+        .zip(0u64..)
+        .map(|((key, _), node_index)| key.get_stake_table_entry(config.stake_of(node_index)))
+        .collect();
+
+    // A DA committee over the stake table the file declares
+    let handle = build_system_handle(0).await.0;
+    let network = handle
+        .hotshot
+        .inner
+        .exchanges
+        .committee_exchange()
+        .network()
+        .clone();
+    let (public_key, private_key) = keys[0].clone();
+    let exchange = <CommitteeEx<SequencingTestTypes, SequencingMemoryImpl> as ConsensusExchange<
+        SequencingTestTypes,
+        Message<SequencingTestTypes, SequencingMemoryImpl>,
+    >>::create(
+        entries.clone(),
+        keys.iter().map(|(key, _)| *key).collect(),
+        <StaticMembership as Membership<SequencingTestTypes>>::stake_weighted_election_config(
+            config.stake_distribution.as_ref().unwrap(),
+        ),
+        network,
+        public_key,
+        entries[0].clone(),
+        private_key,
+    );
+
+    let view = ViewNumber::new(1);
+    let commitment = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    })
+    .commit();
+    let vote_data = VoteData::DA(commitment);
+    let signed_data = vote_data.commit_for_chain(exchange.membership().chain_id());
+
+    // Every node votes until the votes form a certificate
+    let mut accumulator = exchange.empty_accumulator();
+    let mut certificate = None;
+    for (key, private_key) in &keys {
+        let token = exchange
+            .membership()
+            .make_vote_token(view, private_key)
+            .unwrap()
+            .unwrap();
+        match exchange.accumulate_vote(
+            &key.to_bytes(),
+            &BN254Pub::sign(private_key, signed_data.as_ref()),
+            commitment,
+            vote_data.clone(),
+            token,
+            view,
+            accumulator,
+            None,
+        ) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(formed) => {
+                certificate = Some(formed);
+                break;
+            }
+        }
+    }
+
+    // The certificate verifies against the declared stakes
+    let certificate = certificate.expect("Votes from every node should form a certificate");
+    assert!(exchange.is_valid_cert(&certificate, commitment));
+}
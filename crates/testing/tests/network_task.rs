@@ -8,7 +8,8 @@ use hotshot_testing::{
 use hotshot_types::{
     data::{DAProposal, ViewNumber},
     traits::{
-        consensus_api::ConsensusSharedApi, node_implementation::ExchangesType, state::ConsensusTime,
+        consensus_api::ConsensusSharedApi, election::DEFAULT_CHAIN_ID,
+        node_implementation::ExchangesType, state::ConsensusTime,
     },
 };
 use std::collections::HashMap;
@@ -44,10 +45,7 @@ async fn test_network_task() {
     let block_commitment = block.commit();
     let signature = committee_exchange.sign_da_proposal(&block_commitment);
     let da_proposal = Proposal {
-        data: DAProposal {
-            deltas: block.clone(),
-            view_number: ViewNumber::new(2),
-        },
+        data: DAProposal::new(block.clone(), ViewNumber::new(2)),
         signature,
     };
     let quorum_proposal = build_quorum_proposal(&handle, priv_key, 2).await;
@@ -98,3 +96,1066 @@ async fn test_network_task() {
     let build_fn = |task_runner, _| async { task_runner };
     run_harness(input, output, Some(event_stream), build_fn).await;
 }
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_drops_non_committee_da_vote() {
+    use either::Right;
+    use futures::StreamExt;
+    use hotshot::{
+        demos::sdemo::{SDemoBlock, SDemoNormalBlock},
+        types::{bn254::BN254Pub, SignatureKey},
+    };
+    use hotshot_task::{
+        event_stream::{ChannelStream, EventStream},
+        task::FilterEvent,
+    };
+    use hotshot_task_impls::network::NetworkMessageTaskState;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        message::{Message, MessageKind, SequencingMessage},
+        traits::election::{CommitteeExchangeType, ConsensusExchange},
+    };
+    use std::marker::PhantomData;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let pub_key = *api.public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let view = ViewNumber::new(2);
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let vote = committee_exchange.create_da_message(block.commit(), view, vote_token);
+
+    // The same vote, once from a committee member and once relayed by a node outside the committee.
+    let outsider = BN254Pub::generated_from_seed_indexed([1u8; 32], 0).0;
+    let messages = [outsider, pub_key]
+        .into_iter()
+        .map(|sender| Message {
+            sender,
+            kind: MessageKind::from_consensus_message(SequencingMessage(Right(vote.clone()))),
+            _phantom: PhantomData,
+        })
+        .collect();
+
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream.subscribe(FilterEvent::default()).await;
+    let mut state = NetworkMessageTaskState::<SequencingTestTypes, SequencingMemoryImpl>::new(
+        event_stream.clone(),
+        DEFAULT_CHAIN_ID,
+    )
+    .dropping_non_committee_votes(committee_exchange.membership().clone());
+    state.handle_messages(messages).await;
+    event_stream.publish(SequencingHotShotEvent::Shutdown).await;
+
+    // Only the committee member's vote is published.
+    assert!(matches!(
+        events.next().await,
+        Some(SequencingHotShotEvent::DAVoteRecv(_))
+    ));
+    assert!(matches!(
+        events.next().await,
+        Some(SequencingHotShotEvent::Shutdown)
+    ));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_prunes_per_view_state() {
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_task::event_stream::ChannelStream;
+    use hotshot_task_impls::network::{NetworkEventTaskState, RETAINED_VIEWS};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        message::Proposal,
+        traits::election::{CommitteeExchangeType, ConsensusExchange},
+        vote::DAVote,
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let pub_key = *api.public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let signature = committee_exchange.sign_da_proposal(&block.commit());
+
+    let mut state: NetworkEventTaskState<
+        _,
+        _,
+        DAProposal<SequencingTestTypes>,
+        DAVote<SequencingTestTypes>,
+        _,
+        _,
+    > = NetworkEventTaskState::new(committee_exchange.network().clone(), ChannelStream::new());
+
+    // Send one proposal in each of many views.
+    let last_view = 5 * RETAINED_VIEWS;
+    for view in 1..=last_view {
+        let view = ViewNumber::new(view);
+        let proposal = Proposal {
+            data: DAProposal::new(block.clone(), view),
+            signature: signature.clone(),
+        };
+        let membership = committee_exchange.membership();
+        assert!(state
+            .handle_event(SequencingHotShotEvent::ViewChange(view), membership)
+            .await
+            .is_none());
+        assert!(state
+            .handle_event(
+                SequencingHotShotEvent::DAProposalSend(proposal, pub_key),
+                membership,
+            )
+            .await
+            .is_none());
+    }
+
+    // Only the most recent views are still tracked, each with its one message.
+    assert_eq!(state.view, ViewNumber::new(last_view));
+    let tracked: Vec<_> = state.messages_per_view.keys().map(|view| **view).collect();
+    assert_eq!(
+        tracked,
+        (last_view - RETAINED_VIEWS..=last_view).collect::<Vec<_>>()
+    );
+    assert!(state.messages_per_view.values().all(|count| *count == 1));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_drops_forged_view_sync_vote() {
+    use either::Left;
+    use futures::StreamExt;
+    use hotshot::types::{bn254::BN254Pub, SignatureKey};
+    use hotshot_task::{
+        event_stream::{ChannelStream, EventStream},
+        task::FilterEvent,
+    };
+    use hotshot_task_impls::network::NetworkMessageTaskState;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        message::{GeneralConsensusMessage, Message, MessageKind, SequencingMessage},
+        traits::election::{ConsensusExchange, ViewSyncExchangeType},
+        vote::ViewSyncVote,
+    };
+    use std::marker::PhantomData;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let view_sync_exchange = api.inner.exchanges.view_sync_exchange().clone();
+    let pub_key = *api.public_key();
+    let round = ViewNumber::new(2);
+    let vote_token = view_sync_exchange.make_vote_token(round).unwrap().unwrap();
+    let Some(GeneralConsensusMessage::ViewSyncVote(ViewSyncVote::PreCommit(vote))) =
+        view_sync_exchange.create_precommit_message::<SequencingMemoryImpl>(round, 0, vote_token)
+    else {
+        panic!("Failed to create a precommit vote");
+    };
+
+    // Another node claims the vote as its own, keeping our signature.
+    let forger = BN254Pub::generated_from_seed_indexed([0u8; 32], 3).0;
+    let mut forged = vote.clone();
+    forged.signature.0 = forger.to_bytes();
+
+    let messages = [
+        (forger, forged.clone()),
+        (forger, vote.clone()),
+        (pub_key, vote),
+    ]
+    .into_iter()
+    .map(|(sender, vote)| Message {
+        sender,
+        kind: MessageKind::from_consensus_message(SequencingMessage(Left(
+            GeneralConsensusMessage::ViewSyncVote(ViewSyncVote::PreCommit(vote)),
+        ))),
+        _phantom: PhantomData,
+    })
+    .collect();
+
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream.subscribe(FilterEvent::default()).await;
+    let mut state = NetworkMessageTaskState::<SequencingTestTypes, SequencingMemoryImpl>::new(
+        event_stream.clone(),
+        DEFAULT_CHAIN_ID,
+    );
+    state.handle_messages(messages).await;
+    event_stream.publish(SequencingHotShotEvent::Shutdown).await;
+
+    // Only the vote signed by its sender is published.
+    assert!(matches!(
+        events.next().await,
+        Some(SequencingHotShotEvent::ViewSyncVoteRecv(vote)) if vote.signature_key() == pub_key
+    ));
+    assert!(matches!(
+        events.next().await,
+        Some(SequencingHotShotEvent::Shutdown)
+    ));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_send_metrics() {
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_task::event_stream::ChannelStream;
+    use hotshot_task_impls::network::{NetworkEventTaskState, SendCounts};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        message::{CommitteeConsensusMessage, Proposal},
+        traits::election::{CommitteeExchangeType, ConsensusExchange, Membership},
+        vote::DAVote,
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let membership = committee_exchange.membership();
+    let pub_key = *api.public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+
+    let mut state: NetworkEventTaskState<
+        _,
+        _,
+        DAProposal<SequencingTestTypes>,
+        DAVote<SequencingTestTypes>,
+        _,
+        _,
+    > = NetworkEventTaskState::new(committee_exchange.network().clone(), ChannelStream::new());
+
+    // DA votes go directly to the leader of their view: three to view 1's, two to view 2's.
+    let mut vote_size = 0;
+    for (view, count) in [(1, 3), (2, 2)] {
+        let view = ViewNumber::new(view);
+        let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+        let CommitteeConsensusMessage::DAVote(vote) =
+            committee_exchange.create_da_message(block.commit(), view, vote_token)
+        else {
+            panic!("Expected a DA vote");
+        };
+        for _ in 0..count {
+            state
+                .handle_event(SequencingHotShotEvent::DAVoteSend(vote.clone()), membership)
+                .await;
+        }
+        vote_size = state.send_metrics.direct[&membership.get_leader(view)].bytes / count;
+    }
+    let proposal = Proposal {
+        data: DAProposal::new(block.clone(), ViewNumber::new(1)),
+        signature: committee_exchange.sign_da_proposal(&block.commit()),
+    };
+    state
+        .handle_event(
+            SequencingHotShotEvent::DAProposalSend(proposal, pub_key),
+            membership,
+        )
+        .await;
+
+    let snapshot = state.send_metrics.snapshot();
+    assert!(vote_size > 0);
+    assert_eq!(snapshot.direct.len(), 2);
+    assert_eq!(
+        snapshot.direct[&membership.get_leader(ViewNumber::new(1))],
+        SendCounts {
+            messages: 3,
+            bytes: 3 * vote_size,
+        }
+    );
+    assert_eq!(
+        snapshot.direct[&membership.get_leader(ViewNumber::new(2))],
+        SendCounts {
+            messages: 2,
+            bytes: 2 * vote_size,
+        }
+    );
+    assert_eq!(snapshot.broadcast.messages, 1);
+    assert!(snapshot.broadcast.bytes > 0);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_fallback_handler() {
+    use either::Left;
+    use futures::StreamExt;
+    use hotshot::demos::sdemo::SDemoTransaction;
+    use hotshot_task::{
+        event_stream::{ChannelStream, EventStream},
+        task::FilterEvent,
+    };
+    use hotshot_task_impls::network::{NetworkMessageTaskState, UnhandledMessage};
+    use hotshot_types::message::{
+        DataMessage, GeneralConsensusMessage, InternalTrigger, Message, MessageKind,
+        SequencingMessage,
+    };
+    use std::{
+        marker::PhantomData,
+        sync::{Arc, Mutex},
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = hotshot_testing::task_helpers::build_system_handle(2)
+        .await
+        .0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let sender = *api.public_key();
+    let transaction = SDemoTransaction {
+        id: 1,
+        padding: Vec::new(),
+    };
+
+    // An internal-only message, a placeholder message, then a transaction.
+    let messages = vec![
+        Message {
+            sender,
+            kind: MessageKind::from_consensus_message(SequencingMessage(Left(
+                GeneralConsensusMessage::InternalTrigger(InternalTrigger::Timeout(
+                    ViewNumber::new(1),
+                )),
+            ))),
+            _phantom: PhantomData,
+        },
+        Message {
+            sender,
+            kind: MessageKind::_Unreachable(PhantomData),
+            _phantom: PhantomData,
+        },
+        Message {
+            sender,
+            kind: MessageKind::Data(DataMessage::SubmitTransaction(
+                transaction.clone(),
+                ViewNumber::new(1),
+            )),
+            _phantom: PhantomData,
+        },
+    ];
+
+    let unhandled = Arc::new(Mutex::new(Vec::new()));
+    let recorded = unhandled.clone();
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream.subscribe(FilterEvent::default()).await;
+    let mut state = NetworkMessageTaskState::<SequencingTestTypes, SequencingMemoryImpl>::new(
+        event_stream.clone(),
+        DEFAULT_CHAIN_ID,
+    )
+    .with_fallback_handler(Arc::new(move |reason, message| {
+        recorded.lock().unwrap().push((reason, message.sender));
+    }));
+    state.handle_messages(messages).await;
+
+    assert_eq!(
+        *unhandled.lock().unwrap(),
+        vec![
+            (UnhandledMessage::Unexpected, sender),
+            (UnhandledMessage::Unknown, sender)
+        ]
+    );
+    // The rest of the batch is still handled.
+    assert_eq!(
+        events.next().await,
+        Some(SequencingHotShotEvent::TransactionsRecv(vec![transaction]))
+    );
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_event_observer() {
+    use either::Right;
+    use futures::StreamExt;
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock, SDemoTransaction};
+    use hotshot_task::{
+        event_stream::{ChannelStream, EventStream},
+        task::FilterEvent,
+    };
+    use hotshot_task_impls::network::NetworkMessageTaskState;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        message::{DataMessage, Message, MessageKind, SequencingMessage},
+        traits::election::{CommitteeExchangeType, ConsensusExchange},
+    };
+    use std::{
+        marker::PhantomData,
+        sync::{Arc, Mutex},
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let sender = *api.public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let view = ViewNumber::new(2);
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let vote = committee_exchange.create_da_message(block.commit(), view, vote_token);
+    let messages = vec![
+        Message {
+            sender,
+            kind: MessageKind::from_consensus_message(SequencingMessage(Right(vote))),
+            _phantom: PhantomData,
+        },
+        Message {
+            sender,
+            kind: MessageKind::Data(DataMessage::SubmitTransaction(
+                SDemoTransaction {
+                    id: 1,
+                    padding: Vec::new(),
+                },
+                view,
+            )),
+            _phantom: PhantomData,
+        },
+    ];
+
+    let observed = Arc::new(Mutex::new(Vec::new()));
+    let recorded = observed.clone();
+    let event_stream = ChannelStream::new();
+    let (events, _) = event_stream.subscribe(FilterEvent::default()).await;
+    let mut state = NetworkMessageTaskState::<SequencingTestTypes, SequencingMemoryImpl>::new(
+        event_stream.clone(),
+        DEFAULT_CHAIN_ID,
+    )
+    .with_event_observer(Arc::new(move |event| {
+        recorded.lock().unwrap().push(event.clone());
+    }));
+    state.handle_messages(messages).await;
+
+    // Downstream consumers receive exactly what the observer saw, in the same order.
+    let received: Vec<_> = events.take(2).collect().await;
+    let observed = observed.lock().unwrap().clone();
+    assert_eq!(observed.len(), 2);
+    assert_eq!(received, observed);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_pending_outbound() {
+    use futures::StreamExt;
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_task::event_stream::{ChannelStream, EventStream};
+    use hotshot_task_impls::network::{NetworkEventTaskState, NetworkTaskKind};
+    use hotshot_testing::{
+        mock_channel::MockDAComm, node_types::StaticMembership, task_helpers::build_system_handle,
+    };
+    use hotshot_types::{
+        message::CommitteeConsensusMessage,
+        traits::election::{CommitteeExchangeType, ConsensusExchange},
+        vote::DAVote,
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let membership = committee_exchange.membership();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let view = ViewNumber::new(1);
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let CommitteeConsensusMessage::DAVote(vote) =
+        committee_exchange.create_da_message(block.commit(), view, vote_token)
+    else {
+        panic!("Expected a DA vote");
+    };
+
+    let channel = MockDAComm::new(*api.public_key());
+    let mut state = NetworkEventTaskState::new(channel.clone(), ChannelStream::new());
+    let outbound = state.outbound.clone();
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream
+        .subscribe(NetworkEventTaskState::<
+            SequencingTestTypes,
+            SequencingMemoryImpl,
+            DAProposal<SequencingTestTypes>,
+            DAVote<SequencingTestTypes>,
+            StaticMembership,
+            MockDAComm,
+        >::counted_filter(
+            NetworkTaskKind::Committee, outbound.clone()
+        ))
+        .await;
+    assert_eq!(state.pending_outbound(), 0);
+
+    // Three votes queue up for the task; an event it does not handle is not counted.
+    for _ in 0..3 {
+        event_stream
+            .publish(SequencingHotShotEvent::DAVoteSend(vote.clone()))
+            .await;
+    }
+    event_stream
+        .publish(SequencingHotShotEvent::Timeout(view))
+        .await;
+    assert_eq!(outbound.pending(), 3);
+
+    for remaining in (0..3).rev() {
+        let event = events.next().await.unwrap();
+        state.handle_event(event, membership).await;
+        state.outbound.event_handled();
+        assert_eq!(state.pending_outbound(), remaining);
+    }
+    assert_eq!(channel.recorded_sends().len(), 3);
+
+    // Handling an event that was never counted leaves the gauge at zero.
+    outbound.event_handled();
+    assert_eq!(outbound.pending(), 0);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_kinds_filter_their_events() {
+    use hotshot::{
+        demos::sdemo::{SDemoBlock, SDemoNormalBlock},
+        tasks::register_network_tasks,
+    };
+    use hotshot_task::{event_stream::ChannelStream, global_registry::GlobalRegistry};
+    use hotshot_task_impls::network::NetworkTaskKind;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        certificate::{
+            AssembledSignature, DACertificate, ViewSyncCertificate, ViewSyncCertificateInternal,
+        },
+        data::fake_commitment,
+        message::Proposal,
+        traits::{election::CommitteeExchangeType, signature_key::EncodedSignature},
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let pub_key = *api.public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let da_proposal = Proposal {
+        data: DAProposal::new(block.clone(), ViewNumber::new(2)),
+        signature: committee_exchange.sign_da_proposal(&block.commit()),
+    };
+    let quorum_proposal = build_quorum_proposal(&handle, api.private_key(), 2).await;
+    let dac = DACertificate {
+        view_number: ViewNumber::new(2),
+        block_commitment: fake_commitment(),
+        signatures: AssembledSignature::Genesis(),
+    };
+    let view_sync_certificate = Proposal {
+        data: ViewSyncCertificate::PreCommit(ViewSyncCertificateInternal {
+            relay: 0,
+            round: ViewNumber::new(2),
+            signatures: AssembledSignature::Genesis(),
+        }),
+        signature: EncodedSignature(Vec::new()),
+    };
+
+    let sends = [
+        (
+            NetworkTaskKind::Quorum,
+            SequencingHotShotEvent::QuorumProposalSend(quorum_proposal, pub_key),
+        ),
+        (
+            NetworkTaskKind::Quorum,
+            SequencingHotShotEvent::DACSend(dac, pub_key),
+        ),
+        (
+            NetworkTaskKind::Committee,
+            SequencingHotShotEvent::DAProposalSend(da_proposal, pub_key),
+        ),
+        (
+            NetworkTaskKind::ViewSync,
+            SequencingHotShotEvent::ViewSyncCertificateSend(view_sync_certificate, pub_key),
+        ),
+    ];
+
+    // Each send goes to the task of its kind only, and every task follows views and shutdown.
+    // The tasks registered for the node's exchanges come in the order of their kinds.
+    let tasks = register_network_tasks::<SequencingTestTypes, SequencingMemoryImpl>(
+        &handle.hotshot.inner.exchanges,
+        ChannelStream::new(),
+        &GlobalRegistry::new(),
+    )
+    .await;
+    assert_eq!(tasks.len(), NetworkTaskKind::ALL.len());
+    for (task_kind, task) in NetworkTaskKind::ALL.into_iter().zip(&tasks) {
+        for (sending_kind, event) in &sends {
+            assert_eq!(
+                (task.filter)(event),
+                task_kind == *sending_kind,
+                "{task_kind:?} task and {event:?}"
+            );
+        }
+        assert!((task.filter)(&SequencingHotShotEvent::ViewChange(
+            ViewNumber::new(3)
+        )));
+        assert!((task.filter)(&SequencingHotShotEvent::Shutdown));
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_drops_own_messages() {
+    use either::Right;
+    use futures::StreamExt;
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_task::{
+        event_stream::{ChannelStream, EventStream},
+        task::FilterEvent,
+    };
+    use hotshot_task_impls::network::NetworkMessageTaskState;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        message::{Message, MessageKind, SequencingMessage},
+        traits::{
+            election::{CommitteeExchangeType, ConsensusExchange},
+            network::CommunicationChannel,
+        },
+    };
+    use std::marker::PhantomData;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let own_key = committee_exchange.network().local_public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let view = ViewNumber::new(2);
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let vote = committee_exchange.create_da_message(block.commit(), view, vote_token);
+    let own_vote = || {
+        vec![Message {
+            sender: own_key,
+            kind: MessageKind::from_consensus_message(SequencingMessage(Right(vote.clone()))),
+            _phantom: PhantomData,
+        }]
+    };
+
+    let event_stream = ChannelStream::new();
+    let (mut events, _) = event_stream.subscribe(FilterEvent::default()).await;
+    let mut looping_back =
+        NetworkMessageTaskState::<SequencingTestTypes, SequencingMemoryImpl>::new(
+            event_stream.clone(),
+            DEFAULT_CHAIN_ID,
+        );
+
+    // Looped back, the node's own vote is published by default...
+    looping_back.handle_messages(own_vote()).await;
+    assert!(matches!(
+        events.next().await,
+        Some(SequencingHotShotEvent::DAVoteRecv(_))
+    ));
+
+    // ...and dropped once the task is told to drop its own messages.
+    let mut dropping = looping_back.dropping_own_messages(own_key);
+    dropping.handle_messages(own_vote()).await;
+    event_stream.publish(SequencingHotShotEvent::Shutdown).await;
+    assert!(matches!(
+        events.next().await,
+        Some(SequencingHotShotEvent::Shutdown)
+    ));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_mock_comm_channel() {
+    use hotshot::demos::sdemo::SDemoTransaction;
+    use hotshot_testing::{
+        mock_channel::{MockDAComm, RecordedSend},
+        task_helpers::build_system_handle,
+    };
+    use hotshot_types::{
+        message::{DataMessage, Message, MessageKind},
+        traits::{
+            election::ConsensusExchange,
+            network::{CommunicationChannel, NetworkError, TransmitType},
+        },
+    };
+    use std::marker::PhantomData;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let membership = committee_exchange.membership();
+    let sender = *api.public_key();
+    let message = |id| Message::<SequencingTestTypes, SequencingMemoryImpl> {
+        sender,
+        kind: MessageKind::Data(DataMessage::SubmitTransaction(
+            SDemoTransaction {
+                id,
+                padding: Vec::new(),
+            },
+            ViewNumber::new(1),
+        )),
+        _phantom: PhantomData,
+    };
+
+    let channel = MockDAComm::new(sender);
+    // The task under test gets a clone; the test drives the original.
+    let task_channel = channel.clone();
+    assert_eq!(task_channel.local_public_key(), sender);
+
+    // Successful sends are recorded in order.
+    task_channel
+        .broadcast_message(message(0), membership)
+        .await
+        .unwrap();
+    task_channel
+        .direct_message(message(1), sender)
+        .await
+        .unwrap();
+    assert_eq!(
+        channel.recorded_sends(),
+        vec![
+            RecordedSend::Broadcast(message(0)),
+            RecordedSend::Direct(message(1), sender),
+        ]
+    );
+
+    // Failed sends report an error and are not recorded, then sending recovers.
+    channel.fail_next_n_sends(2);
+    assert!(matches!(
+        task_channel.direct_message(message(2), sender).await,
+        Err(NetworkError::CouldNotDeliver)
+    ));
+    assert!(matches!(
+        task_channel.broadcast_message(message(3), membership).await,
+        Err(NetworkError::CouldNotDeliver)
+    ));
+    task_channel
+        .direct_message(message(4), sender)
+        .await
+        .unwrap();
+    assert_eq!(channel.recorded_sends().len(), 3);
+    assert_eq!(
+        channel.recorded_sends()[2],
+        RecordedSend::Direct(message(4), sender)
+    );
+
+    // Injected messages are received once, by the transmit type they were injected with.
+    channel.inject(TransmitType::Direct, message(5));
+    channel.inject(TransmitType::Broadcast, message(6));
+    channel.inject(TransmitType::Direct, message(7));
+    assert_eq!(
+        task_channel.recv_msgs(TransmitType::Direct).await.unwrap(),
+        vec![message(5), message(7)]
+    );
+    assert_eq!(
+        task_channel
+            .recv_msgs(TransmitType::Broadcast)
+            .await
+            .unwrap(),
+        vec![message(6)]
+    );
+    assert!(task_channel
+        .recv_msgs(TransmitType::Direct)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Once shut down, nothing more is sent.
+    task_channel.shut_down().await;
+    assert!(matches!(
+        task_channel.direct_message(message(8), sender).await,
+        Err(NetworkError::ShutDown)
+    ));
+    assert_eq!(channel.recorded_sends().len(), 3);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_broadcasts_vote_to_unreachable_leader() {
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_task::event_stream::ChannelStream;
+    use hotshot_task_impls::network::NetworkEventTaskState;
+    use hotshot_testing::{
+        mock_channel::{MockDAComm, RecordedSend},
+        task_helpers::build_system_handle,
+    };
+    use hotshot_types::{
+        message::{CommitteeConsensusMessage, GeneralConsensusMessage},
+        traits::election::{
+            CommitteeExchangeType, ConsensusExchange, Membership, ViewSyncExchangeType,
+        },
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let view_sync_exchange = api.inner.exchanges.view_sync_exchange().clone();
+    let membership = committee_exchange.membership();
+    let view = ViewNumber::new(1);
+    let leader = membership.get_leader(view);
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let CommitteeConsensusMessage::DAVote(da_vote) =
+        committee_exchange.create_da_message(block.commit(), view, vote_token)
+    else {
+        panic!("Expected a DA vote");
+    };
+    // Relayed to the leader of the same view, which is sent as a plain direct message
+    let vote_token = view_sync_exchange.make_vote_token(view).unwrap().unwrap();
+    let Some(GeneralConsensusMessage::ViewSyncVote(view_sync_vote)) =
+        view_sync_exchange.create_precommit_message::<SequencingMemoryImpl>(view, 0, vote_token)
+    else {
+        panic!("Failed to create a precommit vote");
+    };
+
+    let channel = MockDAComm::new(*api.public_key());
+    channel.set_unreachable(leader);
+    let mut state = NetworkEventTaskState::new(channel.clone(), ChannelStream::new());
+
+    // A vote the leader cannot receive directly is broadcast instead.
+    state
+        .handle_event(
+            SequencingHotShotEvent::DAVoteSend(da_vote.clone()),
+            membership,
+        )
+        .await;
+    let sends = channel.recorded_sends();
+    assert_eq!(sends.len(), 1);
+    assert!(matches!(sends[0], RecordedSend::Broadcast(_)));
+
+    // A plain direct message to the same recipient is dropped rather than broadcast.
+    state
+        .handle_event(
+            SequencingHotShotEvent::ViewSyncVoteSend(view_sync_vote),
+            membership,
+        )
+        .await;
+    assert_eq!(channel.recorded_sends().len(), 1);
+
+    // Only the broadcast vote was delivered, so neither direct attempt is counted.
+    assert!(!state.send_metrics.direct.contains_key(&leader));
+    assert_eq!(state.send_metrics.broadcast.messages, 1);
+
+    // A transport that fails to deliver the vote is treated the same as an unreachable leader.
+    let channel = MockDAComm::new(*api.public_key());
+    channel.fail_next_n_sends(1);
+    let mut state = NetworkEventTaskState::new(channel.clone(), ChannelStream::new());
+    state
+        .handle_event(
+            SequencingHotShotEvent::DAVoteSend(da_vote.clone()),
+            membership,
+        )
+        .await;
+    assert!(matches!(
+        channel.recorded_sends()[..],
+        [RecordedSend::Broadcast(_)]
+    ));
+    assert!(state.send_metrics.direct.is_empty());
+    assert_eq!(state.send_metrics.broadcast.messages, 1);
+
+    // A vote that neither path delivers is not counted at all, but kept to retry.
+    channel.fail_next_n_sends(2);
+    state
+        .handle_event(SequencingHotShotEvent::DAVoteSend(da_vote), membership)
+        .await;
+    assert_eq!(channel.recorded_sends().len(), 1);
+    assert!(state.send_metrics.direct.is_empty());
+    assert_eq!(state.send_metrics.broadcast.messages, 1);
+    assert_eq!(state.outbound_queue.len(), 1);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_network_task_flushes_queue_on_shutdown() {
+    use hotshot::demos::sdemo::{SDemoBlock, SDemoNormalBlock};
+    use hotshot_task::{event_stream::ChannelStream, task::HotShotTaskCompleted};
+    use hotshot_task_impls::network::NetworkEventTaskState;
+    use hotshot_testing::{
+        mock_channel::{MockDAComm, RecordedSend},
+        task_helpers::build_system_handle,
+    };
+    use hotshot_types::{
+        message::CommitteeConsensusMessage,
+        traits::election::{CommitteeExchangeType, ConsensusExchange, Membership},
+    };
+    use std::time::Duration;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let membership = committee_exchange.membership();
+    let view = ViewNumber::new(1);
+    let leader = membership.get_leader(view);
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let CommitteeConsensusMessage::DAVote(vote) =
+        committee_exchange.create_da_message(block.commit(), view, vote_token)
+    else {
+        panic!("Expected a DA vote");
+    };
+
+    // The vote is enqueued while the transport fails both the direct send and the fallback
+    // broadcast.
+    let channel = MockDAComm::new(*api.public_key());
+    channel.fail_next_n_sends(2);
+    let mut state = NetworkEventTaskState::new(channel.clone(), ChannelStream::new());
+    state
+        .handle_event(SequencingHotShotEvent::DAVoteSend(vote.clone()), membership)
+        .await;
+    assert!(channel.recorded_sends().is_empty());
+    assert_eq!(state.outbound_queue.len(), 1);
+
+    // Shutting the task down flushes it before the task is dropped.
+    assert!(matches!(
+        state
+            .handle_event(SequencingHotShotEvent::Shutdown, membership)
+            .await,
+        Some(HotShotTaskCompleted::ShutDown)
+    ));
+    let send_metrics = state.send_metrics.snapshot();
+    drop(state);
+    assert!(matches!(
+        &channel.recorded_sends()[..],
+        [RecordedSend::Direct(_, recipient)] if *recipient == leader
+    ));
+    assert_eq!(send_metrics.direct[&leader].messages, 1);
+
+    // A transport that never recovers leaves the message unsent once the deadline passes.
+    let channel = MockDAComm::new(*api.public_key());
+    channel.fail_next_n_sends(usize::MAX);
+    let mut state = NetworkEventTaskState::new(channel.clone(), ChannelStream::new());
+    state
+        .handle_event(SequencingHotShotEvent::DAVoteSend(vote), membership)
+        .await;
+    assert_eq!(
+        state
+            .shutdown_and_flush(membership, Duration::from_millis(50))
+            .await,
+        1
+    );
+    assert!(channel.recorded_sends().is_empty());
+}
@@ -0,0 +1,327 @@
+use commit::Committable;
+use hotshot::{
+    demos::sdemo::{SDemoBlock, SDemoNormalBlock},
+    types::bn254::BN254Pub,
+    HotShotSequencingConsensusApi,
+};
+use hotshot_testing::{
+    node_types::{SequencingMemoryImpl, SequencingTestTypes},
+    task_helpers::build_system_handle,
+};
+use hotshot_types::{
+    certificate::check_individual_signatures,
+    traits::{
+        election::{
+            CommitteeExchangeType, ConsensusExchange, ProposalData, QuorumExchangeType, VoteData,
+        },
+        node_implementation::ExchangesType,
+        signature_key::SignatureKey,
+    },
+    verification::VerificationPool,
+};
+use std::num::{NonZeroU64, NonZeroUsize};
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_proposal_signature_domain() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let pub_key = *committee_exchange.public_key();
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let commitment = block.commit();
+    let signature = committee_exchange.sign_da_proposal(&commitment);
+
+    assert!(pub_key.validate(&signature, ProposalData::DA(commitment).commit().as_ref()));
+    assert!(!pub_key.validate(
+        &signature,
+        ProposalData::Quorum(commitment).commit().as_ref()
+    ));
+
+    // A proposal signature must never be replayable as a vote on the same commitment.
+    let votes = [
+        VoteData::DA(commitment),
+        VoteData::Yes(commitment),
+        VoteData::No(commitment),
+        VoteData::Timeout(commitment),
+        VoteData::ViewSyncPreCommit(commitment),
+        VoteData::ViewSyncCommit(commitment),
+        VoteData::ViewSyncFinalize(commitment),
+        VoteData::Upgrade(commitment),
+    ];
+    for vote in votes {
+        assert!(!pub_key.validate(&signature, vote.commit().as_ref()));
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_is_valid_proposal_signature() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let quorum_exchange = api.inner.exchanges.quorum_exchange().clone();
+    let proposer = *quorum_exchange.public_key();
+    let leaf = handle.get_decided_leaf().await;
+    let leaf_commitment = leaf.commit();
+    let signature = quorum_exchange
+        .sign_validating_or_commitment_proposal::<SequencingMemoryImpl>(&leaf_commitment);
+
+    assert!(quorum_exchange.is_valid_proposal_signature(&proposer, leaf_commitment, &signature));
+
+    // Signed by someone other than the claimed proposer.
+    let other = BN254Pub::generated_from_seed_indexed([0u8; 32], 2).0;
+    assert!(!quorum_exchange.is_valid_proposal_signature(&other, leaf_commitment, &signature));
+
+    // Signed as a vote rather than a proposal.
+    let (_, vote_signature) = quorum_exchange.sign_yes_vote(leaf_commitment);
+    assert!(!quorum_exchange.is_valid_proposal_signature(
+        &proposer,
+        leaf_commitment,
+        &vote_signature
+    ));
+}
+
+#[test]
+fn test_check_individual_signatures() {
+    let data = b"individual certificate payload";
+    let keys: Vec<_> = (0..4)
+        .map(|i| BN254Pub::generated_from_seed_indexed([0u8; 32], i))
+        .collect();
+    let stake_table: Vec<_> = keys
+        .iter()
+        .map(|(pub_key, _)| pub_key.get_stake_table_entry(1))
+        .collect();
+    let signatures: Vec<_> = keys
+        .iter()
+        .take(3)
+        .map(|(pub_key, priv_key)| (pub_key.to_bytes(), BN254Pub::sign(priv_key, data)))
+        .collect();
+    let threshold = NonZeroU64::new(3).unwrap();
+
+    // The signatures are checked the same way inline and as a batch on a dedicated pool.
+    for pool in [
+        VerificationPool::inline(),
+        VerificationPool::new(NonZeroUsize::new(2).unwrap()).unwrap(),
+    ] {
+        assert!(check_individual_signatures::<SequencingTestTypes>(
+            &pool,
+            &signatures,
+            &stake_table,
+            threshold,
+            data
+        ));
+
+        // Too little stake once a signer is dropped.
+        assert!(!check_individual_signatures::<SequencingTestTypes>(
+            &pool,
+            &signatures[..2],
+            &stake_table,
+            threshold,
+            data
+        ));
+
+        // A repeated signer must not be counted twice.
+        let mut duplicated = signatures[..2].to_vec();
+        duplicated.push(signatures[0].clone());
+        assert!(!check_individual_signatures::<SequencingTestTypes>(
+            &pool,
+            &duplicated,
+            &stake_table,
+            threshold,
+            data
+        ));
+
+        // A signature over different data is rejected.
+        let mut tampered = signatures.clone();
+        tampered[2].1 = BN254Pub::sign(&keys[2].1, b"something else");
+        assert!(!check_individual_signatures::<SequencingTestTypes>(
+            &pool,
+            &tampered,
+            &stake_table,
+            threshold,
+            data
+        ));
+
+        // Signers outside the stake table are rejected.
+        assert!(!check_individual_signatures::<SequencingTestTypes>(
+            &pool,
+            &signatures,
+            &stake_table[1..],
+            threshold,
+            data
+        ));
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_validate_keys_detects_mismatched_key_pair() {
+    use hotshot::traits::election::static_committee::StaticElectionConfig;
+    use hotshot_types::traits::{election::ElectionError, node_implementation::SequencingQuorumEx};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    assert!(api.inner.exchanges.validate_keys().is_ok());
+
+    let quorum_exchange = api.inner.exchanges.quorum_exchange().clone();
+    let num_nodes = quorum_exchange.total_nodes() as u64;
+    let keys: Vec<BN254Pub> = (0..num_nodes)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id).0)
+        .collect();
+    let entries = keys
+        .iter()
+        .map(|key| key.get_stake_table_entry(1u64))
+        .collect();
+    let (public_key, _) = BN254Pub::generated_from_seed_indexed([0u8; 32], 0);
+    let (_, other_private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], 1);
+    let mismatched = SequencingQuorumEx::<SequencingTestTypes, SequencingMemoryImpl>::create(
+        entries,
+        keys,
+        StaticElectionConfig::new(num_nodes, None).unwrap(),
+        quorum_exchange.network().clone(),
+        public_key,
+        public_key.get_stake_table_entry(1u64),
+        other_private_key,
+    );
+
+    assert!(matches!(
+        mismatched.validate_keys(),
+        Err(ElectionError::InconsistentKeyPair)
+    ));
+}
+
+/// A signer standing in for a remote signing service: it records every request and signs with a
+/// key it holds itself.
+struct RecordingSigner {
+    /// The key the "remote" service signs with
+    private_key: <BN254Pub as SignatureKey>::PrivateKey,
+    /// Every payload it was asked to sign, in order
+    requests: std::sync::Mutex<Vec<Vec<u8>>>,
+}
+
+impl hotshot_types::traits::signature_key::Signer<BN254Pub> for RecordingSigner {
+    fn sign(&self, data: &[u8]) -> hotshot_types::traits::signature_key::EncodedSignature {
+        self.requests.lock().unwrap().push(data.to_vec());
+        BN254Pub::sign(&self.private_key, data)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_remote_signer() {
+    use hotshot_types::traits::election::Membership;
+    use std::sync::Arc;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let mut committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let pub_key = *committee_exchange.public_key();
+    let signer = Arc::new(RecordingSigner {
+        private_key: committee_exchange.private_key().clone(),
+        requests: std::sync::Mutex::default(),
+    });
+    committee_exchange.set_signer(signer.clone());
+
+    let commitment = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    })
+    .commit();
+    let proposal_signature = committee_exchange.sign_da_proposal(&commitment);
+    let (_, vote_signature) = committee_exchange.sign_da_vote(commitment);
+    committee_exchange.validate_keys().unwrap();
+
+    // Every signature went through the remote signer, and is still valid for the node's key.
+    let proposal_data = ProposalData::DA(commitment).commit();
+    let vote_data =
+        VoteData::DA(commitment).commit_for_chain(committee_exchange.membership().chain_id());
+    let requests = signer.requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0], proposal_data.as_ref());
+    assert_eq!(requests[1], vote_data.as_ref());
+    assert!(pub_key.validate(&proposal_signature, proposal_data.as_ref()));
+    assert!(pub_key.validate(&vote_signature, vote_data.as_ref()));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_sign_and_verify_self_test() {
+    use hotshot_types::traits::{
+        consensus_api::{ConsensusSharedApi, SelfTestReport},
+        signature_key::InMemorySigner,
+    };
+    use std::sync::Arc;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(1).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let mut committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let report = api.sign_and_verify_self_test(&committee_exchange);
+    assert!(report.passed(), "{report:?}");
+
+    // A signer holding a key that does not belong to the node's public key fails verification.
+    let other_private_key = BN254Pub::generated_from_seed_indexed([0u8; 32], 2).1;
+    committee_exchange.set_signer(Arc::new(InMemorySigner::<BN254Pub>(other_private_key)));
+    let report = api.sign_and_verify_self_test(&committee_exchange);
+    assert_eq!(
+        report,
+        SelfTestReport {
+            signature_verified: false,
+            certificate_assembled: true,
+            certificate_verified: false,
+        }
+    );
+    assert!(!report.passed());
+}
@@ -0,0 +1,321 @@
+use hotshot::{
+    traits::election::static_committee::StaticElectionConfig,
+    types::{bn254::BN254Pub, SignatureKey},
+};
+use hotshot_testing::{
+    node_types::{SequencingMemoryImpl, SequencingTestTypes, StaticMembership},
+    test_builder::TestMetadata,
+};
+use hotshot_types::{
+    data::ViewNumber,
+    traits::{
+        election::{ElectionConfig, ElectionError, Membership},
+        state::ConsensusTime,
+    },
+    vote::ViewSyncPhase,
+};
+use std::collections::{HashMap, HashSet};
+
+fn known_nodes(
+    num_nodes: u64,
+) -> (
+    Vec<BN254Pub>,
+    Vec<<BN254Pub as SignatureKey>::StakeTableEntry>,
+) {
+    let keys: Vec<BN254Pub> = (0..num_nodes)
+        .map(|id| {
+            let private_key = BN254Pub::generated_from_seed_indexed([0u8; 32], id).1;
+            BN254Pub::from_private(&private_key)
+        })
+        .collect();
+    let entries = keys
+        .iter()
+        .map(|key| key.get_stake_table_entry(1u64))
+        .collect();
+    (keys, entries)
+}
+
+#[test]
+fn test_da_committee_size() {
+    let (keys, entries) = known_nodes(10);
+    let config = StaticElectionConfig::new(10, Some(4)).unwrap();
+
+    let quorum = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        entries.clone(),
+        keys.clone(),
+        config.clone(),
+    );
+    let da = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        entries,
+        keys.clone(),
+        config.da_committee_config(),
+    );
+
+    assert_eq!(
+        <StaticMembership as Membership<SequencingTestTypes>>::total_nodes(&quorum),
+        10
+    );
+    assert_eq!(
+        <StaticMembership as Membership<SequencingTestTypes>>::total_nodes(&da),
+        4
+    );
+    // The DA committee is the first `da_committee_size` nodes, and its threshold follows its size.
+    assert_eq!(
+        <StaticMembership as Membership<SequencingTestTypes>>::get_committee(
+            &da,
+            ViewNumber::genesis()
+        ),
+        keys[..4].iter().cloned().collect()
+    );
+    assert_eq!(
+        <StaticMembership as Membership<SequencingTestTypes>>::success_threshold(&da).get(),
+        3
+    );
+}
+
+#[test]
+fn test_overflowing_stake_distribution_rejected() {
+    let config = StaticElectionConfig::new(2, None).unwrap();
+    assert!(matches!(
+        config.clone().with_stake_distribution(vec![u64::MAX, 1]),
+        Err(ElectionError::StakeOverflow)
+    ));
+    assert!(config
+        .with_stake_distribution(vec![u64::MAX - 1, 1])
+        .is_ok());
+
+    // A config that skipped the check falls back to picking leaders round robin.
+    let (keys, entries) = known_nodes(2);
+    let membership = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        entries,
+        keys.clone(),
+        <StaticMembership as Membership<SequencingTestTypes>>::stake_weighted_election_config(&[
+            u64::MAX,
+            1,
+        ]),
+    );
+    for view in 0..4 {
+        assert_eq!(
+            <StaticMembership as Membership<SequencingTestTypes>>::get_leader(
+                &membership,
+                ViewNumber::new(view)
+            ),
+            keys[view as usize % 2]
+        );
+    }
+}
+
+#[test]
+fn test_da_committee_larger_than_quorum() {
+    assert!(StaticElectionConfig::new(4, Some(5)).is_err());
+    assert!(StaticElectionConfig::new(4, Some(4)).is_ok());
+}
+
+#[test]
+fn test_stake_weighted_leaders() {
+    let launcher = TestMetadata::default()
+        .with_stake_distribution(vec![1, 2, 4])
+        .gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>();
+    let config = launcher.resource_generator.config;
+    let membership = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        config.known_nodes_with_stake,
+        config.known_nodes.clone(),
+        config.election_config.unwrap(),
+    );
+
+    let mut leader_counts: HashMap<BN254Pub, u64> = HashMap::new();
+    for view in 0..700 {
+        let leader = <StaticMembership as Membership<SequencingTestTypes>>::get_leader(
+            &membership,
+            ViewNumber::new(view),
+        );
+        *leader_counts.entry(leader).or_default() += 1;
+    }
+
+    // Over many views each node leads in proportion to its stake.
+    let counts: Vec<u64> = config
+        .known_nodes
+        .iter()
+        .map(|key| leader_counts.get(key).copied().unwrap_or(0))
+        .collect();
+    assert_eq!(counts, vec![100, 200, 400]);
+}
+
+#[test]
+fn test_stake_table_for_view() {
+    let (keys, entries) = known_nodes(4);
+    let membership = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        entries,
+        keys,
+        <StaticMembership as Membership<SequencingTestTypes>>::default_election_config(4),
+    );
+
+    // A static committee never rotates, so every view resolves to the current stake table.
+    for view in [
+        ViewNumber::genesis(),
+        ViewNumber::new(1),
+        ViewNumber::new(1000),
+    ] {
+        assert_eq!(
+            <StaticMembership as Membership<SequencingTestTypes>>::get_committee_qc_stake_table_for_view(
+                &membership,
+                view
+            ),
+            <StaticMembership as Membership<SequencingTestTypes>>::get_committee_qc_stake_table(
+                &membership
+            )
+        );
+        assert_eq!(
+            <StaticMembership as Membership<SequencingTestTypes>>::success_threshold_for_view(
+                &membership,
+                view
+            ),
+            <StaticMembership as Membership<SequencingTestTypes>>::success_threshold(&membership)
+        );
+    }
+}
+
+#[test]
+fn test_membership_snapshot_round_trip() {
+    let launcher = TestMetadata::default()
+        .with_stake_distribution(vec![1, 2, 4])
+        .gen_launcher::<SequencingTestTypes, SequencingMemoryImpl>();
+    let config = launcher.resource_generator.config;
+    let fresh = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        config.known_nodes_with_stake,
+        config.known_nodes,
+        config.election_config.unwrap(),
+    );
+
+    let snapshot = <StaticMembership as Membership<SequencingTestTypes>>::snapshot(&fresh);
+    let bytes = bincode::serialize(&snapshot).unwrap();
+    let restored = <StaticMembership as Membership<SequencingTestTypes>>::restore(
+        bincode::deserialize(&bytes).unwrap(),
+    );
+
+    // A restored node must elect exactly the leaders a freshly elected one would.
+    assert_eq!(restored, fresh);
+    for view in 0..50 {
+        assert_eq!(
+            <StaticMembership as Membership<SequencingTestTypes>>::get_leader(
+                &restored,
+                ViewNumber::new(view)
+            ),
+            <StaticMembership as Membership<SequencingTestTypes>>::get_leader(
+                &fresh,
+                ViewNumber::new(view)
+            )
+        );
+    }
+}
+
+#[test]
+fn test_committee_digest() {
+    let (keys, entries) = known_nodes(10);
+    let config = StaticElectionConfig::new(10, Some(4)).unwrap();
+    let elect = |entries: Vec<_>, keys: Vec<_>, config: StaticElectionConfig| {
+        <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+            entries, keys, config,
+        )
+    };
+
+    let node_a = elect(entries.clone(), keys.clone(), config.clone());
+    let node_b = elect(entries.clone(), keys.clone(), config.clone());
+    let digest = <StaticMembership as Membership<SequencingTestTypes>>::committee_digest(&node_a);
+    assert!(
+        <StaticMembership as Membership<SequencingTestTypes>>::verify_committee_consistency(
+            &node_b, &digest
+        )
+    );
+
+    // Listing the same keys in another order elects different leaders.
+    let mut reordered_keys = keys.clone();
+    reordered_keys.reverse();
+    let mut reordered_entries = entries.clone();
+    reordered_entries.reverse();
+    let reordered = elect(reordered_entries, reordered_keys, config.clone());
+    assert!(
+        !<StaticMembership as Membership<SequencingTestTypes>>::verify_committee_consistency(
+            &reordered, &digest
+        )
+    );
+
+    // So does a committee configured for another chain.
+    let other_chain = elect(entries, keys, config.with_chain_id(1));
+    assert!(
+        !<StaticMembership as Membership<SequencingTestTypes>>::verify_committee_consistency(
+            &other_chain,
+            &digest
+        )
+    );
+}
+
+#[test]
+fn test_upcoming_leaders() {
+    let (keys, entries) = known_nodes(4);
+    let config = StaticElectionConfig::new(4, None).unwrap();
+    let membership = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        entries, keys, config,
+    );
+    let upcoming = |from, count| {
+        <StaticMembership as Membership<SequencingTestTypes>>::upcoming_leaders(
+            &membership,
+            ViewNumber::new(from),
+            count,
+        )
+    };
+
+    for count in [0, 1, 3, 4, 10] {
+        let leaders = upcoming(2, count);
+        assert!(leaders.len() <= count);
+        let distinct: HashSet<_> = leaders.iter().collect();
+        assert_eq!(distinct.len(), leaders.len());
+    }
+
+    // Leaders come in the order they first lead, and leaders who lead again are skipped.
+    let leaders = upcoming(2, 10);
+    assert_eq!(leaders.len(), 4);
+    assert_eq!(
+        leaders[0],
+        <StaticMembership as Membership<SequencingTestTypes>>::get_leader(
+            &membership,
+            ViewNumber::new(2)
+        )
+    );
+
+    // Near the largest view, only the views that exist are counted.
+    let leaders = upcoming(u64::MAX - 1, 10);
+    assert_eq!(leaders.len(), 2);
+    assert_eq!(
+        leaders[1],
+        <StaticMembership as Membership<SequencingTestTypes>>::get_leader(
+            &membership,
+            ViewNumber::new(u64::MAX)
+        )
+    );
+}
+
+#[test]
+fn test_effective_threshold_for_phase() {
+    let (keys, entries) = known_nodes(10);
+    let config = StaticElectionConfig::new(10, None).unwrap();
+    let membership = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        entries, keys, config,
+    );
+    let threshold = |phase| {
+        <StaticMembership as Membership<SequencingTestTypes>>::effective_threshold_for_phase(
+            &membership,
+            phase,
+        )
+    };
+    let success =
+        <StaticMembership as Membership<SequencingTestTypes>>::success_threshold(&membership);
+    let failure =
+        <StaticMembership as Membership<SequencingTestTypes>>::failure_threshold(&membership);
+    assert_ne!(success, failure);
+
+    assert_eq!(threshold(ViewSyncPhase::None), failure);
+    assert_eq!(threshold(ViewSyncPhase::PreCommit), failure);
+    assert_eq!(threshold(ViewSyncPhase::Commit), success);
+    assert_eq!(threshold(ViewSyncPhase::Finalize), success);
+}
@@ -0,0 +1,167 @@
+use commit::{Commitment, Committable};
+use either::Either;
+use hotshot::{
+    types::{bn254::BN254Pub, SignatureKey},
+    HotShotSequencingConsensusApi,
+};
+use hotshot_testing::{
+    node_types::{SequencingMemoryImpl, SequencingTestTypes},
+    task_helpers::build_system_handle,
+};
+use hotshot_types::{
+    consensus::ViewSyncMetrics,
+    data::ViewNumber,
+    traits::{
+        election::{ConsensusExchange, Membership, ViewSyncExchangeType, VoteData},
+        metrics::{Counter, Gauge, Histogram, Label, Metrics, NoMetrics},
+        node_implementation::ExchangesType,
+        state::ConsensusTime,
+    },
+    vote::ViewSyncData,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Metrics that only record counters, so tests can read them back by label.
+#[derive(Clone, Default)]
+struct CountingMetrics {
+    counters: Arc<Mutex<HashMap<String, Arc<AtomicUsize>>>>,
+}
+
+impl CountingMetrics {
+    fn get(&self, label: &str) -> usize {
+        self.counters.lock().unwrap()[label].load(Ordering::SeqCst)
+    }
+}
+
+struct CountingCounter(Arc<AtomicUsize>);
+
+impl Counter for CountingCounter {
+    fn add(&self, amount: usize) {
+        self.0.fetch_add(amount, Ordering::SeqCst);
+    }
+}
+
+impl Metrics for CountingMetrics {
+    fn create_counter(&self, label: String, _: Option<String>) -> Box<dyn Counter> {
+        let counter = self
+            .counters
+            .lock()
+            .unwrap()
+            .entry(label)
+            .or_default()
+            .clone();
+        Box::new(CountingCounter(counter))
+    }
+
+    fn create_gauge(&self, _: String, _: Option<String>) -> Box<dyn Gauge> {
+        Box::new(NoMetrics)
+    }
+
+    fn create_histogram(&self, _: String, _: Option<String>) -> Box<dyn Histogram> {
+        Box::new(NoMetrics)
+    }
+
+    fn create_label(&self, _: String) -> Box<dyn Label> {
+        Box::new(NoMetrics)
+    }
+
+    fn subgroup(&self, _: String) -> Box<dyn Metrics> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_view_sync_metrics() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let mut exchange = api.inner.exchanges.view_sync_exchange().clone();
+    let metrics = CountingMetrics::default();
+    exchange.set_metrics(Arc::new(ViewSyncMetrics::new(&metrics)));
+
+    let round = ViewNumber::new(3);
+    let vote_token = exchange.make_vote_token(round).unwrap().unwrap();
+    let commitment = ViewSyncData::<SequencingTestTypes> {
+        relay: exchange.get_leader(round).to_bytes(),
+        round,
+    }
+    .commit();
+
+    // Run each phase: send our own vote, then act as the relay and collect every node's vote.
+    let phases: [fn(Commitment<ViewSyncData<SequencingTestTypes>>) -> VoteData<_>; 3] = [
+        VoteData::ViewSyncPreCommit,
+        VoteData::ViewSyncCommit,
+        VoteData::ViewSyncFinalize,
+    ];
+    for (phase, vote_kind) in phases.into_iter().enumerate() {
+        let message = match phase {
+            0 => exchange.create_precommit_message::<SequencingMemoryImpl>(
+                round,
+                0,
+                vote_token.clone(),
+            ),
+            1 => {
+                exchange.create_commit_message::<SequencingMemoryImpl>(round, 0, vote_token.clone())
+            }
+            _ => exchange.create_finalize_message::<SequencingMemoryImpl>(
+                round,
+                0,
+                vote_token.clone(),
+            ),
+        };
+        assert!(message.is_some());
+
+        let mut accumulator = exchange.empty_accumulator();
+        let mut formed = false;
+        for node_id in 0..exchange.total_nodes() as u64 {
+            let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], node_id);
+            let vote_data = vote_kind(commitment);
+            let signature = BN254Pub::sign(&private_key, vote_data.commit().as_ref());
+            let token = exchange
+                .membership()
+                .make_vote_token(round, &private_key)
+                .unwrap()
+                .unwrap();
+            match exchange.accumulate_vote(
+                &key.to_bytes(),
+                &signature,
+                commitment,
+                vote_data,
+                token,
+                round,
+                accumulator,
+                Some(0),
+            ) {
+                Either::Left(acc) => accumulator = acc,
+                Either::Right(_) => {
+                    formed = true;
+                    break;
+                }
+            }
+        }
+        assert!(formed);
+    }
+
+    assert_eq!(metrics.get("precommit_votes_sent"), 1);
+    assert_eq!(metrics.get("commit_votes_sent"), 1);
+    assert_eq!(metrics.get("finalize_votes_sent"), 1);
+    assert_eq!(metrics.get("certificates_formed"), 3);
+    // Views are only recovered once a replica sees the finalize certificate.
+    assert_eq!(metrics.get("views_recovered"), 0);
+}
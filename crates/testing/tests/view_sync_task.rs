@@ -88,3 +88,354 @@ async fn test_view_sync_task() {
 
     run_harness(input, output, None, build_fn).await;
 }
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_view_sync_relay_overflow() {
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::message::GeneralConsensusMessage;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(3).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let view_sync_exchange = api.inner.exchanges.view_sync_exchange().clone();
+    let vote_token = view_sync_exchange
+        .make_vote_token(ViewNumber::new(3))
+        .unwrap()
+        .unwrap();
+
+    let near_max = ViewNumber::new(u64::MAX - 1);
+    assert_eq!(near_max.checked_add(1), Some(ViewNumber::new(u64::MAX)));
+    assert_eq!(near_max.checked_add(2), None);
+
+    // A relay that fits still produces a vote.
+    let message = view_sync_exchange.create_precommit_message::<SequencingMemoryImpl>(
+        near_max,
+        1,
+        vote_token.clone(),
+    );
+    assert!(matches!(
+        message,
+        Some(GeneralConsensusMessage::ViewSyncVote(_))
+    ));
+
+    // A relay that would wrap around is refused rather than selecting the wrong relay.
+    assert!(view_sync_exchange
+        .create_precommit_message::<SequencingMemoryImpl>(near_max, 2, vote_token.clone())
+        .is_none());
+    assert!(view_sync_exchange
+        .create_commit_message::<SequencingMemoryImpl>(near_max, 2, vote_token.clone())
+        .is_none());
+    assert!(view_sync_exchange
+        .create_finalize_message::<SequencingMemoryImpl>(near_max, 2, vote_token)
+        .is_none());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_view_sync_phase_thresholds() {
+    use commit::Commitment;
+    use either::Either;
+    use hotshot::types::bn254::BN254Pub;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        certificate::AssembledSignatureKind,
+        traits::election::{Membership, SignedCertificate, VoteData},
+        vote::ViewSyncData,
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(3).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let exchange = api.inner.exchanges.view_sync_exchange().clone();
+    let round = ViewNumber::new(3);
+    let commitment = ViewSyncData::<SequencingTestTypes> {
+        relay: exchange.get_leader(round).to_bytes(),
+        round,
+    }
+    .commit();
+
+    // Feed votes from nodes 0, 1, ... until a certificate forms, returning how many it took.
+    let votes_to_form =
+        |vote_kind: fn(Commitment<ViewSyncData<SequencingTestTypes>>) -> VoteData<_>| {
+            let mut accumulator = exchange.empty_accumulator();
+            for node_id in 0..exchange.total_nodes() as u64 {
+                let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], node_id);
+                let vote_data = vote_kind(commitment);
+                let signature = BN254Pub::sign(&private_key, vote_data.commit().as_ref());
+                let token = exchange
+                    .membership()
+                    .make_vote_token(round, &private_key)
+                    .unwrap()
+                    .unwrap();
+                match exchange.accumulate_vote(
+                    &key.to_bytes(),
+                    &signature,
+                    commitment,
+                    vote_data,
+                    token,
+                    round,
+                    accumulator,
+                    Some(0),
+                ) {
+                    Either::Left(acc) => accumulator = acc,
+                    Either::Right(certificate) => {
+                        return Some((node_id + 1, certificate.signatures().kind()));
+                    }
+                }
+            }
+            None
+        };
+
+    assert_eq!(
+        votes_to_form(VoteData::ViewSyncPreCommit),
+        Some((
+            exchange.failure_threshold().get(),
+            AssembledSignatureKind::ViewSyncPreCommit
+        ))
+    );
+    assert_eq!(
+        votes_to_form(VoteData::ViewSyncCommit),
+        Some((
+            exchange.success_threshold().get(),
+            AssembledSignatureKind::ViewSyncCommit
+        ))
+    );
+    assert_eq!(
+        votes_to_form(VoteData::ViewSyncFinalize),
+        Some((
+            exchange.success_threshold().get(),
+            AssembledSignatureKind::ViewSyncFinalize
+        ))
+    );
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_view_sync_relay_rejects_phase_regression() {
+    use either::Either;
+    use hotshot_task::event_stream::ChannelStream;
+    use hotshot_task_impls::view_sync::{ViewSyncPhase, ViewSyncRelayTaskState};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::message::GeneralConsensusMessage;
+    use std::sync::Arc;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    assert!(ViewSyncPhase::None < ViewSyncPhase::PreCommit);
+    assert!(ViewSyncPhase::PreCommit < ViewSyncPhase::Commit);
+    assert!(ViewSyncPhase::Commit < ViewSyncPhase::Finalize);
+    assert!(ViewSyncPhase::None.accepts_vote_for(&ViewSyncPhase::Commit));
+    assert!(ViewSyncPhase::PreCommit.accepts_vote_for(&ViewSyncPhase::Commit));
+    assert!(!ViewSyncPhase::PreCommit.accepts_vote_for(&ViewSyncPhase::Finalize));
+    assert!(!ViewSyncPhase::Commit.accepts_vote_for(&ViewSyncPhase::PreCommit));
+    assert!(!ViewSyncPhase::Finalize.accepts_vote_for(&ViewSyncPhase::Finalize));
+
+    // Node 3 is the first relay for round 3.
+    let handle = build_system_handle(3).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let exchange = api.inner.exchanges.view_sync_exchange().clone();
+    let round = ViewNumber::new(3);
+    let vote_token = exchange.make_vote_token(round).unwrap().unwrap();
+    let Some(GeneralConsensusMessage::ViewSyncVote(finalize_vote)) =
+        exchange.create_finalize_message::<SequencingMemoryImpl>(round, 0, vote_token)
+    else {
+        panic!("Expected a view sync vote");
+    };
+
+    let relay_state = |phase| ViewSyncRelayTaskState::<SequencingTestTypes, SequencingMemoryImpl> {
+        event_stream: ChannelStream::new(),
+        exchange: Arc::new(exchange.clone()),
+        accumulator: Either::Left(exchange.empty_accumulator()),
+        phase,
+        id: 3,
+    };
+
+    // A finalize vote before the commit certificate formed is rejected.
+    let (result, state) = relay_state(ViewSyncPhase::PreCommit)
+        .handle_event(SequencingHotShotEvent::ViewSyncVoteRecv(
+            finalize_vote.clone(),
+        ))
+        .await;
+    assert!(result.is_none());
+    assert!(state.accumulator.left().unwrap().signers.not_any());
+
+    // After the commit certificate it is accumulated.
+    let (_, state) = relay_state(ViewSyncPhase::Commit)
+        .handle_event(SequencingHotShotEvent::ViewSyncVoteRecv(finalize_vote))
+        .await;
+    assert!(state.accumulator.left().unwrap().signers.any());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_view_sync_cert_relay_out_of_range() {
+    use hotshot::types::bn254::BN254Pub;
+    use hotshot_testing::task_helpers::build_system_handle;
+    use hotshot_types::{
+        certificate::{
+            AssembledSignature, AssembledSignatureKind, ViewSyncCertificate,
+            ViewSyncCertificateInternal,
+        },
+        traits::election::{Membership, VoteData, MAX_VIEW_SYNC_RELAY},
+        vote::ViewSyncData,
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(3).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let exchange = api.inner.exchanges.view_sync_exchange().clone();
+    let round = ViewNumber::new(3);
+
+    // A precommit certificate for `relay` signed by every node.
+    let certificate_for = |relay: u64| {
+        let relay_view = ViewNumber::new(*round + relay % exchange.total_nodes() as u64);
+        let vote_data = ViewSyncData::<SequencingTestTypes> {
+            relay: exchange.get_leader(relay_view).to_bytes(),
+            round,
+        };
+        let data = VoteData::ViewSyncPreCommit(vote_data.commit())
+            .commit_for_chain(exchange.membership().chain_id());
+        let signatures = (0..exchange.total_nodes() as u64)
+            .map(|node_id| {
+                let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], node_id);
+                (key.to_bytes(), BN254Pub::sign(&private_key, data.as_ref()))
+            })
+            .collect();
+        ViewSyncCertificate::PreCommit(ViewSyncCertificateInternal {
+            relay,
+            round,
+            signatures: AssembledSignature::Individual(
+                AssembledSignatureKind::ViewSyncPreCommit,
+                signatures,
+            ),
+        })
+    };
+
+    assert!(exchange.is_valid_view_sync_cert(certificate_for(1), round));
+    assert!(exchange.is_valid_view_sync_cert(certificate_for(MAX_VIEW_SYNC_RELAY), round));
+
+    // Signatures over the right relay key don't save a certificate naming an absurd relay.
+    assert!(!exchange.is_valid_view_sync_cert(certificate_for(MAX_VIEW_SYNC_RELAY + 1), round));
+    assert!(!exchange.is_valid_view_sync_cert(certificate_for(u64::MAX), round));
+
+    // A certificate whose signatures are not view sync signatures is rejected, not waved through.
+    let unsigned = ViewSyncCertificate::PreCommit(ViewSyncCertificateInternal {
+        relay: 1,
+        round,
+        signatures: AssembledSignature::Genesis(),
+    });
+    assert!(!exchange.is_valid_view_sync_cert(unsigned, round));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_view_sync_fails_after_max_relay_attempts() {
+    use futures::{FutureExt, StreamExt};
+    use hotshot_task::{
+        event_stream::{ChannelStream, EventStream},
+        task::FilterEvent,
+    };
+    use hotshot_task_impls::view_sync::{ViewSyncPhase, ViewSyncReplicaTaskState};
+    use hotshot_testing::task_helpers::build_system_handle;
+    use std::{num::NonZeroU64, sync::Arc, time::Duration};
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(3).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let round = ViewNumber::new(3);
+    let event_stream = ChannelStream::new();
+    let (mut failed, _) = event_stream
+        .subscribe(FilterEvent(Arc::new(|event| {
+            matches!(event, SequencingHotShotEvent::ViewSyncFailed(_))
+        })))
+        .await;
+    let mut state = ViewSyncReplicaTaskState {
+        current_view: ViewNumber::new(1),
+        next_view: round,
+        relay: 0,
+        max_relay_attempts: NonZeroU64::new(3),
+        finalized: false,
+        sent_view_change_event: false,
+        phase: ViewSyncPhase::None,
+        exchange: api.inner.exchanges.view_sync_exchange().clone().into(),
+        api,
+        event_stream,
+        // Long enough that the relay timers never fire; the test delivers the timeouts.
+        view_sync_timeout: Duration::from_secs(3600),
+        id: 3,
+    };
+
+    // Timing out on the first two relays moves on to the next relay.
+    for relay in 0..2 {
+        state = state
+            .handle_event(SequencingHotShotEvent::ViewSyncTimeout(
+                round,
+                relay,
+                ViewSyncPhase::None,
+            ))
+            .await
+            .1;
+        assert_eq!(state.relay, relay + 1);
+        assert_eq!(failed.next().now_or_never().flatten(), None);
+    }
+
+    // Timing out on the third relay gives up on the round instead of trying a fourth.
+    state = state
+        .handle_event(SequencingHotShotEvent::ViewSyncTimeout(
+            round,
+            2,
+            ViewSyncPhase::None,
+        ))
+        .await
+        .1;
+    assert_eq!(state.relay, 2);
+    assert_eq!(
+        failed.next().now_or_never().flatten(),
+        Some(SequencingHotShotEvent::ViewSyncFailed(round))
+    );
+}
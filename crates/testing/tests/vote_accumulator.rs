@@ -0,0 +1,1521 @@
+use bitvec::bitvec;
+use commit::Committable;
+use either::Either;
+use hotshot::{
+    demos::sdemo::{SDemoBlock, SDemoNormalBlock},
+    traits::election::static_committee::StaticVoteToken,
+    types::{bn254::BN254Pub, SignatureKey},
+    HotShotSequencingConsensusApi,
+};
+use hotshot_testing::{
+    node_types::{SequencingMemoryImpl, SequencingTestTypes, StaticMembership},
+    task_helpers::build_system_handle,
+};
+use hotshot_types::{
+    certificate::{AssembledSignature, ContributorsError, DACertificate},
+    data::ViewNumber,
+    message::Message,
+    traits::{
+        election::{ConsensusExchange, Membership, SignedCertificate, VoteData},
+        node_implementation::{CommitteeEx, ExchangesType},
+        signature_key::{EncodedPublicKey, EncodedSignature},
+        state::ConsensusTime,
+    },
+    vote::{Accumulator, VoteAccumulator, VoteType},
+};
+use std::num::NonZeroU64;
+
+type DAAccumulator = VoteAccumulator<StaticVoteToken<BN254Pub>, SDemoBlock>;
+
+type DAVoteInput = (
+    commit::Commitment<SDemoBlock>,
+    (
+        EncodedPublicKey,
+        (
+            EncodedSignature,
+            Vec<<BN254Pub as SignatureKey>::StakeTableEntry>,
+            usize,
+            VoteData<SDemoBlock>,
+            StaticVoteToken<BN254Pub>,
+        ),
+    ),
+);
+
+fn empty_accumulator(total_nodes: usize, success_threshold: u64) -> DAAccumulator {
+    VoteAccumulator::new(
+        NonZeroU64::new(success_threshold).unwrap(),
+        NonZeroU64::new(2).unwrap(),
+        total_nodes,
+    )
+}
+
+/// Build the stake table and a signed vote of kind `vote_kind` on `block` from each of
+/// `total_nodes` nodes.
+fn signed_votes(
+    total_nodes: u64,
+    block: &SDemoBlock,
+    vote_kind: fn(commit::Commitment<SDemoBlock>) -> VoteData<SDemoBlock>,
+) -> (
+    Vec<<BN254Pub as SignatureKey>::StakeTableEntry>,
+    Vec<DAVoteInput>,
+) {
+    let private_keys: Vec<_> = (0..total_nodes)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id).1)
+        .collect();
+    let keys: Vec<_> = private_keys.iter().map(BN254Pub::from_private).collect();
+    let entries: Vec<_> = keys
+        .iter()
+        .map(|key| key.get_stake_table_entry(1u64))
+        .collect();
+    let membership = <StaticMembership as Membership<SequencingTestTypes>>::create_election(
+        entries.clone(),
+        keys.clone(),
+        <StaticMembership as Membership<SequencingTestTypes>>::default_election_config(total_nodes),
+    );
+
+    let commitment = block.commit();
+    let vote_data = vote_kind(commitment);
+    let votes = private_keys
+        .iter()
+        .zip(keys.iter())
+        .enumerate()
+        .map(|(node_id, (private_key, key))| {
+            let signature = BN254Pub::sign(private_key, vote_data.commit().as_ref());
+            let token = membership
+                .make_vote_token(ViewNumber::new(1), private_key)
+                .unwrap()
+                .unwrap();
+            (
+                commitment,
+                (
+                    key.to_bytes(),
+                    (
+                        signature,
+                        entries.clone(),
+                        node_id,
+                        vote_data.clone(),
+                        token,
+                    ),
+                ),
+            )
+        })
+        .collect();
+    (entries, votes)
+}
+
+fn append(
+    accumulator: DAAccumulator,
+    vote: DAVoteInput,
+) -> Either<DAAccumulator, AssembledSignature<SequencingTestTypes>> {
+    <DAAccumulator as Accumulator<_, AssembledSignature<SequencingTestTypes>>>::append(
+        accumulator,
+        vote,
+    )
+}
+
+#[test]
+fn test_try_into_certificate() {
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let commitment = block.commit();
+    let (entries, votes) = signed_votes(4, &block, VoteData::DA);
+    let mut votes = votes.into_iter();
+
+    // Accumulate to one below the threshold of 3.
+    let mut accumulator = empty_accumulator(4, 3);
+    for _ in 0..2 {
+        accumulator = append(accumulator, votes.next().unwrap()).left().unwrap();
+    }
+
+    // Checkpoint and restore the accumulator; it cannot complete yet.
+    let checkpoint = accumulator.clone();
+    let restored = checkpoint
+        .try_into_certificate::<SequencingTestTypes>(commitment, entries.clone())
+        .unwrap_err();
+
+    // One more vote on the restored accumulator completes the certificate.
+    assert!(matches!(
+        append(restored, votes.next().unwrap()),
+        Either::Right(AssembledSignature::DA(_))
+    ));
+
+    // The same votes restored under a lower threshold complete without another append.
+    accumulator.success_threshold = NonZeroU64::new(2).unwrap();
+    let signature = accumulator
+        .try_into_certificate::<SequencingTestTypes>(commitment, entries)
+        .unwrap_or_else(|_| panic!("Accumulator should have reached the threshold"));
+    assert!(matches!(signature, AssembledSignature::DA(_)));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_upgrade_certificate() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let total_nodes = committee_exchange.total_nodes() as u64;
+    let threshold = committee_exchange.success_threshold().get();
+
+    // The upgrade proposal being voted on.
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let commitment = block.commit();
+    let (_, votes) = signed_votes(total_nodes, &block, VoteData::Upgrade);
+
+    let mut accumulator = empty_accumulator(total_nodes as usize, threshold);
+    let mut signature = None;
+    for vote in votes {
+        match append(accumulator, vote) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(assembled) => {
+                signature = Some(assembled);
+                break;
+            }
+        }
+    }
+    let Some(AssembledSignature::Upgrade(qc)) = signature else {
+        panic!("Upgrade votes should assemble into an upgrade certificate");
+    };
+
+    let certificate = DACertificate::from_signatures_and_commitment(
+        ViewNumber::new(1),
+        AssembledSignature::Upgrade(qc.clone()),
+        commitment,
+        None,
+    );
+    assert!(committee_exchange.is_valid_upgrade_cert(&certificate, commitment));
+    // It does not pass as the exchange's own kind of certificate.
+    assert!(!committee_exchange.is_valid_cert(&certificate, commitment));
+
+    // The upgrade signatures are in their own domain and do not pass as a DA certificate.
+    let certificate = DACertificate::from_signatures_and_commitment(
+        ViewNumber::new(1),
+        AssembledSignature::DA(qc),
+        commitment,
+        None,
+    );
+    assert!(!committee_exchange.is_valid_cert(&certificate, commitment));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_yes_signatures_in_da_cert_rejected() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let total_nodes = committee_exchange.total_nodes() as u64;
+    let threshold = committee_exchange.success_threshold().get();
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let commitment = block.commit();
+    let (_, votes) = signed_votes(total_nodes, &block, VoteData::Yes);
+
+    let mut accumulator = empty_accumulator(total_nodes as usize, threshold);
+    let mut signature = None;
+    for vote in votes {
+        match append(accumulator, vote) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(assembled) => {
+                signature = Some(assembled);
+                break;
+            }
+        }
+    }
+    let Some(signature @ AssembledSignature::Yes(_)) = signature else {
+        panic!("Yes votes should assemble into a yes certificate");
+    };
+
+    // The signatures are valid over their own vote data, but the DA exchange only accepts DA
+    // signatures.
+    let certificate = DACertificate::from_signatures_and_commitment(
+        ViewNumber::new(1),
+        signature,
+        commitment,
+        None,
+    );
+    assert!(!committee_exchange.is_valid_cert(&certificate, commitment));
+}
+
+#[test]
+fn test_da_certificate_contributors() {
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let commitment = block.commit();
+    let (_, votes) = signed_votes(4, &block, VoteData::DA);
+    let stake_table: Vec<_> = (0..4)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id).0)
+        .collect();
+
+    // Only nodes 0, 2 and 3 vote, which meets the threshold of 3.
+    let subset = [0, 2, 3];
+    let mut accumulator = empty_accumulator(4, 3);
+    let mut signature = None;
+    for (node_id, vote) in votes.into_iter().enumerate() {
+        if !subset.contains(&node_id) {
+            continue;
+        }
+        match append(accumulator, vote) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(assembled) => {
+                signature = Some(assembled);
+                break;
+            }
+        }
+    }
+    let Some(signatures) = signature else {
+        panic!("DA votes from the subset should assemble into a certificate");
+    };
+
+    let certificate = DACertificate::<SequencingTestTypes> {
+        view_number: ViewNumber::new(1),
+        block_commitment: commitment,
+        signatures,
+    };
+    let expected: Vec<_> = subset
+        .iter()
+        .map(|&node_id| stake_table[node_id].to_bytes())
+        .collect();
+    assert_eq!(certificate.contributors(&stake_table), Ok(expected));
+
+    // The bitmap has to be read against the table it was built from.
+    assert_eq!(
+        certificate.contributors(&stake_table[..3]),
+        Err(ContributorsError::StakeTableMismatch {
+            signers: 4,
+            stake_table: 3,
+        })
+    );
+
+    // Only DA certificates carry a bitmap of DA committee members.
+    let AssembledSignature::DA(qc) = certificate.signatures.clone() else {
+        unreachable!();
+    };
+    let certificate = DACertificate::<SequencingTestTypes> {
+        signatures: AssembledSignature::Yes(qc),
+        ..certificate
+    };
+    assert_eq!(
+        certificate.contributors(&stake_table),
+        Err(ContributorsError::NotDASignature)
+    );
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_vote_from_key_outside_stake_table() {
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange();
+    let total_nodes = committee_exchange.total_nodes() as u64;
+
+    // The last node is on the committee, but its stake table entry carries a different stake, so
+    // the entry its key maps to is not in the committee stake table.
+    let keys: Vec<_> = (0..total_nodes)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id))
+        .collect();
+    let entries: Vec<_> = keys
+        .iter()
+        .enumerate()
+        .map(|(id, (key, _))| {
+            let stake = if id as u64 == total_nodes - 1 { 2 } else { 1 };
+            key.get_stake_table_entry(stake)
+        })
+        .collect();
+    let (public_key, private_key) = keys[0].clone();
+    let entry = public_key.get_stake_table_entry(1u64);
+    let exchange = <CommitteeEx<SequencingTestTypes, SequencingMemoryImpl> as ConsensusExchange<
+        SequencingTestTypes,
+        Message<SequencingTestTypes, SequencingMemoryImpl>,
+    >>::create(
+        entries,
+        keys.iter().map(|(key, _)| key.clone()).collect(),
+        <StaticMembership as Membership<SequencingTestTypes>>::default_election_config(total_nodes),
+        committee_exchange.network().clone(),
+        public_key,
+        entry,
+        private_key,
+    );
+
+    let view = ViewNumber::new(1);
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let commitment = block.commit();
+    let (key, private_key) = &keys[total_nodes as usize - 1];
+    let vote_data = VoteData::DA(commitment);
+    let signature = BN254Pub::sign(private_key, vote_data.commit().as_ref());
+    let token = exchange
+        .membership()
+        .make_vote_token(view, private_key)
+        .unwrap()
+        .unwrap();
+
+    let accumulator = empty_accumulator(total_nodes as usize, 1);
+    let Either::Left(accumulator) = exchange.accumulate_vote(
+        &key.to_bytes(),
+        &signature,
+        commitment,
+        vote_data,
+        token,
+        view,
+        accumulator,
+        None,
+    ) else {
+        panic!("A vote from outside the stake table should not form a certificate");
+    };
+    assert!(accumulator.total_vote_outcomes.is_empty());
+    assert!(accumulator.sig_lists.is_empty());
+    assert!(accumulator.signers.not_any());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_zero_weight_token_rejected() {
+    use bincode::Options;
+    use hotshot_types::traits::election::VoteToken;
+    use hotshot_utils::bincode::bincode_opts;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange = api.inner.exchanges.committee_exchange();
+    let total_nodes = committee_exchange.total_nodes();
+
+    let view = ViewNumber::new(1);
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let commitment = block.commit();
+    let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], 1);
+    let vote_data = VoteData::DA(commitment);
+    let signature = BN254Pub::sign(&private_key, vote_data.commit().as_ref());
+
+    // A committee member's token with its proof stripped, as a peer could put on the wire.
+    let bytes = bincode_opts()
+        .serialize(&(EncodedSignature(Vec::new()), key.clone()))
+        .unwrap();
+    let token: StaticVoteToken<BN254Pub> = bincode_opts().deserialize(&bytes).unwrap();
+    assert!(token.is_zero_weight());
+
+    let accumulator = empty_accumulator(total_nodes, 1);
+    let Either::Left(accumulator) = committee_exchange.accumulate_vote(
+        &key.to_bytes(),
+        &signature,
+        commitment,
+        vote_data,
+        token,
+        view,
+        accumulator,
+        None,
+    ) else {
+        panic!("A zero-weight token should not form a certificate");
+    };
+    assert!(accumulator.total_vote_outcomes.is_empty());
+    assert!(accumulator.sig_lists.is_empty());
+    assert!(accumulator.signers.not_any());
+
+    // A token actually issued to the same key carries weight.
+    let token = committee_exchange
+        .membership()
+        .make_vote_token(view, &private_key)
+        .unwrap()
+        .unwrap();
+    assert!(!token.is_zero_weight());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_signature_cache_skips_repeated_checks() {
+    use hotshot_types::{
+        message::CommitteeConsensusMessage,
+        traits::election::{Checked, CommitteeExchangeType},
+        verification::SignatureCache,
+    };
+    use std::num::NonZeroUsize;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let mut committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let cache = SignatureCache::new(NonZeroUsize::new(16).unwrap());
+    committee_exchange.set_signature_cache(Some(cache.clone()));
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let view = ViewNumber::new(2);
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let CommitteeConsensusMessage::DAVote(vote) =
+        committee_exchange.create_da_message(block.commit(), view, vote_token)
+    else {
+        panic!("Expected a DA vote");
+    };
+    let is_valid = |signature: &EncodedSignature| {
+        committee_exchange.is_valid_vote(
+            &vote.signature.0,
+            signature,
+            vote.vote_data.clone(),
+            Checked::Unchecked(vote.vote_token.clone()),
+        )
+    };
+
+    assert!(is_valid(&vote.signature.1));
+    assert_eq!(cache.hits(), 0);
+    assert!(is_valid(&vote.signature.1));
+    assert_eq!(cache.hits(), 1);
+
+    // Another signature by the same key over the same data is checked, not served from the cache.
+    let (_, other_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], 3);
+    let forged = BN254Pub::sign(&other_key, vote.vote_data.commit().as_ref());
+    assert!(!is_valid(&forged));
+    assert_eq!(cache.hits(), 1);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_signature_cache_across_committee_change() {
+    use hotshot_types::{
+        message::CommitteeConsensusMessage,
+        traits::election::{Checked, CommitteeExchangeType},
+        verification::SignatureCache,
+    };
+    use std::num::NonZeroUsize;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(2).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let mut committee_exchange = api.inner.exchanges.committee_exchange().clone();
+    let cache = SignatureCache::new(NonZeroUsize::new(16).unwrap());
+    committee_exchange.set_signature_cache(Some(cache.clone()));
+
+    // The committee rotates to nodes 3 through 12, dropping node 2, and keeps the cache.
+    let total_nodes = committee_exchange.total_nodes() as u64;
+    let keys: Vec<_> = (3..3 + total_nodes)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id))
+        .collect();
+    let (public_key, private_key) = keys[0].clone();
+    let mut rotated =
+        <CommitteeEx<SequencingTestTypes, SequencingMemoryImpl> as ConsensusExchange<
+            SequencingTestTypes,
+            Message<SequencingTestTypes, SequencingMemoryImpl>,
+        >>::create(
+            keys.iter()
+                .map(|(key, _)| key.get_stake_table_entry(1u64))
+                .collect(),
+            keys.iter().map(|(key, _)| *key).collect(),
+            <StaticMembership as Membership<SequencingTestTypes>>::default_election_config(
+                total_nodes,
+            ),
+            committee_exchange.network().clone(),
+            public_key,
+            public_key.get_stake_table_entry(1u64),
+            private_key,
+        );
+    rotated.set_signature_cache(Some(cache.clone()));
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let view = ViewNumber::new(2);
+    let vote_token = committee_exchange.make_vote_token(view).unwrap().unwrap();
+    let CommitteeConsensusMessage::DAVote(vote) =
+        committee_exchange.create_da_message(block.commit(), view, vote_token)
+    else {
+        panic!("Expected a DA vote");
+    };
+    let is_valid = |exchange: &CommitteeEx<SequencingTestTypes, SequencingMemoryImpl>| {
+        exchange.is_valid_vote(
+            &vote.signature.0,
+            &vote.signature.1,
+            vote.vote_data.clone(),
+            Checked::Unchecked(vote.vote_token.clone()),
+        )
+    };
+
+    assert!(is_valid(&committee_exchange));
+    assert_eq!(cache.hits(), 0);
+
+    // The signature check is served from the cache, but the signer has left the committee.
+    assert!(!is_valid(&rotated));
+    assert_eq!(cache.hits(), 1);
+
+    // Once cleared, the next validation checks the signature again.
+    cache.clear();
+    assert!(is_valid(&committee_exchange));
+    assert_eq!(cache.hits(), 1);
+}
+
+#[cfg(test)]
+#[test]
+fn test_assembled_signature_kind() {
+    use bincode::Options;
+    use ethereum_types::U256;
+    use hotshot_types::certificate::AssembledSignatureKind;
+    use hotshot_utils::bincode::bincode_opts;
+
+    let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], 0);
+    let real_qc_pp =
+        BN254Pub::get_public_parameter(vec![key.get_stake_table_entry(1u64)], U256::from(1));
+    let signature = BN254Pub::sign(&private_key, b"assembled signature kind");
+    let qc = BN254Pub::assemble(
+        &real_qc_pp,
+        &bitvec![1; 1],
+        &[bincode_opts().deserialize(&signature.0).unwrap()],
+    );
+
+    let cases: [(
+        AssembledSignature<SequencingTestTypes>,
+        AssembledSignatureKind,
+    ); 9] = [
+        (
+            AssembledSignature::Yes(qc.clone()),
+            AssembledSignatureKind::Yes,
+        ),
+        (
+            AssembledSignature::No(qc.clone()),
+            AssembledSignatureKind::No,
+        ),
+        (
+            AssembledSignature::DA(qc.clone()),
+            AssembledSignatureKind::DA,
+        ),
+        (
+            AssembledSignature::Genesis(),
+            AssembledSignatureKind::Genesis,
+        ),
+        (
+            AssembledSignature::ViewSyncPreCommit(qc.clone()),
+            AssembledSignatureKind::ViewSyncPreCommit,
+        ),
+        (
+            AssembledSignature::ViewSyncCommit(qc.clone()),
+            AssembledSignatureKind::ViewSyncCommit,
+        ),
+        (
+            AssembledSignature::ViewSyncFinalize(qc.clone()),
+            AssembledSignatureKind::ViewSyncFinalize,
+        ),
+        (
+            AssembledSignature::Upgrade(qc.clone()),
+            AssembledSignatureKind::Upgrade,
+        ),
+        (
+            AssembledSignature::Timeout(qc),
+            AssembledSignatureKind::Timeout,
+        ),
+    ];
+    for (signatures, kind) in cases {
+        assert_eq!(signatures.kind(), kind);
+        let is_genesis = kind == AssembledSignatureKind::Genesis;
+        assert_eq!(signatures.is_genesis(), is_genesis);
+        assert_eq!(signatures.into_qc().is_none(), is_genesis);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_assembled_signature_eq_ignores_contributor_order() {
+    use bincode::Options;
+    use ethereum_types::U256;
+    use hotshot_types::certificate::AssembledSignatureKind;
+    use hotshot_utils::bincode::bincode_opts;
+    use std::collections::HashSet;
+
+    let data = b"assembled signature order";
+    let keys: Vec<_> = (0..3)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id))
+        .collect();
+    let entries = keys
+        .iter()
+        .map(|(key, _)| key.get_stake_table_entry(1u64))
+        .collect();
+    let real_qc_pp = BN254Pub::get_public_parameter(entries, U256::from(3));
+    let signatures: Vec<_> = keys
+        .iter()
+        .map(|(key, private_key)| (key.to_bytes(), BN254Pub::sign(private_key, data)))
+        .collect();
+    let assemble = |order: [usize; 3]| {
+        let sigs: Vec<_> = order
+            .iter()
+            .map(|&i| bincode_opts().deserialize(&signatures[i].1 .0).unwrap())
+            .collect();
+        AssembledSignature::<SequencingTestTypes>::Yes(BN254Pub::assemble(
+            &real_qc_pp,
+            &bitvec![1; 3],
+            &sigs,
+        ))
+    };
+    let individual = |kind, order: [usize; 3]| {
+        AssembledSignature::<SequencingTestTypes>::Individual(
+            kind,
+            order.iter().map(|&i| signatures[i].clone()).collect(),
+        )
+    };
+
+    assert_eq!(assemble([0, 1, 2]), assemble([2, 0, 1]));
+    let forward = individual(AssembledSignatureKind::DA, [0, 1, 2]);
+    let shuffled = individual(AssembledSignatureKind::DA, [1, 2, 0]);
+    assert_eq!(forward, shuffled);
+    let distinct: HashSet<_> = [forward.clone(), shuffled].into_iter().collect();
+    assert_eq!(distinct.len(), 1);
+
+    // The kind and the signers still matter.
+    assert_ne!(forward, individual(AssembledSignatureKind::Yes, [0, 1, 2]));
+    assert_ne!(
+        forward,
+        AssembledSignature::Individual(AssembledSignatureKind::DA, signatures[..2].to_vec())
+    );
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_reconstruct_qc_from_parts() {
+    use ethereum_types::U256;
+    use hotshot_types::{
+        certificate::{reconstruct_and_verify, VerifyError},
+        data::{fake_commitment, SequencingLeaf},
+        traits::election::{QuorumExchangeType, DEFAULT_CHAIN_ID},
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let quorum_exchange = api.inner.exchanges.quorum_exchange().clone();
+    let threshold = quorum_exchange.success_threshold();
+    let view = ViewNumber::new(3);
+    let leaf_commitment = fake_commitment::<SequencingLeaf<SequencingTestTypes>>();
+    let stake_table: Vec<_> = (0..quorum_exchange.total_nodes() as u64)
+        .map(|id| (BN254Pub::generated_from_seed_indexed([0u8; 32], id).0, 1))
+        .collect();
+
+    // Node 0's vote comes from its exchange; the rest sign the same vote data with their keys.
+    let mut parts = vec![quorum_exchange.sign_yes_vote(leaf_commitment)];
+    for id in 1..threshold.get() {
+        let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], id);
+        let data = VoteData::Yes(leaf_commitment).commit();
+        parts.push((key.to_bytes(), BN254Pub::sign(&private_key, data.as_ref())));
+    }
+
+    let qc = reconstruct_and_verify(
+        view,
+        leaf_commitment,
+        &parts,
+        &stake_table,
+        threshold,
+        DEFAULT_CHAIN_ID,
+    )
+    .expect("votes from a quorum should reconstruct into a valid QC");
+    assert_eq!(qc.view_number, view);
+    assert!(quorum_exchange.is_valid_cert(&qc, leaf_commitment));
+
+    // One vote short of the threshold is not a QC.
+    assert_eq!(
+        reconstruct_and_verify::<SequencingTestTypes, SequencingLeaf<SequencingTestTypes>>(
+            view,
+            leaf_commitment,
+            &parts[1..],
+            &stake_table,
+            threshold,
+            DEFAULT_CHAIN_ID,
+        ),
+        Err(VerifyError::InsufficientStake {
+            stake: U256::from(threshold.get() - 1),
+            threshold: threshold.get(),
+        })
+    );
+
+    // A signer cannot be counted twice towards the threshold.
+    let mut duplicated = parts[1..].to_vec();
+    duplicated.push(parts[1].clone());
+    assert_eq!(
+        reconstruct_and_verify::<SequencingTestTypes, SequencingLeaf<SequencingTestTypes>>(
+            view,
+            leaf_commitment,
+            &duplicated,
+            &stake_table,
+            threshold,
+            DEFAULT_CHAIN_ID,
+        ),
+        Err(VerifyError::DuplicateSigner {
+            key: parts[1].0.clone(),
+        })
+    );
+
+    // Stakes that together exceed `u64::MAX` are tallied without overflowing.
+    let heavy_stake_table: Vec<_> = stake_table
+        .iter()
+        .map(|(key, _)| (*key, u64::MAX))
+        .collect();
+    let qc = reconstruct_and_verify(
+        view,
+        leaf_commitment,
+        &parts,
+        &heavy_stake_table,
+        NonZeroU64::new(u64::MAX).unwrap(),
+        DEFAULT_CHAIN_ID,
+    )
+    .expect("signers holding more than the threshold should reconstruct into a valid QC");
+    assert_eq!(qc.view_number, view);
+}
+
+#[cfg(test)]
+#[test]
+fn test_certificate_short_debug() {
+    use hotshot_types::{
+        certificate::QuorumCertificate,
+        data::{fake_commitment, SequencingLeaf},
+    };
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let (_, votes) = signed_votes(4, &block, VoteData::DA);
+    let mut accumulator = empty_accumulator(4, 3);
+    let mut signature = None;
+    for vote in votes {
+        match append(accumulator, vote) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(assembled) => {
+                signature = Some(assembled);
+                break;
+            }
+        }
+    }
+    let Some(AssembledSignature::DA(qc)) = signature else {
+        panic!("DA votes should assemble into a DA certificate");
+    };
+    let signature_len = AssembledSignature::<SequencingTestTypes>::DA(qc.clone()).serialized_len();
+    assert!(signature_len > 0);
+
+    let dac = DACertificate::<SequencingTestTypes> {
+        view_number: ViewNumber::new(1),
+        block_commitment: block.commit(),
+        signatures: AssembledSignature::DA(qc.clone()),
+    };
+    let quorum_certificate =
+        QuorumCertificate::<SequencingTestTypes, SequencingLeaf<SequencingTestTypes>> {
+            leaf_commitment: fake_commitment(),
+            view_number: ViewNumber::new(1),
+            signatures: AssembledSignature::Yes(qc),
+            is_genesis: false,
+        };
+
+    for short in [dac.short_debug(), quorum_certificate.short_debug()] {
+        assert!(short.len() <= 80, "{short} is too long");
+        assert!(short.contains("view: 1"));
+        assert!(short.contains(&format!("signatures: {signature_len} bytes")));
+    }
+
+    // The full debug output leaves the signatures out too.
+    assert!(!format!("{dac:?}").contains("signatures"));
+    assert!(!format!("{quorum_certificate:?}").contains("signatures"));
+}
+
+#[cfg(test)]
+#[test]
+fn test_view_sync_certificate_without_relay() {
+    use hotshot_types::{
+        certificate::ViewSyncCertificate, data::fake_commitment, vote::ViewSyncData,
+    };
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let (_, votes) = signed_votes(4, &block, VoteData::DA);
+    let mut accumulator = empty_accumulator(4, 3);
+    let mut signature = None;
+    for vote in votes {
+        match append(accumulator, vote) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(assembled) => {
+                signature = Some(assembled);
+                break;
+            }
+        }
+    }
+    let Some(AssembledSignature::DA(qc)) = signature else {
+        panic!("DA votes should assemble into a DA certificate");
+    };
+
+    // A generic caller passes no relay, as it would for a QC or DA certificate.
+    let certificate = ViewSyncCertificate::<SequencingTestTypes>::from_signatures_and_commitment(
+        ViewNumber::new(5),
+        AssembledSignature::ViewSyncPreCommit(qc),
+        fake_commitment::<ViewSyncData<SequencingTestTypes>>(),
+        None,
+    );
+    let ViewSyncCertificate::PreCommit(internal) = certificate else {
+        panic!("Expected a precommit certificate");
+    };
+    assert_eq!(internal.round, ViewNumber::new(5));
+    assert_eq!(internal.relay, 0);
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_vote_rejected_on_other_chain() {
+    use hotshot::traits::election::static_committee::StaticElectionConfig;
+    use hotshot_types::{
+        data::{fake_commitment, SequencingLeaf},
+        traits::{
+            election::{Checked, QuorumExchangeType},
+            node_implementation::SequencingQuorumEx,
+        },
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let chain_a = api.inner.exchanges.quorum_exchange().clone();
+    let num_nodes = chain_a.total_nodes() as u64;
+    let keys: Vec<BN254Pub> = (0..num_nodes)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id).0)
+        .collect();
+    let entries = keys
+        .iter()
+        .map(|key| key.get_stake_table_entry(1u64))
+        .collect();
+    let (public_key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], 0);
+    // The same node, with the same keys, on a second chain.
+    let chain_b = SequencingQuorumEx::<SequencingTestTypes, SequencingMemoryImpl>::create(
+        entries,
+        keys,
+        StaticElectionConfig::new(num_nodes, None)
+            .unwrap()
+            .with_chain_id(1),
+        chain_a.network().clone(),
+        public_key,
+        public_key.get_stake_table_entry(1u64),
+        private_key,
+    );
+    assert_ne!(
+        chain_a.membership().chain_id(),
+        chain_b.membership().chain_id()
+    );
+
+    let view = ViewNumber::new(1);
+    let leaf_commitment = fake_commitment::<SequencingLeaf<SequencingTestTypes>>();
+    let vote_token = chain_a.make_vote_token(view).unwrap().unwrap();
+    let (key, signature) = chain_a.sign_yes_vote(leaf_commitment);
+
+    assert!(chain_a.is_valid_vote(
+        &key,
+        &signature,
+        VoteData::Yes(leaf_commitment),
+        Checked::Unchecked(vote_token.clone()),
+    ));
+    assert!(!chain_b.is_valid_vote(
+        &key,
+        &signature,
+        VoteData::Yes(leaf_commitment),
+        Checked::Unchecked(vote_token),
+    ));
+}
+
+#[test]
+fn test_remaining_stake_needed() {
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let (_, votes) = signed_votes(7, &block, VoteData::DA);
+    let threshold = NonZeroU64::new(5).unwrap();
+
+    let mut accumulator = empty_accumulator(7, 5);
+    assert_eq!(accumulator.remaining_stake_needed(threshold), 5.into());
+    for vote in votes.into_iter().take(3) {
+        accumulator = append(accumulator, vote).left().unwrap();
+    }
+    assert_eq!(accumulator.current_stake(), 3.into());
+    assert_eq!(accumulator.remaining_stake_needed(threshold), 2.into());
+    // Stake beyond a lower threshold does not go negative.
+    assert_eq!(
+        accumulator.remaining_stake_needed(NonZeroU64::new(2).unwrap()),
+        0.into()
+    );
+}
+
+/// A token weighted by an exact stake, which may exceed `u64::MAX`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+struct StakeToken(ethereum_types::U256);
+
+impl Committable for StakeToken {
+    fn commit(&self) -> commit::Commitment<Self> {
+        let mut stake = [0u8; 32];
+        self.0.to_little_endian(&mut stake);
+        commit::RawCommitmentBuilder::new("Stake Token")
+            .fixed_size_bytes(&stake)
+            .finalize()
+    }
+}
+
+impl hotshot_types::traits::election::VoteToken for StakeToken {
+    fn vote_count(&self) -> NonZeroU64 {
+        NonZeroU64::new(self.0.low_u64().max(1)).unwrap()
+    }
+
+    fn vote_weight(&self) -> ethereum_types::U256 {
+        self.0
+    }
+}
+
+#[test]
+fn test_vote_weight_beyond_u64() {
+    use ethereum_types::U256;
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let (_, votes) = signed_votes(4, &block, VoteData::DA);
+    let half = U256::from(1u64) << 63;
+    let mut votes = votes.into_iter().map(
+        |(commitment, (key, (sig, entries, node_id, vote_data, _)))| {
+            (
+                commitment,
+                (key, (sig, entries, node_id, vote_data, StakeToken(half))),
+            )
+        },
+    );
+    let append = |accumulator: VoteAccumulator<StakeToken, SDemoBlock>, vote| {
+        <VoteAccumulator<StakeToken, SDemoBlock> as Accumulator<
+            _,
+            AssembledSignature<SequencingTestTypes>,
+        >>::append(accumulator, vote)
+    };
+
+    let threshold = NonZeroU64::new(u64::MAX).unwrap();
+    let accumulator = VoteAccumulator::new(threshold, threshold, 4);
+
+    // 2^63 of stake is just over half of the threshold.
+    let accumulator = append(accumulator, votes.next().unwrap()).left().unwrap();
+    assert_eq!(accumulator.current_stake(), half);
+    assert_eq!(
+        accumulator.remaining_stake_needed(threshold),
+        U256::from(u64::MAX) - half
+    );
+
+    // The second vote brings the total to 2^64, which a `u64` tally would wrap to 0.
+    assert!(matches!(
+        append(accumulator, votes.next().unwrap()),
+        Either::Right(AssembledSignature::DA(_))
+    ));
+}
+
+#[test]
+fn test_equivocation_evidence() {
+    use bincode::Options;
+    use hotshot_types::{traits::election::DEFAULT_CHAIN_ID, vote::EquivocationEvidence};
+    use hotshot_utils::bincode::bincode_opts;
+
+    let block_of = |id| {
+        SDemoBlock::Normal(SDemoNormalBlock {
+            previous_state: (),
+            transactions: vec![hotshot::demos::sdemo::SDemoTransaction {
+                id,
+                padding: Vec::new(),
+            }],
+        })
+    };
+    let (_, votes_a) = signed_votes(4, &block_of(0), VoteData::DA);
+    let (_, votes_b) = signed_votes(4, &block_of(1), VoteData::DA);
+
+    // Node 0 votes for both blocks.
+    let accumulator = empty_accumulator(4, 3);
+    let accumulator = append(accumulator, votes_a[0].clone()).left().unwrap();
+    let accumulator = append(accumulator, votes_b[0].clone()).left().unwrap();
+    assert_eq!(accumulator.equivocations.len(), 1);
+
+    // The evidence verifies on its own, including after a round trip through the wire format.
+    let evidence = &accumulator.equivocations[0];
+    assert_eq!(evidence.key, votes_a[0].1 .0);
+    let bytes = bincode_opts().serialize(evidence).unwrap();
+    let evidence: EquivocationEvidence<SDemoBlock> = bincode_opts().deserialize(&bytes).unwrap();
+    assert!(evidence.verify::<BN254Pub>(DEFAULT_CHAIN_ID));
+
+    // Forged evidence does not.
+    let forged = EquivocationEvidence {
+        sig_b: evidence.sig_a.clone(),
+        ..evidence.clone()
+    };
+    assert!(!forged.verify::<BN254Pub>(DEFAULT_CHAIN_ID));
+    let same_vote = EquivocationEvidence {
+        vote_b: evidence.vote_a.clone(),
+        sig_b: evidence.sig_a.clone(),
+        ..evidence
+    };
+    assert!(!same_vote.verify::<BN254Pub>(DEFAULT_CHAIN_ID));
+}
+
+/// Feed `votes` to `exchange` through [`ConsensusExchange::accumulate`], returning how many it
+/// took to form a certificate.
+fn votes_to_certificate<EXCHANGE, VOTE>(exchange: &EXCHANGE, votes: &[VOTE]) -> Option<usize>
+where
+    EXCHANGE:
+        ConsensusExchange<SequencingTestTypes, Message<SequencingTestTypes, SequencingMemoryImpl>>,
+    VOTE: VoteType<SequencingTestTypes, Commitment = EXCHANGE::Commitment>,
+{
+    let mut accumulator = exchange.empty_accumulator();
+    for (count, vote) in votes.iter().enumerate() {
+        match exchange.accumulate(vote, accumulator) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(_) => return Some(count + 1),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_accumulate_each_vote_kind() {
+    use hotshot_types::{
+        certificate::QuorumCertificate,
+        data::{fake_commitment, SequencingLeaf},
+        traits::node_implementation::{QuorumEx, ViewSyncEx},
+        vote::{
+            DAVote, QuorumVote, TimeoutVote, ViewSyncData, ViewSyncVote, ViewSyncVoteInternal,
+            YesOrNoVote,
+        },
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let committee_exchange: &CommitteeEx<SequencingTestTypes, SequencingMemoryImpl> =
+        api.inner.exchanges.committee_exchange();
+    let quorum_exchange: &QuorumEx<SequencingTestTypes, SequencingMemoryImpl> =
+        api.inner.exchanges.quorum_exchange();
+    let view_sync_exchange: &ViewSyncEx<SequencingTestTypes, SequencingMemoryImpl> =
+        api.inner.exchanges.view_sync_exchange();
+    let view = ViewNumber::new(1);
+    let keys: Vec<_> = (0..committee_exchange.total_nodes() as u64)
+        .map(|id| BN254Pub::generated_from_seed_indexed([0u8; 32], id))
+        .collect();
+    let sign = |private_key: &<BN254Pub as SignatureKey>::PrivateKey, data: &[u8]| {
+        (
+            BN254Pub::sign(private_key, data),
+            committee_exchange
+                .membership()
+                .make_vote_token(view, private_key)
+                .unwrap()
+                .unwrap(),
+        )
+    };
+
+    let block_commitment = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    })
+    .commit();
+    let da_votes: Vec<_> = keys
+        .iter()
+        .map(|(key, private_key)| {
+            let vote_data = VoteData::DA(block_commitment);
+            let (signature, vote_token) = sign(private_key, vote_data.commit().as_ref());
+            DAVote {
+                signature: (key.to_bytes(), signature),
+                block_commitment,
+                current_view: view,
+                vote_token,
+                vote_data,
+            }
+        })
+        .collect();
+    assert_eq!(
+        votes_to_certificate(committee_exchange, &da_votes),
+        Some(committee_exchange.success_threshold().get() as usize)
+    );
+
+    let leaf_commitment = fake_commitment::<SequencingLeaf<SequencingTestTypes>>();
+    let yes_votes: Vec<_> = keys
+        .iter()
+        .map(|(key, private_key)| {
+            let vote_data = VoteData::Yes(leaf_commitment);
+            let (signature, vote_token) = sign(private_key, vote_data.commit().as_ref());
+            QuorumVote::Yes(YesOrNoVote {
+                justify_qc_commitment: fake_commitment(),
+                signature: (key.to_bytes(), signature),
+                leaf_commitment,
+                current_view: view,
+                vote_token,
+                vote_data,
+            })
+        })
+        .collect();
+    assert_eq!(
+        votes_to_certificate(quorum_exchange, &yes_votes),
+        Some(quorum_exchange.success_threshold().get() as usize)
+    );
+
+    // Timeout votes are not gathered into the exchange's certificates, only into timeout
+    // certificates.
+    let timeout_votes: Vec<_> = keys
+        .iter()
+        .map(|(key, private_key)| {
+            let vote_data = VoteData::Timeout(view.commit());
+            let (signature, vote_token) = sign(private_key, vote_data.commit().as_ref());
+            QuorumVote::<_, SequencingLeaf<SequencingTestTypes>>::Timeout(TimeoutVote {
+                high_qc: QuorumCertificate::genesis(),
+                signature: (key.to_bytes(), signature),
+                current_view: view,
+                vote_token,
+                vote_data,
+            })
+        })
+        .collect();
+    assert_eq!(votes_to_certificate(quorum_exchange, &timeout_votes), None);
+
+    let relay_pub_key = view_sync_exchange.get_leader(view).to_bytes();
+    let view_sync_commitment = ViewSyncData::<SequencingTestTypes> {
+        relay: relay_pub_key.clone(),
+        round: view,
+    }
+    .commit();
+    let view_sync_votes: Vec<_> = keys
+        .iter()
+        .map(|(key, private_key)| {
+            let vote_data = VoteData::ViewSyncCommit(view_sync_commitment);
+            let (signature, vote_token) = sign(private_key, vote_data.commit().as_ref());
+            ViewSyncVote::Commit(ViewSyncVoteInternal {
+                relay_pub_key: relay_pub_key.clone(),
+                relay: 0,
+                round: view,
+                signature: (key.to_bytes(), signature),
+                vote_token,
+                vote_data,
+            })
+        })
+        .collect();
+    assert_eq!(
+        votes_to_certificate(view_sync_exchange, &view_sync_votes),
+        Some(view_sync_exchange.success_threshold().get() as usize)
+    );
+
+    // View sync votes carry their phase, so the exchange has no single kind of vote to offer, and
+    // its keys are still checked.
+    assert!(view_sync_exchange.vote_data(view_sync_commitment).is_none());
+    assert!(view_sync_exchange.validate_keys().is_ok());
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_signature_kind_mismatch_rejected() {
+    use hotshot_types::{
+        certificate::{reconstruct_and_verify, AssembledSignatureKind},
+        data::{fake_commitment, SequencingLeaf},
+        traits::election::DEFAULT_CHAIN_ID,
+    };
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let commitment = fake_commitment::<SequencingLeaf<SequencingTestTypes>>();
+    for (vote_data, kind) in [
+        (VoteData::DA(commitment), AssembledSignatureKind::DA),
+        (VoteData::Yes(commitment), AssembledSignatureKind::Yes),
+        (VoteData::No(commitment), AssembledSignatureKind::No),
+        (
+            VoteData::Timeout(commitment),
+            AssembledSignatureKind::Timeout,
+        ),
+        (
+            VoteData::ViewSyncPreCommit(commitment),
+            AssembledSignatureKind::ViewSyncPreCommit,
+        ),
+        (
+            VoteData::ViewSyncCommit(commitment),
+            AssembledSignatureKind::ViewSyncCommit,
+        ),
+        (
+            VoteData::ViewSyncFinalize(commitment),
+            AssembledSignatureKind::ViewSyncFinalize,
+        ),
+        (
+            VoteData::Upgrade(commitment),
+            AssembledSignatureKind::Upgrade,
+        ),
+    ] {
+        assert_eq!(vote_data.expected_signature_kind(), kind);
+    }
+    assert!(VoteData::Yes(commitment).matches_signature_kind(AssembledSignatureKind::Yes));
+    assert!(!VoteData::Yes(commitment).matches_signature_kind(AssembledSignatureKind::No));
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let quorum_exchange = api.inner.exchanges.quorum_exchange().clone();
+    let threshold = quorum_exchange.success_threshold();
+    let stake_table: Vec<_> = (0..quorum_exchange.total_nodes() as u64)
+        .map(|id| (BN254Pub::generated_from_seed_indexed([0u8; 32], id).0, 1))
+        .collect();
+    let parts: Vec<_> = (0..threshold.get())
+        .map(|id| {
+            let (key, private_key) = BN254Pub::generated_from_seed_indexed([0u8; 32], id);
+            let data = VoteData::Yes(commitment).commit();
+            (key.to_bytes(), BN254Pub::sign(&private_key, data.as_ref()))
+        })
+        .collect();
+    let qc = reconstruct_and_verify(
+        ViewNumber::new(3),
+        commitment,
+        &parts,
+        &stake_table,
+        threshold,
+        DEFAULT_CHAIN_ID,
+    )
+    .unwrap();
+    assert!(quorum_exchange.is_valid_cert(&qc, commitment));
+
+    // The same signatures relabelled as a 'No' certificate are rejected.
+    let mut mismatched = qc.clone();
+    mismatched.signatures = AssembledSignature::No(qc.signatures.into_qc().unwrap());
+    assert!(!quorum_exchange.is_valid_cert(&mismatched, commitment));
+}
+
+#[cfg(test)]
+#[cfg_attr(
+    async_executor_impl = "tokio",
+    tokio::test(flavor = "multi_thread", worker_threads = 2)
+)]
+#[cfg_attr(async_executor_impl = "async-std", async_std::test)]
+async fn test_upgrade_signed_qc_rejected() {
+    use bincode::Options;
+    use ethereum_types::U256;
+    use hotshot_types::{
+        certificate::QuorumCertificate,
+        data::{fake_commitment, SequencingLeaf},
+    };
+    use hotshot_utils::bincode::bincode_opts;
+
+    async_compatibility_layer::logging::setup_logging();
+    async_compatibility_layer::logging::setup_backtrace();
+
+    let handle = build_system_handle(0).await.0;
+    let api: HotShotSequencingConsensusApi<SequencingTestTypes, SequencingMemoryImpl> =
+        HotShotSequencingConsensusApi {
+            inner: handle.hotshot.inner.clone(),
+        };
+    let quorum_exchange = api.inner.exchanges.quorum_exchange().clone();
+    let total_nodes = quorum_exchange.total_nodes();
+    let threshold = quorum_exchange.success_threshold().get();
+    let commitment = fake_commitment::<SequencingLeaf<SequencingTestTypes>>();
+
+    // A quorum of upgrade votes on a leaf commitment.
+    let entries: Vec<_> = (0..total_nodes as u64)
+        .map(|id| {
+            BN254Pub::generated_from_seed_indexed([0u8; 32], id)
+                .0
+                .get_stake_table_entry(1u64)
+        })
+        .collect();
+    let data = VoteData::Upgrade(commitment).commit();
+    let signatures: Vec<_> = (0..threshold)
+        .map(|id| {
+            let private_key = BN254Pub::generated_from_seed_indexed([0u8; 32], id).1;
+            let signature = BN254Pub::sign(&private_key, data.as_ref());
+            bincode_opts().deserialize(&signature.0).unwrap()
+        })
+        .collect();
+    let mut signers = bitvec![0; total_nodes];
+    signers[..threshold as usize].fill(true);
+    let real_qc_pp = BN254Pub::get_public_parameter(entries, U256::from(threshold));
+    let signatures =
+        AssembledSignature::Upgrade(BN254Pub::assemble(&real_qc_pp, &signers, &signatures));
+    let qc = QuorumCertificate::<SequencingTestTypes, _>::from_signatures_and_commitment(
+        ViewNumber::new(3),
+        signatures,
+        commitment,
+        None,
+    );
+
+    // The certificate is a valid upgrade certificate, but not a valid QC.
+    assert!(quorum_exchange.is_valid_upgrade_cert(&qc, commitment));
+    assert!(!quorum_exchange.is_valid_cert(&qc, commitment));
+}
+
+#[cfg(test)]
+#[test]
+fn test_timeout_votes_form_timeout_signature() {
+    use ethereum_types::U256;
+
+    let block = SDemoBlock::Normal(SDemoNormalBlock {
+        previous_state: (),
+        transactions: Vec::new(),
+    });
+    let (entries, votes) = signed_votes(4, &block, VoteData::Timeout);
+
+    // Timeout stake counts towards the success threshold like any other kind of vote.
+    let mut accumulator = empty_accumulator(4, 3);
+    let mut signatures = None;
+    for (count, vote) in votes.into_iter().enumerate() {
+        match append(accumulator, vote) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(assembled) => {
+                assert_eq!(count + 1, 3);
+                signatures = Some(assembled);
+                break;
+            }
+        }
+    }
+    let Some(AssembledSignature::Timeout(qc)) = signatures else {
+        panic!("a quorum of timeout votes did not form a timeout signature");
+    };
+    let real_qc_pp = BN254Pub::get_public_parameter(entries, U256::from(3));
+    assert!(BN254Pub::check(
+        &real_qc_pp,
+        VoteData::Timeout(block.commit()).commit().as_ref(),
+        &qc
+    ));
+}
+
+#[cfg(test)]
+#[test]
+fn test_empty_block_is_certified() {
+    use ethereum_types::U256;
+    use hotshot::demos::sdemo::SDemoState;
+    use hotshot_types::{
+        data::{DAProposal, ProposalType},
+        traits::{state::TestableBlock, Block, State},
+    };
+
+    let (_, genesis_state) = SDemoState::genesis();
+    let block = SDemoBlock::empty(&genesis_state);
+    assert_eq!(block.txn_count(), 0);
+    assert!(block.contained_transactions().is_empty());
+
+    // The empty block extends the state like any other block.
+    let state = genesis_state.append(&block, &ViewNumber::new(1)).unwrap();
+    state.on_commit();
+    let proposal =
+        DAProposal::<SequencingTestTypes>::build(&genesis_state, block.clone(), ViewNumber::new(1))
+            .unwrap();
+    assert_eq!(proposal.validate_well_formed(), Ok(()));
+
+    // And the committee certifies it.
+    let (entries, votes) = signed_votes(4, &block, VoteData::DA);
+    let mut accumulator = empty_accumulator(4, 3);
+    let mut signatures = None;
+    for vote in votes {
+        match append(accumulator, vote) {
+            Either::Left(next) => accumulator = next,
+            Either::Right(assembled) => {
+                signatures = Some(assembled);
+                break;
+            }
+        }
+    }
+    let Some(AssembledSignature::DA(qc)) = signatures else {
+        panic!("votes on the empty block did not form a DA certificate");
+    };
+    let real_qc_pp = BN254Pub::get_public_parameter(entries, U256::from(3));
+    assert!(BN254Pub::check(
+        &real_qc_pp,
+        VoteData::DA(block.commit()).commit().as_ref(),
+        &qc
+    ));
+}
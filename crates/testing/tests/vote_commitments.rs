@@ -0,0 +1,84 @@
+//! Golden vectors for the commitments votes sign.
+//!
+//! Vote signatures are over [`VoteData::commit_for_chain`], so the tags and field names below are
+//! part of the protocol: changing any of them invalidates every signature made before the change
+//! and splits nodes running different versions. If this test fails, revert the change, or roll it
+//! out as a breaking protocol upgrade and update the vectors here in the same change.
+
+use commit::{Commitment, Committable, RawCommitmentBuilder};
+use hotshot::demos::sdemo::SDemoBlock;
+use hotshot_types::traits::election::{VoteData, DEFAULT_CHAIN_ID};
+use std::collections::HashSet;
+
+/// The commitment every vote in the vectors is over.
+fn input() -> Commitment<SDemoBlock> {
+    RawCommitmentBuilder::new("Vote data golden vector input").finalize()
+}
+
+/// Each kind of vote with the tag and field name its commitment must use.
+#[allow(clippy::type_complexity)]
+fn golden_vectors() -> Vec<(VoteData<SDemoBlock>, &'static str, &'static str)> {
+    let input = input();
+    vec![
+        (VoteData::DA(input), "DA Block Commit", "block_commitment"),
+        (VoteData::Yes(input), "Yes Vote Commit", "leaf_commitment"),
+        (VoteData::No(input), "No Vote Commit", "leaf_commitment"),
+        (
+            VoteData::Timeout(input),
+            "Timeout View Number Commit",
+            "view_number_commitment",
+        ),
+        (
+            VoteData::ViewSyncPreCommit(input),
+            "ViewSyncPreCommit",
+            "commitment",
+        ),
+        (
+            VoteData::ViewSyncCommit(input),
+            "ViewSyncCommit",
+            "commitment",
+        ),
+        (
+            VoteData::ViewSyncFinalize(input),
+            "ViewSyncFinalize",
+            "commitment",
+        ),
+        (
+            VoteData::Upgrade(input),
+            "Upgrade Vote Commit",
+            "upgrade_commitment",
+        ),
+    ]
+}
+
+#[cfg(test)]
+#[test]
+fn test_vote_data_commitments_match_golden_vectors() {
+    for (vote_data, tag, field) in golden_vectors() {
+        for chain_id in [DEFAULT_CHAIN_ID, 7] {
+            let expected: Commitment<VoteData<SDemoBlock>> = RawCommitmentBuilder::new(tag)
+                .field(field, input())
+                .u64_field("chain_id", chain_id)
+                .finalize();
+            assert_eq!(
+                vote_data.commit_for_chain(chain_id),
+                expected,
+                "commitment of {vote_data:?} on chain {chain_id} changed"
+            );
+        }
+        assert_eq!(
+            vote_data.commit(),
+            vote_data.commit_for_chain(DEFAULT_CHAIN_ID)
+        );
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_vote_data_commitments_are_distinct() {
+    let commitments: HashSet<_> = golden_vectors()
+        .into_iter()
+        .map(|(vote_data, _, _)| vote_data.commit())
+        .collect();
+    assert_eq!(commitments.len(), golden_vectors().len());
+}
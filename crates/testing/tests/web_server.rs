@@ -1,8 +1,7 @@
 use async_compatibility_layer::logging::shutdown_logging;
 use hotshot_testing::{
-    node_types::{SequencingTestTypes, SequencingWebImpl},
     overall_safety_task::OverallSafetyPropertiesDescription,
-    test_builder::{TestMetadata, TimingData},
+    test_builder::{TestMetadata, TimingData, Transport},
 };
 use tracing::instrument;
 
@@ -30,10 +29,6 @@ async fn web_server_network() {
         },
         ..TestMetadata::default()
     };
-    metadata
-        .gen_launcher::<SequencingTestTypes, SequencingWebImpl>()
-        .launch()
-        .run_test()
-        .await;
+    metadata.run_sequencing_test(Transport::WebServer).await;
     shutdown_logging();
 }
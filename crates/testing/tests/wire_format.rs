@@ -0,0 +1,97 @@
+use arbitrary::{Arbitrary, Unstructured};
+use bincode::Options;
+use hotshot::demos::sdemo::SDemoTransaction;
+use hotshot_testing::node_types::{SequencingMemoryImpl, SequencingTestTypes};
+use hotshot_types::{
+    certificate::{AssembledSignature, DACertificate, QuorumCertificate, ViewSyncCertificate},
+    data::{SequencingLeaf, ViewNumber},
+    message::{CommitteeConsensusMessage, DataMessage, GeneralConsensusMessage, Proposal},
+    traits::{election::VoteData, signature_key::EncodedSignature, state::ConsensusTime},
+    vote::ViewSyncData,
+};
+use hotshot_utils::bincode::bincode_opts;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{any::type_name, fmt::Debug};
+
+/// Number of values generated per type
+const CASES: usize = 32;
+
+/// Bytes of randomness handed to the generator for each value
+const BYTES_PER_CASE: usize = 1024;
+
+/// Serialize `value` the way it goes over the wire, deserialize it again and check nothing
+/// changed, naming the type on failure.
+fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) {
+    let bytes = bincode_opts()
+        .serialize(value)
+        .unwrap_or_else(|e| panic!("{} failed to serialize: {e}", type_name::<T>()));
+    let decoded: T = bincode_opts()
+        .deserialize(&bytes)
+        .unwrap_or_else(|e| panic!("{} failed to deserialize: {e}", type_name::<T>()));
+    assert_eq!(
+        &decoded,
+        value,
+        "{} changed across a wire round trip",
+        type_name::<T>()
+    );
+}
+
+/// Round trip [`CASES`] values produced by `generate` from seeded random bytes.
+fn check_round_trips<T: Serialize + DeserializeOwned + PartialEq + Debug>(
+    generate: impl Fn(&mut Unstructured<'_>) -> arbitrary::Result<T>,
+) {
+    let mut rng = ChaCha20Rng::from_seed([7u8; 32]);
+    let mut data = vec![0u8; BYTES_PER_CASE];
+    for _ in 0..CASES {
+        rng.fill_bytes(&mut data);
+        let value = generate(&mut Unstructured::new(&data))
+            .unwrap_or_else(|e| panic!("failed to generate a {}: {e}", type_name::<T>()));
+        assert_round_trip(&value);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_certificates_round_trip() {
+    check_round_trips(
+        QuorumCertificate::<SequencingTestTypes, SequencingLeaf<SequencingTestTypes>>::arbitrary,
+    );
+    check_round_trips(DACertificate::<SequencingTestTypes>::arbitrary);
+    check_round_trips(ViewSyncCertificate::<SequencingTestTypes>::arbitrary);
+    check_round_trips(AssembledSignature::<SequencingTestTypes>::arbitrary);
+}
+
+#[cfg(test)]
+#[test]
+fn test_vote_data_round_trip() {
+    check_round_trips(VoteData::<SequencingLeaf<SequencingTestTypes>>::arbitrary);
+    check_round_trips(VoteData::<ViewSyncData<SequencingTestTypes>>::arbitrary);
+}
+
+#[cfg(test)]
+#[test]
+fn test_messages_round_trip() {
+    check_round_trips(|u| {
+        Ok(CommitteeConsensusMessage::<SequencingTestTypes>::DACertificate(u.arbitrary()?))
+    });
+    check_round_trips(|u| {
+        Ok(GeneralConsensusMessage::<
+            SequencingTestTypes,
+            SequencingMemoryImpl,
+        >::ViewSyncCertificate(Proposal {
+            data: u.arbitrary()?,
+            signature: EncodedSignature(u.arbitrary()?),
+        }))
+    });
+    check_round_trips(|u| {
+        Ok(DataMessage::<SequencingTestTypes>::SubmitTransaction(
+            SDemoTransaction {
+                id: u.arbitrary()?,
+                padding: u.arbitrary()?,
+            },
+            ViewNumber::new(u.arbitrary()?),
+        ))
+    });
+}
@@ -1,25 +1,37 @@
 //! Provides two types of cerrtificates and their accumulators.
 
 use crate::{
-    data::{fake_commitment, serialize_signature, LeafType},
+    data::{arbitrary_commitment, fake_commitment, serialize_signature, LeafType},
     traits::{
         election::{SignedCertificate, VoteData, VoteToken},
         node_implementation::NodeType,
         signature_key::{EncodedPublicKey, EncodedSignature, SignatureKey},
         state::ConsensusTime,
     },
+    verification::VerificationPool,
     vote::ViewSyncData,
 };
+use arbitrary::{Arbitrary, Unstructured};
 use bincode::Options;
+use bitvec::bitvec;
 use commit::{Commitment, Committable};
 use espresso_systems_common::hotshot::tag;
+use ethereum_types::U256;
+use hex_fmt::HexFmt;
 use hotshot_utils::bincode::bincode_opts;
+use jf_primitives::signatures::{
+    bls_over_bn254::BLSOverBN254CurveSignatureScheme, SignatureScheme,
+};
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 use std::{
+    collections::HashSet,
     fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    num::NonZeroU64,
     ops::Deref,
 };
-use tracing::debug;
+use tracing::{debug, warn};
 
 /// A `DACertificate` is a threshold signature that some data is available.
 /// It is signed by the members of the DA committee, not the entire network. It is used
@@ -36,6 +48,7 @@ pub struct DACertificate<TYPES: NodeType> {
     pub block_commitment: Commitment<TYPES::BlockType>,
 
     /// Assembled signature for certificate aggregation
+    #[debug(skip)]
     pub signatures: AssembledSignature<TYPES>,
 }
 
@@ -52,6 +65,7 @@ pub struct QuorumCertificate<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>>
     /// Which view this QC relates to
     pub view_number: TYPES::Time,
     /// assembled signature for certificate aggregation
+    #[debug(skip)]
     pub signatures: AssembledSignature<TYPES>,
     /// If this QC is for the genesis block
     pub is_genesis: bool,
@@ -67,6 +81,59 @@ impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> Display for QuorumCertif
     }
 }
 
+/// Number of leading commitment bytes shown in a certificate's `short_debug`
+const SHORT_DEBUG_COMMITMENT_BYTES: usize = 4;
+
+/// The first few bytes of `commitment` in hex, for compact log lines
+fn commitment_prefix<T: Committable>(commitment: &Commitment<T>) -> String {
+    let bytes: &[u8] = commitment.as_ref();
+    format!("{}", HexFmt(&bytes[..SHORT_DEBUG_COMMITMENT_BYTES]))
+}
+
+impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> QuorumCertificate<TYPES, LEAF> {
+    /// A compact description for log lines: the view, a prefix of the leaf commitment and the
+    /// size of the signatures.
+    #[must_use]
+    pub fn short_debug(&self) -> String {
+        format!(
+            "QC {{ view: {}, leaf: {}.., signatures: {} bytes }}",
+            *self.view_number,
+            commitment_prefix(&self.leaf_commitment),
+            self.signatures.serialized_len()
+        )
+    }
+
+    /// Whether `child` was proposed with this certificate as its justify QC
+    #[must_use]
+    pub fn justifies(&self, child: &LEAF) -> bool {
+        child.get_justify_qc().commit() == self.commit()
+    }
+
+    /// The genesis quorum certificate of a chain that starts at `view_number` rather than at
+    /// [`ConsensusTime::genesis`]; to be used only for bootstrapping such a chain
+    #[must_use]
+    pub fn genesis_at(view_number: TYPES::Time) -> Self {
+        Self {
+            leaf_commitment: fake_commitment::<LEAF>(),
+            view_number,
+            signatures: AssembledSignature::Genesis(),
+            is_genesis: true,
+        }
+    }
+}
+
+impl<TYPES: NodeType> TimeoutCertificate<TYPES> {
+    /// A compact description for log lines: the view and the size of the signatures.
+    #[must_use]
+    pub fn short_debug(&self) -> String {
+        format!(
+            "TC {{ view: {}, signatures: {} bytes }}",
+            *self.view_number,
+            self.signatures.serialized_len()
+        )
+    }
+}
+
 /// Timeout Certificate
 #[derive(custom_debug::Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(bound(deserialize = ""))]
@@ -74,6 +141,7 @@ pub struct TimeoutCertificate<TYPES: NodeType> {
     /// View that timed out
     pub view_number: TYPES::Time,
     /// assembled signature for certificate aggregation
+    #[debug(skip)]
     pub signatures: AssembledSignature<TYPES>,
 }
 
@@ -96,6 +164,23 @@ impl<TYPES: NodeType> ViewSyncCertificate<TYPES> {
     pub fn as_bytes(&self) -> Vec<u8> {
         bincode_opts().serialize(&self).unwrap()
     }
+
+    /// A compact description for log lines: the phase, round, relay and the size of the
+    /// signatures.
+    #[must_use]
+    pub fn short_debug(&self) -> String {
+        let (phase, certificate) = match self {
+            ViewSyncCertificate::PreCommit(certificate) => ("PreCommit", certificate),
+            ViewSyncCertificate::Commit(certificate) => ("Commit", certificate),
+            ViewSyncCertificate::Finalize(certificate) => ("Finalize", certificate),
+        };
+        format!(
+            "ViewSync{phase} {{ round: {}, relay: {}, signatures: {} bytes }}",
+            *certificate.round,
+            certificate.relay,
+            certificate.signatures.serialized_len()
+        )
+    }
 }
 
 /// A view sync certificate representing a quorum of votes for a particular view sync phase
@@ -107,12 +192,17 @@ pub struct ViewSyncCertificateInternal<TYPES: NodeType> {
     /// View number the network is attempting to synchronize on
     pub round: TYPES::Time,
     /// Aggregated QC
+    #[debug(skip)]
     pub signatures: AssembledSignature<TYPES>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(bound(deserialize = ""))]
-/// Enum representing whether a signatures is for a 'Yes' or 'No' or 'DA' or 'Genesis' certificate
+/// Enum representing whether a signatures is for a 'Yes' or 'No' or 'DA' or 'Genesis' or 'Timeout'
+/// certificate
+///
+/// Two assembled signatures are equal if they are of the same kind and hold the same signatures,
+/// whatever order the signers contributed in.
 pub enum AssembledSignature<TYPES: NodeType> {
     // (enum, signature)
     /// These signatures are for a 'Yes' certificate
@@ -129,6 +219,134 @@ pub enum AssembledSignature<TYPES: NodeType> {
     ViewSyncCommit(<TYPES::SignatureKey as SignatureKey>::QCType),
     /// These signatures are for ViewSyncFinalize
     ViewSyncFinalize(<TYPES::SignatureKey as SignatureKey>::QCType),
+    /// These signatures are for an 'Upgrade' certificate
+    Upgrade(<TYPES::SignatureKey as SignatureKey>::QCType),
+    /// Each signer's own signature, for a certificate of the given kind, from a signature scheme
+    /// that cannot aggregate
+    Individual(
+        AssembledSignatureKind,
+        Vec<(EncodedPublicKey, EncodedSignature)>,
+    ),
+    /// These signatures are for a 'Timeout' certificate
+    Timeout(<TYPES::SignatureKey as SignatureKey>::QCType),
+}
+
+/// The kind of certificate an [`AssembledSignature`] belongs to, without the signatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Arbitrary)]
+pub enum AssembledSignatureKind {
+    /// A 'Yes' certificate
+    Yes,
+    /// A 'No' certificate
+    No,
+    /// A 'DA' certificate
+    DA,
+    /// The genesis certificate
+    Genesis,
+    /// A ViewSyncPreCommit certificate
+    ViewSyncPreCommit,
+    /// A ViewSyncCommit certificate
+    ViewSyncCommit,
+    /// A ViewSyncFinalize certificate
+    ViewSyncFinalize,
+    /// An 'Upgrade' certificate
+    Upgrade,
+    /// A 'Timeout' certificate
+    Timeout,
+}
+
+impl<TYPES: NodeType> AssembledSignature<TYPES> {
+    /// Whether these are the (empty) signatures of the genesis certificate
+    #[must_use]
+    pub fn is_genesis(&self) -> bool {
+        matches!(self, AssembledSignature::Genesis())
+    }
+
+    /// The kind of certificate these signatures belong to
+    #[must_use]
+    pub fn kind(&self) -> AssembledSignatureKind {
+        match self {
+            AssembledSignature::Yes(_) => AssembledSignatureKind::Yes,
+            AssembledSignature::No(_) => AssembledSignatureKind::No,
+            AssembledSignature::DA(_) => AssembledSignatureKind::DA,
+            AssembledSignature::Genesis() => AssembledSignatureKind::Genesis,
+            AssembledSignature::ViewSyncPreCommit(_) => AssembledSignatureKind::ViewSyncPreCommit,
+            AssembledSignature::ViewSyncCommit(_) => AssembledSignatureKind::ViewSyncCommit,
+            AssembledSignature::ViewSyncFinalize(_) => AssembledSignatureKind::ViewSyncFinalize,
+            AssembledSignature::Upgrade(_) => AssembledSignatureKind::Upgrade,
+            AssembledSignature::Timeout(_) => AssembledSignatureKind::Timeout,
+            AssembledSignature::Individual(kind, _) => *kind,
+        }
+    }
+
+    /// Size of the signatures when serialized, for log lines that omit the signatures themselves
+    #[must_use]
+    pub fn serialized_len(&self) -> u64 {
+        bincode_opts().serialized_size(self).unwrap_or(0)
+    }
+
+    /// The assembled signatures, or `None` for the genesis certificate, which has none, and for
+    /// [`AssembledSignature::Individual`] signatures, which are not assembled
+    #[must_use]
+    pub fn into_qc(self) -> Option<<TYPES::SignatureKey as SignatureKey>::QCType> {
+        match self {
+            AssembledSignature::Yes(qc)
+            | AssembledSignature::No(qc)
+            | AssembledSignature::DA(qc)
+            | AssembledSignature::ViewSyncPreCommit(qc)
+            | AssembledSignature::ViewSyncCommit(qc)
+            | AssembledSignature::ViewSyncFinalize(qc)
+            | AssembledSignature::Upgrade(qc)
+            | AssembledSignature::Timeout(qc) => Some(qc),
+            AssembledSignature::Genesis() | AssembledSignature::Individual(_, _) => None,
+        }
+    }
+}
+
+/// The form of an [`AssembledSignature`] that equality and hashing use.
+///
+/// Aggregated signatures are already independent of contributor order: the signers are a bit
+/// vector indexed by stake table position and aggregation is commutative. Individual signatures
+/// are kept in the order they were collected, so they are sorted here.
+type CanonicalSignature<'a, TYPES> = (
+    AssembledSignatureKind,
+    Option<&'a <<TYPES as NodeType>::SignatureKey as SignatureKey>::QCType>,
+    Vec<&'a (EncodedPublicKey, EncodedSignature)>,
+);
+
+impl<TYPES: NodeType> AssembledSignature<TYPES> {
+    /// This signature in the form equality and hashing use
+    fn canonical(&self) -> CanonicalSignature<'_, TYPES> {
+        match self {
+            AssembledSignature::Yes(qc)
+            | AssembledSignature::No(qc)
+            | AssembledSignature::DA(qc)
+            | AssembledSignature::ViewSyncPreCommit(qc)
+            | AssembledSignature::ViewSyncCommit(qc)
+            | AssembledSignature::ViewSyncFinalize(qc)
+            | AssembledSignature::Upgrade(qc)
+            | AssembledSignature::Timeout(qc) => (self.kind(), Some(qc), Vec::new()),
+            AssembledSignature::Genesis() => (self.kind(), None, Vec::new()),
+            AssembledSignature::Individual(kind, signatures) => {
+                let mut signatures: Vec<_> = signatures.iter().collect();
+                signatures.sort();
+                (*kind, None, signatures)
+            }
+        }
+    }
+}
+
+impl<TYPES: NodeType> PartialEq for AssembledSignature<TYPES> {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl<TYPES: NodeType> Eq for AssembledSignature<TYPES> {}
+
+impl<TYPES: NodeType> Hash for AssembledSignature<TYPES> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state);
+    }
 }
 
 /// Data from a vote needed to accumulate into a `SignedCertificate`
@@ -191,12 +409,7 @@ impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>>
     }
 
     fn genesis() -> Self {
-        Self {
-            leaf_commitment: fake_commitment::<LEAF>(),
-            view_number: <TYPES::Time as ConsensusTime>::genesis(),
-            signatures: AssembledSignature::Genesis(),
-            is_genesis: true,
-        }
+        Self::genesis_at(<TYPES::Time as ConsensusTime>::genesis())
     }
 }
 
@@ -264,6 +477,233 @@ impl<TYPES: NodeType> SignedCertificate<TYPES, TYPES::Time, TYPES::VoteTokenType
     }
 }
 
+/// Error recovering the contributors of a certificate
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum ContributorsError {
+    /// The certificate does not carry a DA signature, so there is no signer bitmap to read
+    NotDASignature,
+    /// The signer bitmap does not cover the stake table it is being read against
+    StakeTableMismatch {
+        /// Length of the signer bitmap
+        signers: usize,
+        /// Length of the stake table
+        stake_table: usize,
+    },
+}
+
+impl<TYPES: NodeType> DACertificate<TYPES> {
+    /// A compact description for log lines: the view, a prefix of the block commitment and the
+    /// size of the signatures.
+    #[must_use]
+    pub fn short_debug(&self) -> String {
+        format!(
+            "DAC {{ view: {}, block: {}.., signatures: {} bytes }}",
+            *self.view_number,
+            commitment_prefix(&self.block_commitment),
+            self.signatures.serialized_len()
+        )
+    }
+
+    /// The DA committee members who signed this certificate.
+    ///
+    /// `stake_table` lists the committee's keys in the order of the committee's QC stake table,
+    /// which is the order of the signer bitmap in the assembled signature.
+    /// # Errors
+    /// If the certificate does not carry a DA signature, or its signer bitmap does not match
+    /// `stake_table`
+    pub fn contributors(
+        &self,
+        stake_table: &[TYPES::SignatureKey],
+    ) -> Result<Vec<EncodedPublicKey>, ContributorsError> {
+        let qc = match &self.signatures {
+            AssembledSignature::DA(qc) => qc,
+            AssembledSignature::Individual(AssembledSignatureKind::DA, signatures) => {
+                return Ok(signatures.iter().map(|(key, _)| key.clone()).collect());
+            }
+            _ => return Err(ContributorsError::NotDASignature),
+        };
+        let (_, signers) = <TYPES::SignatureKey as SignatureKey>::get_sig_proof(qc);
+        if signers.len() != stake_table.len() {
+            return Err(ContributorsError::StakeTableMismatch {
+                signers: signers.len(),
+                stake_table: stake_table.len(),
+            });
+        }
+        Ok(signers
+            .iter_ones()
+            .map(|index| stake_table[index].to_bytes())
+            .collect())
+    }
+}
+
+/// Error reconstructing a quorum certificate from individual votes
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// A signer is not in the stake table
+    UnknownSigner {
+        /// The signer's encoded key
+        key: EncodedPublicKey,
+    },
+    /// A signer appears more than once
+    DuplicateSigner {
+        /// The signer's encoded key
+        key: EncodedPublicKey,
+    },
+    /// A signature could not be decoded
+    MalformedSignature {
+        /// The signer's encoded key
+        key: EncodedPublicKey,
+    },
+    /// The signers together hold less stake than the threshold
+    InsufficientStake {
+        /// Stake held by the signers
+        stake: U256,
+        /// Stake required
+        threshold: u64,
+    },
+    /// The assembled signature does not verify against the stake table
+    InvalidSignature,
+}
+
+/// Reconstruct a `Yes` quorum certificate for `leaf_commitment` in `view_number` from the
+/// individual votes `parts`, and verify it, without a running node.
+///
+/// `stake_table` lists the committee's keys and stakes in the order of the committee's QC stake
+/// table, which is the order of the signer bitmap in the assembled signature. The votes must have
+/// been signed for `chain_id`. For signature schemes that cannot aggregate, the certificate keeps
+/// each signer's own signature.
+/// # Errors
+/// If a signer is unknown, duplicated or has an undecodable signature, if the signers hold less
+/// than `threshold` stake, or if the assembled signature does not verify
+pub fn reconstruct_and_verify<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>>(
+    view_number: TYPES::Time,
+    leaf_commitment: Commitment<LEAF>,
+    parts: &[(EncodedPublicKey, EncodedSignature)],
+    stake_table: &[(TYPES::SignatureKey, u64)],
+    threshold: NonZeroU64,
+    chain_id: u64,
+) -> Result<QuorumCertificate<TYPES, LEAF>, VerifyError> {
+    let aggregate = <TYPES::SignatureKey as SignatureKey>::supports_aggregation();
+    let mut signers = bitvec![0; stake_table.len()];
+    let mut signatures = Vec::with_capacity(parts.len());
+    let mut stake = U256::zero();
+    for (key, signature) in parts {
+        let Some(index) = stake_table
+            .iter()
+            .position(|(member, _)| member.to_bytes() == *key)
+        else {
+            return Err(VerifyError::UnknownSigner { key: key.clone() });
+        };
+        if signers.replace(index, true) {
+            return Err(VerifyError::DuplicateSigner { key: key.clone() });
+        }
+        if aggregate {
+            let signature: <BLSOverBN254CurveSignatureScheme as SignatureScheme>::Signature =
+                bincode_opts()
+                    .deserialize(&signature.0)
+                    .map_err(|_| VerifyError::MalformedSignature { key: key.clone() })?;
+            signatures.push((index, signature));
+        }
+        stake = stake.saturating_add(U256::from(stake_table[index].1));
+    }
+    if stake < U256::from(threshold.get()) {
+        return Err(VerifyError::InsufficientStake {
+            stake,
+            threshold: threshold.get(),
+        });
+    }
+
+    let entries: Vec<_> = stake_table
+        .iter()
+        .map(|(key, stake)| key.get_stake_table_entry(*stake))
+        .collect();
+    let real_commit = VoteData::Yes(leaf_commitment).commit_for_chain(chain_id);
+    if !aggregate {
+        if !check_individual_signatures::<TYPES>(
+            &VerificationPool::inline(),
+            parts,
+            &entries,
+            threshold,
+            real_commit.as_ref(),
+        ) {
+            return Err(VerifyError::InvalidSignature);
+        }
+        return Ok(QuorumCertificate {
+            leaf_commitment,
+            view_number,
+            signatures: AssembledSignature::Individual(AssembledSignatureKind::Yes, parts.to_vec()),
+            is_genesis: false,
+        });
+    }
+
+    // Signatures are assembled in signer bitmap order.
+    signatures.sort_by_key(|(index, _)| *index);
+    let signatures: Vec<_> = signatures
+        .into_iter()
+        .map(|(_, signature)| signature)
+        .collect();
+
+    let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
+        entries,
+        U256::from(threshold.get()),
+    );
+    let qc = <TYPES::SignatureKey as SignatureKey>::assemble(&real_qc_pp, &signers, &signatures);
+    if !<TYPES::SignatureKey as SignatureKey>::check(&real_qc_pp, real_commit.as_ref(), &qc) {
+        return Err(VerifyError::InvalidSignature);
+    }
+    Ok(QuorumCertificate {
+        leaf_commitment,
+        view_number,
+        signatures: AssembledSignature::Yes(qc),
+        is_genesis: false,
+    })
+}
+
+/// Check the [`AssembledSignature::Individual`] signatures of a certificate on `data`.
+///
+/// Every signer must be distinct, hold stake in `stake_table` and have signed `data`, and the
+/// signers together must hold at least `threshold` stake. The signatures are only checked once
+/// the signers hold enough stake, as one batch on `pool`.
+#[must_use]
+pub fn check_individual_signatures<TYPES: NodeType>(
+    pool: &VerificationPool,
+    signatures: &[(EncodedPublicKey, EncodedSignature)],
+    stake_table: &[<TYPES::SignatureKey as SignatureKey>::StakeTableEntry],
+    threshold: NonZeroU64,
+    data: &[u8],
+) -> bool {
+    let mut signers = HashSet::new();
+    let mut stake = U256::zero();
+    let mut checks = Vec::with_capacity(signatures.len());
+    for (encoded_key, signature) in signatures {
+        if !signers.insert(encoded_key) {
+            warn!("Certificate counts signer {:?} twice", encoded_key);
+            return false;
+        }
+        let Some(key) = <TYPES::SignatureKey as SignatureKey>::from_bytes(encoded_key) else {
+            return false;
+        };
+        let Some(signer_stake) = stake_table
+            .iter()
+            .find_map(|entry| key.get_stake_from_entry(entry))
+        else {
+            warn!(
+                "Certificate signer {:?} is not in the stake table",
+                encoded_key
+            );
+            return false;
+        };
+        stake = stake.saturating_add(signer_stake);
+        checks.push((key, signature));
+    }
+    if stake < U256::from(threshold.get()) {
+        return false;
+    }
+    pool.run_batch(checks, |(key, signature)| key.validate(signature, data))
+        .into_iter()
+        .all(|valid| valid)
+}
+
 impl<TYPES: NodeType> Eq for DACertificate<TYPES> {}
 
 impl<TYPES: NodeType> Committable for ViewSyncCertificate<TYPES> {
@@ -313,25 +753,35 @@ impl<TYPES: NodeType>
     for ViewSyncCertificate<TYPES>
 {
     /// Build a QC from the threshold signature and commitment
+    ///
+    /// `relay` is shared with the other certificate types, which ignore it, so a generic caller
+    /// may pass `None`. The first relay of the round is used in that case.
     fn from_signatures_and_commitment(
         view_number: TYPES::Time,
         signatures: AssembledSignature<TYPES>,
         _commit: Commitment<ViewSyncData<TYPES>>,
         relay: Option<u64>,
     ) -> Self {
+        let relay = relay.unwrap_or_else(|| {
+            warn!(
+                "View sync certificate for round {:?} built without a relay; using relay 0",
+                view_number
+            );
+            0
+        });
         let certificate_internal = ViewSyncCertificateInternal {
             round: view_number,
-            relay: relay.unwrap(),
+            relay,
             signatures: signatures.clone(),
         };
-        match signatures {
-            AssembledSignature::ViewSyncPreCommit(_) => {
+        match signatures.kind() {
+            AssembledSignatureKind::ViewSyncPreCommit => {
                 ViewSyncCertificate::PreCommit(certificate_internal)
             }
-            AssembledSignature::ViewSyncCommit(_) => {
+            AssembledSignatureKind::ViewSyncCommit => {
                 ViewSyncCertificate::Commit(certificate_internal)
             }
-            AssembledSignature::ViewSyncFinalize(_) => {
+            AssembledSignatureKind::ViewSyncFinalize => {
                 ViewSyncCertificate::Finalize(certificate_internal)
             }
             _ => unimplemented!(),
@@ -380,3 +830,125 @@ impl<TYPES: NodeType>
     }
 }
 impl<TYPES: NodeType> Eq for ViewSyncCertificate<TYPES> {}
+
+/// Most signers in an arbitrary assembled signature; signing is slow, so keep this small.
+const MAX_ARBITRARY_SIGNERS: u64 = 4;
+
+/// Signatures of a few generated keys over arbitrary data, assembled the way the vote accumulator
+/// assembles them for a certificate of the given kind.
+fn arbitrary_signatures<TYPES: NodeType>(
+    u: &mut Unstructured<'_>,
+    kind: AssembledSignatureKind,
+) -> arbitrary::Result<AssembledSignature<TYPES>> {
+    let signers = u.int_in_range(1..=MAX_ARBITRARY_SIGNERS)?;
+    let first_index = u64::from(u.arbitrary::<u16>()?);
+    let data: Vec<u8> = u.arbitrary()?;
+    let keys: Vec<_> = (first_index..first_index + signers)
+        .map(|index| TYPES::SignatureKey::generated_from_seed_indexed([0u8; 32], index))
+        .collect();
+
+    if !TYPES::SignatureKey::supports_aggregation() {
+        let signatures = keys
+            .iter()
+            .map(|(key, private_key)| {
+                (
+                    key.to_bytes(),
+                    TYPES::SignatureKey::sign(private_key, &data),
+                )
+            })
+            .collect();
+        return Ok(AssembledSignature::Individual(kind, signatures));
+    }
+
+    let sigs = keys
+        .iter()
+        .map(|(_, private_key)| {
+            bincode_opts()
+                .deserialize(&TYPES::SignatureKey::sign(private_key, &data).0)
+                .map_err(|_| arbitrary::Error::IncorrectFormat)
+        })
+        .collect::<arbitrary::Result<Vec<_>>>()?;
+    let entries = keys
+        .iter()
+        .map(|(key, _)| key.get_stake_table_entry(1u64))
+        .collect();
+    let real_qc_pp = TYPES::SignatureKey::get_public_parameter(entries, U256::from(signers));
+    #[allow(clippy::cast_possible_truncation)]
+    let real_qc_sig = TYPES::SignatureKey::assemble(
+        &real_qc_pp,
+        bitvec![1; signers as usize].as_bitslice(),
+        &sigs,
+    );
+    Ok(match kind {
+        AssembledSignatureKind::Yes => AssembledSignature::Yes(real_qc_sig),
+        AssembledSignatureKind::No => AssembledSignature::No(real_qc_sig),
+        AssembledSignatureKind::DA => AssembledSignature::DA(real_qc_sig),
+        AssembledSignatureKind::Genesis => AssembledSignature::Genesis(),
+        AssembledSignatureKind::ViewSyncPreCommit => {
+            AssembledSignature::ViewSyncPreCommit(real_qc_sig)
+        }
+        AssembledSignatureKind::ViewSyncCommit => AssembledSignature::ViewSyncCommit(real_qc_sig),
+        AssembledSignatureKind::ViewSyncFinalize => {
+            AssembledSignature::ViewSyncFinalize(real_qc_sig)
+        }
+        AssembledSignatureKind::Upgrade => AssembledSignature::Upgrade(real_qc_sig),
+        AssembledSignatureKind::Timeout => AssembledSignature::Timeout(real_qc_sig),
+    })
+}
+
+impl<'a, TYPES: NodeType> Arbitrary<'a> for AssembledSignature<TYPES> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let kind = u.arbitrary()?;
+        arbitrary_signatures(u, kind)
+    }
+}
+
+impl<'a, TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> Arbitrary<'a>
+    for QuorumCertificate<TYPES, LEAF>
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let is_genesis = u.arbitrary()?;
+        let signatures = if is_genesis {
+            AssembledSignature::Genesis()
+        } else {
+            let kind = *u.choose(&[AssembledSignatureKind::Yes, AssembledSignatureKind::No])?;
+            arbitrary_signatures(u, kind)?
+        };
+        Ok(Self {
+            leaf_commitment: arbitrary_commitment(u)?,
+            view_number: TYPES::Time::new(u.arbitrary()?),
+            signatures,
+            is_genesis,
+        })
+    }
+}
+
+impl<'a, TYPES: NodeType> Arbitrary<'a> for DACertificate<TYPES> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            view_number: TYPES::Time::new(u.arbitrary()?),
+            block_commitment: arbitrary_commitment(u)?,
+            signatures: arbitrary_signatures(u, AssembledSignatureKind::DA)?,
+        })
+    }
+}
+
+impl<'a, TYPES: NodeType> Arbitrary<'a> for ViewSyncCertificate<TYPES> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let kind = *u.choose(&[
+            AssembledSignatureKind::ViewSyncPreCommit,
+            AssembledSignatureKind::ViewSyncCommit,
+            AssembledSignatureKind::ViewSyncFinalize,
+        ])?;
+        let certificate = ViewSyncCertificateInternal {
+            relay: u.arbitrary()?,
+            round: TYPES::Time::new(u.arbitrary()?),
+            signatures: arbitrary_signatures(u, kind)?,
+        };
+        Ok(match kind {
+            AssembledSignatureKind::ViewSyncPreCommit => Self::PreCommit(certificate),
+            AssembledSignatureKind::ViewSyncCommit => Self::Commit(certificate),
+            _ => Self::Finalize(certificate),
+        })
+    }
+}
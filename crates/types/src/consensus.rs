@@ -3,12 +3,13 @@
 pub use crate::traits::node_implementation::ViewQueue;
 pub use crate::utils::{View, ViewInner};
 use async_compatibility_layer::async_primitives::subscribable_rwlock::SubscribableRwLock;
+use async_lock::{Semaphore, SemaphoreGuardArc};
 use std::collections::HashSet;
 
 use crate::utils::Terminator;
 use crate::{
     certificate::QuorumCertificate,
-    data::LeafType,
+    data::{DeltasType, LeafType},
     error::HotShotError,
     traits::{
         metrics::{Counter, Gauge, Histogram, Metrics},
@@ -19,13 +20,21 @@ use commit::{Commitment, Committable};
 use derivative::Derivative;
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
+    num::NonZeroUsize,
     sync::Arc,
 };
 use tracing::error;
 
+/// The default for [`Consensus::txn_index_retention`].
+pub const DEFAULT_TXN_INDEX_RETENTION: u64 = 1024;
+
 /// A type alias for `HashMap<Commitment<T>, T>`
 type CommitmentMap<T> = HashMap<Commitment<T>, T>;
 
+/// A callback invoked with each newly decided leaf, registered with
+/// [`Consensus::register_on_decide`].
+pub type DecideCallback<LEAF> = Arc<dyn Fn(&LEAF) + Send + Sync>;
+
 /// A reference to the consensus algorithm
 ///
 /// This will contain the state of all rounds.
@@ -38,11 +47,15 @@ pub struct Consensus<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> {
     /// cur_view from pseudocode
     pub cur_view: TYPES::Time,
 
+    /// The view this node started from: the view of the genesis leaf, or of the anchor leaf when
+    /// reloaded
+    pub start_view: TYPES::Time,
+
     /// last view had a successful decide event
     pub last_decided_view: TYPES::Time,
 
-    /// A list of undecided transactions
-    pub transactions: Arc<SubscribableRwLock<CommitmentMap<TYPES::Transaction>>>,
+    /// The undecided transactions, in arrival order
+    pub transactions: Arc<SubscribableRwLock<Mempool<TYPES::Transaction>>>,
 
     /// A list of transactions we've seen decided, but didn't receive
     pub seen_transactions: HashSet<Commitment<TYPES::Transaction>>,
@@ -70,6 +83,129 @@ pub struct Consensus<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> {
     /// Amount of invalid QCs we've seen since the last commit
     /// Used for metrics.  This resets to 0 on every decide event.
     pub invalid_qc: usize,
+
+    /// Maximum number of views kept in `state_map`; `None` means unbounded
+    pub max_buffered_views: Option<NonZeroUsize>,
+
+    /// The view each recently decided transaction was included in
+    pub txn_index: HashMap<Commitment<TYPES::Transaction>, TYPES::Time>,
+
+    /// How many views behind the last decided view a transaction stays in `txn_index`
+    pub txn_index_retention: u64,
+
+    /// Callbacks invoked for each newly decided leaf, in registration order
+    #[debug(skip)]
+    pub decide_callbacks: Vec<DecideCallback<LEAF>>,
+}
+
+/// How far a view has progressed, as reported by [`Consensus::view_status`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ViewStatus {
+    /// Nothing is known about the view, either because it has not started or because it was
+    /// garbage collected
+    Unknown,
+    /// The view's block is available, but no leaf has been proposed for it yet
+    DAAvailable,
+    /// A leaf was proposed for the view but has not been decided
+    Proposed,
+    /// The view's leaf has been decided
+    Decided,
+    /// The view failed without producing a leaf
+    Failed,
+}
+
+/// Two different leaves claim the same view, as reported by [`Consensus::insert_leaf`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InsertConflict<LEAF: LeafType> {
+    /// The view both leaves claim
+    pub view: <LEAF::NodeType as NodeType>::Time,
+    /// Commitment of the leaf already stored for the view
+    pub existing: Commitment<LEAF>,
+    /// Commitment of the rejected leaf
+    pub new: Commitment<LEAF>,
+}
+
+impl<LEAF: LeafType> std::fmt::Display for InsertConflict<LEAF> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "conflicting leaves for view {:?}: {:?} is stored, {:?} was rejected",
+            self.view, self.existing, self.new
+        )
+    }
+}
+
+impl<LEAF: LeafType> std::error::Error for InsertConflict<LEAF> {}
+
+/// Why [`Consensus::install_synced_view`] refused a synced view.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyncError<LEAF: LeafType> {
+    /// The leaf does not belong to the view it was synced for
+    WrongView {
+        /// The view being installed
+        view: <LEAF::NodeType as NodeType>::Time,
+        /// The view the leaf claims
+        leaf_view: <LEAF::NodeType as NodeType>::Time,
+    },
+    /// The QC does not certify the synced leaf
+    UnjustifiedLeaf {
+        /// Commitment of the synced leaf
+        leaf: Commitment<LEAF>,
+        /// Commitment of the leaf the QC certifies
+        certified: Commitment<LEAF>,
+    },
+    /// The QC's signatures do not hold up
+    InvalidCertificate {
+        /// The view the QC claims to certify
+        view: <LEAF::NodeType as NodeType>::Time,
+    },
+}
+
+impl<LEAF: LeafType> std::fmt::Display for SyncError<LEAF> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongView { view, leaf_view } => write!(
+                f,
+                "synced leaf for view {view:?} belongs to view {leaf_view:?}"
+            ),
+            Self::UnjustifiedLeaf { leaf, certified } => write!(
+                f,
+                "synced leaf {leaf:?} is not justified by a QC for {certified:?}"
+            ),
+            Self::InvalidCertificate { view } => {
+                write!(f, "QC for synced view {view:?} has invalid signatures")
+            }
+        }
+    }
+}
+
+impl<LEAF: LeafType> std::error::Error for SyncError<LEAF> {}
+
+/// Bounds how many proposals a node assembles at once, so a leader for several consecutive views
+/// does not pile up block-assembly work when assembly is slow.
+#[derive(Clone, Debug, Default)]
+pub struct ProposalSlots {
+    /// One permit per proposal that may be assembled concurrently, or `None` for no limit
+    slots: Option<Arc<Semaphore>>,
+}
+
+impl ProposalSlots {
+    /// Allow at most `limit` proposals to be assembled at once; `None` means unbounded
+    #[must_use]
+    pub fn new(limit: Option<NonZeroUsize>) -> Self {
+        Self {
+            slots: limit.map(|limit| Arc::new(Semaphore::new(limit.get()))),
+        }
+    }
+
+    /// Wait until another proposal may be assembled. Assembly should hold the returned guard
+    /// until the proposal is sent; it is `None` when there is no limit.
+    pub async fn acquire(&self) -> Option<SemaphoreGuardArc> {
+        match &self.slots {
+            Some(slots) => Some(slots.acquire_arc().await),
+            None => None,
+        }
+    }
 }
 
 /// The metrics being collected for the consensus algorithm
@@ -165,6 +301,35 @@ impl ConsensusMetrics {
     }
 }
 
+/// The metrics being collected for the view sync protocol
+pub struct ViewSyncMetrics {
+    /// Number of view sync precommit votes this node sent
+    pub precommit_votes_sent: Box<dyn Counter>,
+    /// Number of view sync commit votes this node sent
+    pub commit_votes_sent: Box<dyn Counter>,
+    /// Number of view sync finalize votes this node sent
+    pub finalize_votes_sent: Box<dyn Counter>,
+    /// Number of view sync certificates this node formed as a relay
+    pub certificates_formed: Box<dyn Counter>,
+    /// Number of views this node recovered through view sync
+    pub views_recovered: Box<dyn Counter>,
+}
+
+impl ViewSyncMetrics {
+    /// Create a new instance of this [`ViewSyncMetrics`] struct, setting all the counters
+    #[must_use]
+    pub fn new(metrics: &dyn Metrics) -> Self {
+        Self {
+            precommit_votes_sent: metrics
+                .create_counter(String::from("precommit_votes_sent"), None),
+            commit_votes_sent: metrics.create_counter(String::from("commit_votes_sent"), None),
+            finalize_votes_sent: metrics.create_counter(String::from("finalize_votes_sent"), None),
+            certificates_formed: metrics.create_counter(String::from("certificates_formed"), None),
+            views_recovered: metrics.create_counter(String::from("views_recovered"), None),
+        }
+    }
+}
+
 impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> Consensus<TYPES, LEAF> {
     /// increment the current view
     /// NOTE may need to do gc here
@@ -226,7 +391,8 @@ impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> Consensus<TYPES, LEAF> {
 
     /// garbage collects based on state change
     /// right now, this removes from both the `saved_blocks`
-    /// and `state_map` fields of `Consensus`
+    /// and `state_map` fields of `Consensus`, and drops transactions older than
+    /// `txn_index_retention` from `txn_index`
     /// # Panics
     /// On inconsistent stored entries
     #[allow(clippy::unused_async)] // async for API compatibility reasons
@@ -262,14 +428,245 @@ impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> Consensus<TYPES, LEAF> {
                 }
             });
         self.state_map = self.state_map.split_off(&new_anchor_view);
+        let retention = self.txn_index_retention;
+        self.txn_index
+            .retain(|_, view| (**view).saturating_add(retention) >= *new_anchor_view);
+    }
+
+    /// Record that `txns` were decided in `view`, so [`Self::inclusion_view`] can find them.
+    pub fn index_transactions(
+        &mut self,
+        view: TYPES::Time,
+        txns: impl IntoIterator<Item = Commitment<TYPES::Transaction>>,
+    ) {
+        self.txn_index
+            .extend(txns.into_iter().map(|txn| (txn, view)));
+    }
+
+    /// The view the transaction `txn` was decided in, if it was decided within the last
+    /// `txn_index_retention` views.
+    #[must_use]
+    pub fn inclusion_view(&self, txn: &Commitment<TYPES::Transaction>) -> Option<TYPES::Time> {
+        self.txn_index.get(txn).copied()
+    }
+
+    /// Register `callback` to be invoked once with each leaf this node decides from now on.
+    ///
+    /// Callbacks run synchronously on the consensus task, in registration order, while the
+    /// consensus state is locked for writing, so they must not take the consensus lock themselves.
+    pub fn register_on_decide(&mut self, callback: DecideCallback<LEAF>) {
+        self.decide_callbacks.push(callback);
+    }
+
+    /// Invoke the decide callbacks with each leaf of a newly decided `leaf_chain`.
+    ///
+    /// `leaf_chain` is ordered newest first, as in
+    /// [`EventType::Decide`](crate::event::EventType::Decide), and the callbacks see the leaves in
+    /// ascending view order.
+    pub fn notify_decided(&self, leaf_chain: &[LEAF]) {
+        for leaf in leaf_chain.iter().rev() {
+            for callback in &self.decide_callbacks {
+                callback(leaf);
+            }
+        }
+    }
+
+    /// Store `leaf` in `saved_leaves`.
+    ///
+    /// Re-inserting a leaf that is already stored is a no-op.
+    ///
+    /// # Errors
+    /// If a different leaf is already stored for the same view, which means the proposer
+    /// equivocated. The stored leaf is kept.
+    pub fn insert_leaf(&mut self, leaf: LEAF) -> Result<(), InsertConflict<LEAF>> {
+        let view = leaf.get_view_number();
+        let new = leaf.commit();
+        if let Some(existing) = self
+            .saved_leaves
+            .iter()
+            .find(|(commitment, saved)| saved.get_view_number() == view && **commitment != new)
+            .map(|(commitment, _)| *commitment)
+        {
+            return Err(InsertConflict {
+                view,
+                existing,
+                new,
+            });
+        }
+        self.saved_leaves.insert(new, leaf);
+        Ok(())
+    }
+
+    /// Install a view obtained through state sync, without re-executing its block.
+    ///
+    /// Stores `leaf` and its state as the view's leaf, replacing whatever this node held for the
+    /// view, and adopts `qc` as the `high_qc` if it is newer. `is_valid_qc` checks the QC's
+    /// signatures, normally through the quorum exchange's `is_valid_cert`.
+    ///
+    /// # Errors
+    /// If `leaf` is not for `view`, `qc` certifies a different leaf or view, or `is_valid_qc`
+    /// rejects `qc`. Nothing is installed in that case.
+    pub fn install_synced_view(
+        &mut self,
+        view: TYPES::Time,
+        leaf: LEAF,
+        qc: QuorumCertificate<TYPES, LEAF>,
+        is_valid_qc: impl FnOnce(&QuorumCertificate<TYPES, LEAF>) -> bool,
+    ) -> Result<(), SyncError<LEAF>> {
+        if leaf.get_view_number() != view {
+            return Err(SyncError::WrongView {
+                view,
+                leaf_view: leaf.get_view_number(),
+            });
+        }
+        let commitment = leaf.commit();
+        if qc.leaf_commitment != commitment || qc.view_number != view {
+            return Err(SyncError::UnjustifiedLeaf {
+                leaf: commitment,
+                certified: qc.leaf_commitment,
+            });
+        }
+        if !is_valid_qc(&qc) {
+            return Err(SyncError::InvalidCertificate { view });
+        }
+
+        // the synced leaf is certified, so it supersedes any leaf we held for the view
+        let superseded: Vec<_> = self
+            .saved_leaves
+            .iter()
+            .filter(|(saved, stored)| **saved != commitment && stored.get_view_number() == view)
+            .map(|(saved, _)| *saved)
+            .collect();
+        for saved in superseded {
+            if let Some(removed) = self.saved_leaves.remove(&saved) {
+                self.saved_blocks.remove(removed.get_deltas_commitment());
+            }
+        }
+        self.saved_leaves.insert(commitment, leaf);
+        if qc.view_number > self.high_qc.view_number {
+            self.high_qc = qc;
+        }
+        self.insert_view(
+            view,
+            View {
+                view_inner: ViewInner::Leaf { leaf: commitment },
+            },
+        );
+        Ok(())
+    }
+
+    /// insert a view into `state_map`, evicting the oldest non-essential views if this takes the
+    /// map past `max_buffered_views`
+    pub fn insert_view(&mut self, view_number: TYPES::Time, view: View<TYPES, LEAF>) {
+        self.state_map.insert(view_number, view);
+        self.evict_excess_views();
+    }
+
+    /// evict the oldest views from `state_map` until it fits in `max_buffered_views`
+    ///
+    /// The last decided view, views on the `high_qc` chain and views at or after `cur_view` are
+    /// never evicted, so the map may stay above the cap if every view is still needed.
+    fn evict_excess_views(&mut self) {
+        let Some(max_views) = self.max_buffered_views else {
+            return;
+        };
+        let excess = self.state_map.len().saturating_sub(max_views.get());
+        if excess == 0 {
+            return;
+        }
+
+        // collect the views still referenced by the `high_qc` chain
+        let mut protected = HashSet::from([self.finalized_view(), self.high_qc.view_number]);
+        let mut next_leaf = self.high_qc.leaf_commitment;
+        while let Some(leaf) = self.saved_leaves.get(&next_leaf) {
+            protected.insert(leaf.get_view_number());
+            if leaf.get_view_number() <= self.finalized_view() {
+                break;
+            }
+            next_leaf = leaf.get_parent_commitment();
+        }
+
+        let evicted: Vec<_> = self
+            .state_map
+            .keys()
+            .filter(|view_number| **view_number < self.cur_view && !protected.contains(view_number))
+            .take(excess)
+            .copied()
+            .collect();
+        for view_number in evicted {
+            let Some(view) = self.state_map.remove(&view_number) else {
+                continue;
+            };
+            if let Some(block) = view.get_block_commitment() {
+                self.saved_blocks.remove(block);
+            }
+            if let Some(leaf) = view.get_leaf_commitment() {
+                if let Some(removed) = self.saved_leaves.remove(&leaf) {
+                    self.saved_blocks.remove(removed.get_deltas_commitment());
+                }
+            }
+        }
+    }
+
+    /// Everything at or below this view is finalized.
+    ///
+    /// This is the view of the last leaf to satisfy the three-chain commit rule, so it only moves
+    /// forward when the consensus task decides.
+    #[must_use]
+    pub fn finalized_view(&self) -> TYPES::Time {
+        self.last_decided_view
     }
 
     /// return a clone of the internal storage of unclaimed transactions
     #[must_use]
-    pub fn get_transactions(&self) -> Arc<SubscribableRwLock<CommitmentMap<TYPES::Transaction>>> {
+    pub fn get_transactions(&self) -> Arc<SubscribableRwLock<Mempool<TYPES::Transaction>>> {
         self.transactions.clone()
     }
 
+    /// Gets the leaf proposed in `view`, if that view produced a leaf we still hold.
+    ///
+    /// Returns `None` if the view is unknown, failed, or only reached DA; [`Self::view_status`]
+    /// tells these apart.
+    #[must_use]
+    pub fn leaf_for_view(&self, view: TYPES::Time) -> Option<&LEAF> {
+        let leaf_commitment = self.state_map.get(&view)?.get_leaf_commitment()?;
+        self.saved_leaves.get(&leaf_commitment)
+    }
+
+    /// Gets the state after the leaf proposed in `view`, if that view produced a leaf we still
+    /// hold and the leaf carries its state.
+    ///
+    /// Returns `None` if the view is unknown, failed, or only reached DA, and always for
+    /// sequencing leaves, which do not carry their state.
+    #[must_use]
+    pub fn validated_state_for_view(&self, view: TYPES::Time) -> Option<TYPES::StateType> {
+        self.leaf_for_view(view)?.get_validated_state()
+    }
+
+    /// Reports how far `view` has progressed according to `state_map` and the last decided view.
+    #[must_use]
+    pub fn view_status(&self, view: TYPES::Time) -> ViewStatus {
+        match self.state_map.get(&view).map(|entry| &entry.view_inner) {
+            None => ViewStatus::Unknown,
+            Some(ViewInner::DA { .. }) => ViewStatus::DAAvailable,
+            Some(ViewInner::Failed) => ViewStatus::Failed,
+            Some(ViewInner::Leaf { .. }) if view <= self.last_decided_view => ViewStatus::Decided,
+            Some(ViewInner::Leaf { .. }) => ViewStatus::Proposed,
+        }
+    }
+
+    /// Gets the full block of `leaf`, loading it from the saved blocks if the leaf only carries
+    /// the block's commitment.
+    ///
+    /// Returns `None` if the block was never received or has already been garbage collected.
+    #[must_use]
+    pub fn leaf_block(&self, leaf: &LEAF) -> Option<TYPES::BlockType> {
+        match leaf.get_deltas().try_resolve() {
+            Ok(block) => Some(block),
+            Err(deltas) => self.saved_blocks.get(deltas.block_commitment()).cloned(),
+        }
+    }
+
     /// Gets the last decided state
     /// # Panics
     /// if the last decided view's state does not exist in the state map
@@ -338,3 +735,75 @@ impl<BLOCK: Committable> BlockStore<BLOCK> {
         None
     }
 }
+
+/// Undecided transactions, in the order they arrived.
+///
+/// Each transaction is tagged with an arrival sequence number and [`iter`](Self::iter) yields the
+/// oldest transaction first, so a leader that takes a prefix of the mempool always includes older
+/// transactions ahead of newer ones and no transaction is starved.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct Mempool<TXN: Committable> {
+    /// Transactions by commitment, with their arrival sequence number
+    txns: HashMap<Commitment<TXN>, (u64, TXN)>,
+    /// Transaction commitments by arrival sequence number
+    arrivals: BTreeMap<u64, Commitment<TXN>>,
+    /// Sequence number of the next new transaction
+    next_seq: u64,
+}
+
+impl<TXN: Committable> Mempool<TXN> {
+    /// Add `txn` behind every transaction already in the mempool.
+    ///
+    /// If a transaction with this commitment is already present, it is replaced but keeps its
+    /// place in line, and the old transaction is returned.
+    pub fn insert(&mut self, commitment: Commitment<TXN>, txn: TXN) -> Option<TXN> {
+        match self.txns.entry(commitment) {
+            Entry::Occupied(mut entry) => Some(std::mem::replace(&mut entry.get_mut().1, txn)),
+            Entry::Vacant(entry) => {
+                entry.insert((self.next_seq, txn));
+                self.arrivals.insert(self.next_seq, commitment);
+                self.next_seq += 1;
+                None
+            }
+        }
+    }
+
+    /// Whether the transaction with this commitment is in the mempool.
+    #[must_use]
+    pub fn contains_key(&self, commitment: &Commitment<TXN>) -> bool {
+        self.txns.contains_key(commitment)
+    }
+
+    /// Get the transaction with this commitment, if it is in the mempool.
+    #[must_use]
+    pub fn get(&self, commitment: &Commitment<TXN>) -> Option<&TXN> {
+        self.txns.get(commitment).map(|(_, txn)| txn)
+    }
+
+    /// Number of transactions in the mempool.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.txns.len()
+    }
+
+    /// Whether the mempool is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.txns.is_empty()
+    }
+
+    /// Iterate over the transactions, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = (&Commitment<TXN>, &TXN)> {
+        self.arrivals
+            .values()
+            .map(|commitment| (commitment, &self.txns[commitment].1))
+    }
+
+    /// Keep only the transactions for which `keep` returns `true`, preserving their order.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Commitment<TXN>, &TXN) -> bool) {
+        let Self { txns, arrivals, .. } = self;
+        txns.retain(|commitment, (_, txn)| keep(commitment, txn));
+        arrivals.retain(|_, commitment| txns.contains_key(commitment));
+    }
+}
@@ -151,6 +151,65 @@ pub struct DAProposal<TYPES: NodeType> {
     pub deltas: TYPES::BlockType,
     /// View this proposal applies to
     pub view_number: TYPES::Time,
+    /// Number of transactions in `deltas`
+    pub txn_count: u32,
+    /// Serialized size of `deltas` in bytes
+    pub block_bytes: u64,
+}
+
+impl<TYPES: NodeType> DAProposal<TYPES> {
+    /// Create a proposal for `deltas`, filling in the block metadata.
+    pub fn new(deltas: TYPES::BlockType, view_number: TYPES::Time) -> Self {
+        let (txn_count, block_bytes) = Self::block_metadata(&deltas);
+        Self {
+            deltas,
+            view_number,
+            txn_count,
+            block_bytes,
+        }
+    }
+
+    /// Create a proposal for `deltas` after checking that the whole block appends to
+    /// `parent_state`, so a block that cannot extend the chain is never proposed.
+    ///
+    /// # Errors
+    /// If appending `deltas` to `parent_state` in `view_number` fails.
+    pub fn build(
+        parent_state: &TYPES::StateType,
+        deltas: TYPES::BlockType,
+        view_number: TYPES::Time,
+    ) -> Result<Self, BuildError<TYPES>> {
+        if let Err(source) = parent_state.append(&deltas, &view_number) {
+            return Err(BuildError {
+                view_number,
+                source,
+            });
+        }
+        Ok(Self::new(deltas, view_number))
+    }
+
+    /// Whether `txn_count` and `block_bytes` agree with the proposed block.
+    #[must_use]
+    pub fn metadata_matches_block(&self) -> bool {
+        Self::block_metadata(&self.deltas) == (self.txn_count, self.block_bytes)
+    }
+
+    /// Compute the transaction count and serialized size of `block`.
+    fn block_metadata(block: &TYPES::BlockType) -> (u32, u64) {
+        let txn_count = u32::try_from(block.contained_transactions().len()).unwrap_or(u32::MAX);
+        let block_bytes = bincode_opts().serialized_size(block).unwrap_or(0);
+        (txn_count, block_bytes)
+    }
+}
+
+/// A block that does not append to its parent state, as reported by [`DAProposal::build`].
+#[derive(Debug, Snafu)]
+#[snafu(display("block for view {view_number:?} does not append to its parent state: {source}"))]
+pub struct BuildError<TYPES: NodeType> {
+    /// View the block was built for
+    pub view_number: TYPES::Time,
+    /// Why the parent state rejected the block
+    pub source: <TYPES::StateType as State>::Error,
 }
 
 /// Proposal to append a block.
@@ -175,8 +234,8 @@ pub struct QuorumProposal<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> {
     /// the propser id
     pub proposer_id: EncodedPublicKey,
 
-    /// Data availibity certificate
-    // TODO We should be able to remove this
+    /// Data availibity certificate, if the leader bundled it with the proposal. Leaders normally
+    /// leave this empty and the certificate arrives separately; replicas match the two by view.
     pub dac: Option<DACertificate<TYPES>>,
 }
 
@@ -187,6 +246,13 @@ impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> ProposalType
     fn get_view_number(&self) -> <Self::NodeType as NodeType>::Time {
         self.view_number
     }
+    fn validate_well_formed(&self) -> Result<(), ProposalError> {
+        ensure!(
+            self.block_commitment == self.deltas.commit(),
+            BlockCommitmentMismatchSnafu
+        );
+        ensure_justify_qc_older(self.view_number, self.justify_qc.view_number)
+    }
 }
 
 impl<TYPES: NodeType> ProposalType for DAProposal<TYPES> {
@@ -194,6 +260,10 @@ impl<TYPES: NodeType> ProposalType for DAProposal<TYPES> {
     fn get_view_number(&self) -> <Self::NodeType as NodeType>::Time {
         self.view_number
     }
+    fn validate_well_formed(&self) -> Result<(), ProposalError> {
+        ensure!(self.metadata_matches_block(), BlockMetadataMismatchSnafu);
+        Ok(())
+    }
 }
 
 impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> ProposalType
@@ -203,6 +273,9 @@ impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> ProposalType
     fn get_view_number(&self) -> <Self::NodeType as NodeType>::Time {
         self.view_number
     }
+    fn validate_well_formed(&self) -> Result<(), ProposalError> {
+        ensure_justify_qc_older(self.view_number, self.justify_qc.view_number)
+    }
 }
 
 impl<TYPES: NodeType> ProposalType for ViewSyncCertificate<TYPES> {
@@ -214,6 +287,9 @@ impl<TYPES: NodeType> ProposalType for ViewSyncCertificate<TYPES> {
             | ViewSyncCertificate::Finalize(certificate_internal) => certificate_internal.round,
         }
     }
+    fn validate_well_formed(&self) -> Result<(), ProposalError> {
+        Ok(())
+    }
 }
 
 /// A proposal to a network of voting nodes.
@@ -225,6 +301,47 @@ pub trait ProposalType:
 
     /// Time at which this proposal is valid.
     fn get_view_number(&self) -> <Self::NodeType as NodeType>::Time;
+
+    /// Check the invariants this proposal must satisfy on its own, before any consensus state is
+    /// consulted.
+    /// # Errors
+    /// If the proposal is malformed
+    fn validate_well_formed(&self) -> Result<(), ProposalError>;
+}
+
+/// Ways in which a proposal can be malformed, as reported by
+/// [`ProposalType::validate_well_formed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Snafu)]
+pub enum ProposalError {
+    /// The block commitment does not match the proposed block
+    #[snafu(display("block commitment does not match the proposed block"))]
+    BlockCommitmentMismatch,
+    /// The transaction count or size does not match the proposed block
+    #[snafu(display("transaction count or size does not match the proposed block"))]
+    BlockMetadataMismatch,
+    /// The justifying QC is not from an earlier view than the proposal
+    #[snafu(display("justify QC for view {justify_view} does not precede proposal view {view}"))]
+    JustifyQcNotOlder {
+        /// The proposal's view
+        view: u64,
+        /// The justifying QC's view
+        justify_view: u64,
+    },
+}
+
+/// Ensure a proposal for `view` is justified by a QC from an earlier view.
+fn ensure_justify_qc_older<TIME: ConsensusTime>(
+    view: TIME,
+    justify_view: TIME,
+) -> Result<(), ProposalError> {
+    ensure!(
+        justify_view < view,
+        JustifyQcNotOlderSnafu {
+            view: *view,
+            justify_view: *justify_view,
+        }
+    );
+    Ok(())
 }
 
 /// A state change encoded in a leaf.
@@ -419,6 +536,8 @@ pub trait LeafType:
     fn fill_deltas(&mut self, block: LeafBlock<Self>) -> Result<(), LeafDeltasError<Self>>;
     /// The blockchain state after appending this leaf.
     fn get_state(&self) -> Self::MaybeState;
+    /// The blockchain state after appending this leaf, if the leaf carries it.
+    fn get_validated_state(&self) -> Option<LeafState<Self>>;
     /// Transactions rejected or invalidated by the application of this leaf.
     fn get_rejected(&self) -> Vec<LeafTransaction<Self>>;
     /// Real-world time when this leaf was created.
@@ -642,6 +761,10 @@ impl<TYPES: NodeType> LeafType for ValidatingLeaf<TYPES> {
         self.state.clone()
     }
 
+    fn get_validated_state(&self) -> Option<LeafState<Self>> {
+        Some(self.state.clone())
+    }
+
     fn get_rejected(&self) -> Vec<<TYPES::BlockType as Block>::Transaction> {
         self.rejected.clone()
     }
@@ -757,6 +880,11 @@ impl<TYPES: NodeType> LeafType for SequencingLeaf<TYPES> {
     // The Sequencing Leaf doesn't have a state.
     fn get_state(&self) -> Self::MaybeState {}
 
+    // Sequencing leaves do not carry the state their block produces.
+    fn get_validated_state(&self) -> Option<LeafState<Self>> {
+        None
+    }
+
     fn get_rejected(&self) -> Vec<<TYPES::BlockType as Block>::Transaction> {
         self.rejected.clone()
     }
@@ -814,6 +942,20 @@ pub fn random_commitment<S: Committable>(rng: &mut dyn rand::RngCore) -> Commitm
         .finalize()
 }
 
+/// An arbitrary commitment, for generating values with [`arbitrary::Arbitrary`]
+///
+/// # Errors
+/// If `u` runs out of data
+pub fn arbitrary_commitment<S: Committable>(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<Commitment<S>> {
+    let bytes: [u8; 32] = u.arbitrary()?;
+    Ok(commit::RawCommitmentBuilder::new("Arbitrary Commitment")
+        .constant_str("Arbitrary Field")
+        .var_size_bytes(&bytes)
+        .finalize())
+}
+
 /// Serialization for the QC assembled signature
 /// # Panics
 /// if serialization fails
@@ -844,7 +986,24 @@ pub fn serialize_signature<TYPES: NodeType>(signature: &AssembledSignature<TYPES
             signatures_bytes.extend("ViewSyncFinalize".as_bytes());
             Some(signatures.clone())
         }
+        AssembledSignature::Upgrade(signatures) => {
+            signatures_bytes.extend("Upgrade".as_bytes());
+            Some(signatures.clone())
+        }
+        AssembledSignature::Timeout(signatures) => {
+            signatures_bytes.extend("Timeout".as_bytes());
+            Some(signatures.clone())
+        }
         AssembledSignature::Genesis() => None,
+        AssembledSignature::Individual(kind, signatures) => {
+            signatures_bytes.extend(format!("Individual {kind:?}").as_bytes());
+            signatures_bytes.extend(
+                bincode_opts()
+                    .serialize(signatures)
+                    .expect("This serialization shouldn't be able to fail"),
+            );
+            None
+        }
     };
     if let Some(sig) = signatures {
         let (sig, proof) = TYPES::SignatureKey::get_sig_proof(&sig);
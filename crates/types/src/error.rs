@@ -3,7 +3,9 @@
 //! This module provides [`HotShotError`], which is an enum representing possible faults that can
 //! occur while interacting with this crate.
 
-use crate::traits::{node_implementation::NodeType, storage::StorageError};
+use crate::traits::{
+    election::ElectionError, node_implementation::NodeType, storage::StorageError,
+};
 use snafu::Snafu;
 use std::num::NonZeroU64;
 
@@ -50,6 +52,12 @@ pub enum HotShotError<TYPES: NodeType> {
         /// Underlying error
         source: StorageError,
     },
+    /// The node's key pair failed its self-test
+    #[snafu(display("Inconsistent key pair: {source}"))]
+    KeyPairError {
+        /// Underlying error
+        source: ElectionError,
+    },
     /// Invalid state machine state
     #[snafu(display("Invalid state machine state: {}", context))]
     InvalidState {
@@ -9,7 +9,10 @@
 )]
 #![allow(clippy::module_name_repetitions)]
 
-use std::{num::NonZeroUsize, time::Duration};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    time::Duration,
+};
 
 pub mod certificate;
 pub mod consensus;
@@ -20,6 +23,7 @@ pub mod event;
 pub mod message;
 pub mod traits;
 pub mod utils;
+pub mod verification;
 pub mod vote;
 /// the type of consensus to run. Either:
 /// wait for a signal to start a view,
@@ -65,6 +69,44 @@ pub struct HotShotConfig<K, ENTRY, ELECTIONCONFIG> {
     pub propose_min_round_time: Duration,
     /// The maximum amount of time a leader can wait to start a round
     pub propose_max_round_time: Duration,
+    /// Maximum number of views buffered in the consensus state map; `None` means unbounded
+    pub max_buffered_views: Option<NonZeroUsize>,
+    /// If waiting for transactions fails, the leader proposes an empty block after this long
+    /// instead of skipping its proposal; `None` skips the proposal
+    pub propose_empty_after: Option<Duration>,
+    /// Whether a leader whose wait for transactions fails may propose an empty block at all
+    pub allow_empty_blocks: bool,
+    /// Number of views in a row a leader may propose an empty block because its wait for
+    /// transactions failed. Past that the transaction channel is taken to be dead, and the
+    /// leader skips its proposals and reports it
+    pub max_empty_fallbacks: NonZeroUsize,
+    /// Number of threads dedicated to signature verification; `None` verifies inline
+    pub verification_threads: Option<NonZeroUsize>,
+    /// Number of vote signature checks to remember so repeated votes are not checked again;
+    /// `None` disables the cache
+    pub signature_cache_size: Option<NonZeroUsize>,
+    /// Maximum number of proposals this node assembles at once; `None` means unbounded
+    pub max_inflight_proposals: Option<NonZeroUsize>,
+    /// Number of times the leader retries a failed proposal in a view before abandoning the
+    /// view; `None` means unbounded
+    pub max_view_retries: Option<NonZeroUsize>,
+    /// How long the DA leader waits for its block to reach the availability threshold before
+    /// abandoning it; `None` waits until the view ends
+    pub da_availability_timeout: Option<Duration>,
+    /// Number of relays a view sync round tries before giving up on the round; `None` means
+    /// unbounded
+    pub max_relay_attempts: Option<NonZeroU64>,
+    /// Whether DA votes may reach the DA leader through nodes outside the DA committee, as in
+    /// relay topologies; if not, DA votes from senders outside the DA committee are dropped
+    pub relay_da_votes: bool,
+    /// Whether the network message tasks drop messages this node sent itself, as gossip networks
+    /// deliver a node's own broadcasts back to it. Leave unset if the node relies on its own
+    /// messages coming back, as it does on networks that loop back a leader's own proposal and
+    /// votes, or the transactions it submits.
+    pub drop_own_messages: bool,
+    /// Number of votes a vote collection task lets wait for it; votes past that are dropped and
+    /// counted. `None` means unbounded
+    pub vote_queue_capacity: Option<NonZeroUsize>,
     /// the election configuration
     pub election_config: Option<ELECTIONCONFIG>,
 }
@@ -48,6 +48,14 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> ViewMessage<TYPES> for Messa
     }
 }
 
+impl<TYPES: NodeType, I: NodeImplementation<TYPES>> Message<TYPES, I> {
+    /// How urgently this message should be delivered, derived from its kind
+    #[must_use]
+    pub fn priority(&self) -> MessagePriority {
+        self.kind.priority()
+    }
+}
+
 /// A wrapper type for implementing `PassType` on a vector of `Message`.
 #[derive(Clone, Debug)]
 pub struct Messages<TYPES: NodeType, I: NodeImplementation<TYPES>>(pub Vec<Message<TYPES, I>>);
@@ -71,6 +79,35 @@ pub enum MessagePurpose {
     Data,
 }
 
+impl MessagePurpose {
+    /// The delivery priority of messages with this purpose: view sync and timeouts are `High`,
+    /// the consensus and DA protocol messages are `Medium`, and transactions are `Low`.
+    #[must_use]
+    pub fn priority(self) -> MessagePriority {
+        match self {
+            MessagePurpose::ViewSyncVote
+            | MessagePurpose::ViewSyncProposal
+            | MessagePurpose::Internal => MessagePriority::High,
+            MessagePurpose::Proposal | MessagePurpose::Vote | MessagePurpose::DAC => {
+                MessagePriority::Medium
+            }
+            MessagePurpose::Data => MessagePriority::Low,
+        }
+    }
+}
+
+/// How urgently a message should be delivered when the network has to choose between messages.
+/// Ordered from least to most urgent.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum MessagePriority {
+    /// Transactions, which can wait for consensus traffic
+    Low,
+    /// Proposals, votes and certificates of the consensus and DA protocols
+    Medium,
+    /// View sync and timeout messages, which are needed to recover progress
+    High,
+}
+
 // TODO (da) make it more customized to the consensus layer, maybe separating the specific message
 // data from the kind enum.
 /// Enum representation of any message type
@@ -92,6 +129,18 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>> MessageKind<TYPES, I> {
     pub fn from_consensus_message(m: I::ConsensusMessage) -> Self {
         Self::Consensus(m)
     }
+
+    /// How urgently this message should be delivered, see [`MessagePurpose::priority`]
+    ///
+    /// [`MessageKind::_Unreachable`] has no purpose, but it can still arrive off the wire, so it
+    /// gets the lowest priority rather than a panic.
+    #[must_use]
+    pub fn priority(&self) -> MessagePriority {
+        match self {
+            MessageKind::_Unreachable(_) => MessagePriority::Low,
+            _ => self.purpose().priority(),
+        }
+    }
 }
 
 impl<TYPES: NodeType, I: NodeImplementation<TYPES>> From<DataMessage<TYPES>>
@@ -180,8 +229,6 @@ where
     I::Exchanges: ExchangesType<TYPES, I::Leaf, Message<TYPES, I>>,
 {
     /// Create a [`ProcessedGeneralConsensusMessage`] from a [`GeneralConsensusMessage`].
-    /// # Panics
-    /// if reaching the unimplemented `ViewSync` case.
     pub fn new(value: GeneralConsensusMessage<TYPES, I>, sender: TYPES::SignatureKey) -> Self {
         match value {
             GeneralConsensusMessage::Proposal(p) => {
@@ -191,8 +238,31 @@ where
             GeneralConsensusMessage::InternalTrigger(a) => {
                 ProcessedGeneralConsensusMessage::InternalTrigger(a)
             }
-            GeneralConsensusMessage::ViewSyncVote(_)
-            | GeneralConsensusMessage::ViewSyncCertificate(_) => todo!(),
+            GeneralConsensusMessage::ViewSyncVote(vote) => {
+                ProcessedGeneralConsensusMessage::ViewSyncVote(vote)
+            }
+            GeneralConsensusMessage::ViewSyncCertificate(certificate) => {
+                ProcessedGeneralConsensusMessage::ViewSyncCertificate(certificate)
+            }
+        }
+    }
+
+    /// The view the message was sent in, or the view of the timeout.
+    ///
+    /// Agrees with [`ConsensusMessageType::view_number`] on the unprocessed message, without
+    /// having to clone and convert it first.
+    #[must_use]
+    pub fn view_number(&self) -> TYPES::Time {
+        match self {
+            ProcessedGeneralConsensusMessage::Proposal(p, _) => p.data.get_view_number(),
+            ProcessedGeneralConsensusMessage::Vote(vote_message, _) => vote_message.current_view(),
+            ProcessedGeneralConsensusMessage::InternalTrigger(trigger) => match trigger {
+                InternalTrigger::Timeout(time) => *time,
+            },
+            ProcessedGeneralConsensusMessage::ViewSyncVote(message) => message.round(),
+            ProcessedGeneralConsensusMessage::ViewSyncCertificate(message) => {
+                message.data.get_view_number()
+            }
         }
     }
 }
@@ -242,6 +312,21 @@ impl<TYPES: NodeType> ProcessedCommitteeConsensusMessage<TYPES> {
             }
         }
     }
+
+    /// The view the message was sent in.
+    ///
+    /// Agrees with [`ConsensusMessageType::view_number`] on the unprocessed message, without
+    /// having to clone and convert it first.
+    #[must_use]
+    pub fn view_number(&self) -> TYPES::Time {
+        match self {
+            ProcessedCommitteeConsensusMessage::DAProposal(p, _) => p.data.get_view_number(),
+            ProcessedCommitteeConsensusMessage::DAVote(vote_message, _) => {
+                vote_message.current_view()
+            }
+            ProcessedCommitteeConsensusMessage::DACertificate(cert, _) => cert.view_number,
+        }
+    }
 }
 
 /// A processed consensus message for sequencing consensus.
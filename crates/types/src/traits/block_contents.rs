@@ -3,6 +3,7 @@
 //! This module provides the [`Block`] trait, which describes the behaviors that a block is
 //! expected to have.
 
+use crate::traits::State;
 use commit::{Commitment, Committable};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
@@ -44,6 +45,14 @@ pub trait Block:
     /// Construct an empty or genesis block.
     fn new() -> Self;
 
+    /// The block without transactions that extends `parent_state`, e.g. to keep views moving when
+    /// there are no transactions to propose.
+    ///
+    /// Empty blocks are ordinary blocks: states must append them like any other.
+    fn empty<STATE: State<BlockType = Self>>(parent_state: &STATE) -> Self {
+        STATE::next_block(Some(parent_state.clone()))
+    }
+
     /// Attempts to add a transaction, returning an Error if it would result in a structurally
     /// invalid block
     ///
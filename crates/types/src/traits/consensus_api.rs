@@ -1,13 +1,15 @@
 //! Contains the [`SequencingConsensusApi`] and [`ValidatingConsensusApi`] traits.
 
 use crate::{
-    certificate::QuorumCertificate,
+    certificate::{check_individual_signatures, QuorumCertificate},
+    consensus::ProposalSlots,
     data::{LeafType, ProposalType},
     error::HotShotError,
     event::{Event, EventType},
     message::{DataMessage, SequencingMessage},
     traits::{
-        network::NetworkError,
+        election::ConsensusExchange,
+        network::{NetworkError, NetworkMsg},
         node_implementation::{NodeImplementation, NodeType},
         signature_key::SignatureKey,
         storage::StorageError,
@@ -15,8 +17,45 @@ use crate::{
     vote::VoteType,
 };
 use async_trait::async_trait;
+use bincode::Options;
+use bitvec::bitvec;
+use ethereum_types::U256;
+use hotshot_utils::bincode::bincode_opts;
 
-use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The message signed by [`ConsensusSharedApi::sign_and_verify_self_test`]
+pub const SELF_TEST_MESSAGE: &[u8] = b"HotShot key self test";
+
+/// The stake the single-node certificate of a self test needs; the node holds a stake of 1
+const SELF_TEST_THRESHOLD: NonZeroU64 = match NonZeroU64::new(1) {
+    Some(threshold) => threshold,
+    None => unreachable!(),
+};
+
+/// The outcome of each step of [`ConsensusSharedApi::sign_and_verify_self_test`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfTestReport {
+    /// The exchange's public key verifies a vote its signer signed
+    pub signature_verified: bool,
+    /// A certificate holding only the signer's signature of [`SELF_TEST_MESSAGE`] could be
+    /// assembled
+    pub certificate_assembled: bool,
+    /// The certificate verifies against a stake table holding only the node
+    pub certificate_verified: bool,
+}
+
+impl SelfTestReport {
+    /// Whether every step passed
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.signature_verified && self.certificate_assembled && self.certificate_verified
+    }
+}
 
 /// The API that [`HotStuff`] needs to talk to the system, implemented for both validating and
 /// sequencing consensus.
@@ -37,6 +76,29 @@ pub trait ConsensusSharedApi<
     /// If this time is reached, the leader has to send a propose without transactions.
     fn propose_max_round_time(&self) -> Duration;
 
+    /// The maximum amount of time a leader of a committee of `committee_size` nodes waits for
+    /// transactions before proposing.
+    ///
+    /// Defaults to [`propose_max_round_time`](Self::propose_max_round_time) for any size;
+    /// networks spread over a large diameter can allow more time for larger committees.
+    fn round_time_for_committee_size(&self, _committee_size: usize) -> Duration {
+        self.propose_max_round_time()
+    }
+
+    /// How long the leader waits before proposing an empty block when it could not wait for
+    /// transactions, or `None` if it should skip the proposal instead.
+    fn propose_empty_after(&self) -> Option<Duration>;
+
+    /// Limits how many proposals this node assembles at once
+    fn proposal_slots(&self) -> &ProposalSlots;
+
+    /// The current time, as seen by the round timers.
+    ///
+    /// Defaults to the wall clock; tests override it to drive the timers deterministically.
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
     /// Store a leaf in the storage
     async fn store_leaf(
         &self,
@@ -50,6 +112,20 @@ pub trait ConsensusSharedApi<
     /// Returns the minimum transactions that must be in a block
     fn min_transactions(&self) -> usize;
 
+    /// Returns the minimum transactions the leader should wait for, given the recent
+    /// transaction arrival rate in transactions per second.
+    fn min_transactions_for(&self, _recent_rate: f64) -> usize {
+        self.min_transactions()
+    }
+
+    /// Number of transactions in the mempool that the parent of the next proposal does not
+    /// already include
+    async fn pending_transaction_count(&self) -> usize;
+
+    /// Total serialized size, in bytes, of the transactions counted by
+    /// [`pending_transaction_count`](Self::pending_transaction_count)
+    async fn pending_transaction_bytes(&self) -> u64;
+
     /// Returns `true` if hotstuff should start the given round. A round can also be started manually by sending `NewView` to the leader.
     ///
     /// In production code this should probably always return `true`.
@@ -64,6 +140,58 @@ pub trait ConsensusSharedApi<
     /// Get a reference to the private key.
     fn private_key(&self) -> &<TYPES::SignatureKey as SignatureKey>::PrivateKey;
 
+    /// Check that the key pair `exchange` signs with works before the node joins consensus.
+    ///
+    /// Checks the key pair with [`ConsensusExchange::validate_keys`], then signs
+    /// [`SELF_TEST_MESSAGE`] through the exchange's signer, assembles the signature into a
+    /// certificate of a one-node committee and validates that. Every step runs even if an earlier
+    /// one fails, so the report shows where the key material breaks.
+    fn sign_and_verify_self_test<M: NetworkMsg, EXCHANGE: ConsensusExchange<TYPES, M>>(
+        &self,
+        exchange: &EXCHANGE,
+    ) -> SelfTestReport {
+        let signature_verified = exchange.validate_keys().is_ok();
+
+        let key = exchange.public_key();
+        let signature = exchange.signer().sign(SELF_TEST_MESSAGE);
+        let stake_table = vec![key.get_stake_table_entry(1u64)];
+        let (certificate_assembled, certificate_verified) =
+            if TYPES::SignatureKey::supports_aggregation() {
+                match bincode_opts().deserialize(&signature.0) {
+                    Ok(sig) => {
+                        let real_qc_pp = TYPES::SignatureKey::get_public_parameter(
+                            stake_table,
+                            U256::from(SELF_TEST_THRESHOLD.get()),
+                        );
+                        let qc = TYPES::SignatureKey::assemble(&real_qc_pp, &bitvec![1; 1], &[sig]);
+                        (
+                            true,
+                            TYPES::SignatureKey::check(&real_qc_pp, SELF_TEST_MESSAGE, &qc),
+                        )
+                    }
+                    Err(_) => (false, false),
+                }
+            } else {
+                let signatures = [(key.to_bytes(), signature)];
+                (
+                    true,
+                    check_individual_signatures::<TYPES>(
+                        exchange.verification_pool(),
+                        &signatures,
+                        &stake_table,
+                        SELF_TEST_THRESHOLD,
+                        SELF_TEST_MESSAGE,
+                    ),
+                )
+            };
+
+        SelfTestReport {
+            signature_verified,
+            certificate_assembled,
+            certificate_verified,
+        }
+    }
+
     // Utility functions
 
     /// notifies client of an error
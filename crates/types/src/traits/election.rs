@@ -5,13 +5,14 @@
 
 use super::{
     node_implementation::{NodeImplementation, NodeType},
-    signature_key::{EncodedPublicKey, EncodedSignature},
+    signature_key::{EncodedPublicKey, EncodedSignature, InMemorySigner, Signer},
 };
 use crate::{
     certificate::{
-        AssembledSignature, DACertificate, QuorumCertificate, ViewSyncCertificate, VoteMetaData,
+        check_individual_signatures, AssembledSignature, AssembledSignatureKind, DACertificate,
+        QuorumCertificate, TimeoutCertificate, ViewSyncCertificate, VoteMetaData,
     },
-    data::{DAProposal, ProposalType},
+    data::{arbitrary_commitment, DAProposal, ProposalType},
 };
 
 use crate::{
@@ -20,16 +21,19 @@ use crate::{
 };
 
 use crate::{
+    consensus::ViewSyncMetrics,
     data::LeafType,
     traits::{
+        metrics::NoMetrics,
         network::{CommunicationChannel, NetworkMsg},
         node_implementation::ExchangesType,
         signature_key::SignatureKey,
         state::ConsensusTime,
     },
+    verification::{SignatureCache, VerificationPool},
     vote::{
-        Accumulator, DAVote, QuorumVote, TimeoutVote, ViewSyncData, ViewSyncVote, VoteAccumulator,
-        VoteType, YesOrNoVote,
+        Accumulator, DAVote, QuorumVote, TimeoutVote, ViewSyncData, ViewSyncPhase, ViewSyncVote,
+        VoteAccumulator, VoteType, YesOrNoVote,
     },
 };
 use bincode::Options;
@@ -40,7 +44,9 @@ use ethereum_types::U256;
 use hotshot_utils::bincode::bincode_opts;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
-use std::{collections::BTreeSet, fmt::Debug, hash::Hash, marker::PhantomData, num::NonZeroU64};
+use std::{
+    collections::BTreeSet, fmt::Debug, hash::Hash, marker::PhantomData, num::NonZeroU64, sync::Arc,
+};
 use tracing::error;
 
 /// Error for election problems
@@ -52,6 +58,17 @@ pub enum ElectionError {
     /// NOTE: it would be better to make Election polymorphic over
     /// the election error and then have specific math errors
     MathError,
+    /// The DA committee is configured to be larger than the full membership
+    DACommitteeTooLarge {
+        /// Configured size of the DA committee
+        da_committee_size: usize,
+        /// Size of the full membership
+        num_nodes: u64,
+    },
+    /// The node's private key does not produce signatures its public key accepts
+    InconsistentKeyPair,
+    /// The committee's stakes sum to more than fits in a `u64`
+    StakeOverflow,
 }
 
 /// For items that will always have the same validity outcome on a successful check,
@@ -69,6 +86,15 @@ pub enum Checked<T> {
     Unchecked(T),
 }
 
+/// The chain id used when the election config does not set one.
+pub const DEFAULT_CHAIN_ID: u64 = 0;
+
+/// The largest relay index a view sync certificate may name.
+///
+/// Replicas move to the next relay on every view sync timeout, so honest relay indices can pass
+/// the committee size, but never by this much within one round.
+pub const MAX_VIEW_SYNC_RELAY: u64 = 1 << 16;
+
 /// Data to vote on for different types of votes.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(bound(deserialize = ""))]
@@ -87,50 +113,158 @@ pub enum VoteData<COMMITTABLE: Committable + Serialize + Clone> {
     ViewSyncCommit(Commitment<COMMITTABLE>),
     /// Vote to finalize the view sync.
     ViewSyncFinalize(Commitment<COMMITTABLE>),
+    /// Vote to adopt a protocol upgrade.
+    Upgrade(Commitment<COMMITTABLE>),
+}
+
+impl<'a, COMMITTABLE: Committable + Serialize + Clone> arbitrary::Arbitrary<'a>
+    for VoteData<COMMITTABLE>
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let commitment = arbitrary_commitment(u)?;
+        Ok(match u.int_in_range(0..=7u8)? {
+            0 => VoteData::DA(commitment),
+            1 => VoteData::Yes(commitment),
+            2 => VoteData::No(commitment),
+            3 => VoteData::Timeout(commitment),
+            4 => VoteData::ViewSyncPreCommit(commitment),
+            5 => VoteData::ViewSyncCommit(commitment),
+            6 => VoteData::ViewSyncFinalize(commitment),
+            _ => VoteData::Upgrade(commitment),
+        })
+    }
 }
 
 /// Make different types of `VoteData` committable
 impl<COMMITTABLE: Committable + Serialize + Clone> Committable for VoteData<COMMITTABLE> {
+    fn commit(&self) -> Commitment<Self> {
+        self.commit_for_chain(DEFAULT_CHAIN_ID)
+    }
+
+    fn tag() -> String {
+        ("VOTE_DATA_COMMIT").to_string()
+    }
+}
+
+/// Data a leader signs when proposing, kept in a separate signing domain from [`VoteData`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(bound(deserialize = ""))]
+pub enum ProposalData<COMMITTABLE: Committable + Serialize + Clone> {
+    /// Proposal of a block to the DA committee.
+    DA(Commitment<COMMITTABLE>),
+    /// Proposal of a leaf to the quorum.
+    Quorum(Commitment<COMMITTABLE>),
+}
+
+/// Make different types of `ProposalData` committable
+impl<COMMITTABLE: Committable + Serialize + Clone> Committable for ProposalData<COMMITTABLE> {
     fn commit(&self) -> Commitment<Self> {
         match self {
+            ProposalData::DA(block_commitment) => {
+                commit::RawCommitmentBuilder::new("DA Proposal Commit")
+                    .field("block_commitment", *block_commitment)
+                    .finalize()
+            }
+            ProposalData::Quorum(leaf_commitment) => {
+                commit::RawCommitmentBuilder::new("Quorum Proposal Commit")
+                    .field("leaf_commitment", *leaf_commitment)
+                    .finalize()
+            }
+        }
+    }
+
+    fn tag() -> String {
+        ("PROPOSAL_DATA_COMMIT").to_string()
+    }
+}
+
+impl<COMMITTABLE: Committable + Serialize + Clone> VoteData<COMMITTABLE> {
+    /// Commitment to this vote data on the chain `chain_id`, which is what votes sign.
+    ///
+    /// Mixing the chain id in keeps a vote or certificate from one chain from validating on
+    /// another.
+    ///
+    /// The tags and field names are part of the protocol: changing one is a breaking change that
+    /// invalidates every existing vote signature. They are pinned by golden vectors in the
+    /// testing crate's `vote_commitments` test.
+    #[must_use]
+    pub fn commit_for_chain(&self, chain_id: u64) -> Commitment<Self> {
+        let builder = match self {
             VoteData::DA(block_commitment) => commit::RawCommitmentBuilder::new("DA Block Commit")
-                .field("block_commitment", *block_commitment)
-                .finalize(),
+                .field("block_commitment", *block_commitment),
             VoteData::Yes(leaf_commitment) => commit::RawCommitmentBuilder::new("Yes Vote Commit")
-                .field("leaf_commitment", *leaf_commitment)
-                .finalize(),
+                .field("leaf_commitment", *leaf_commitment),
             VoteData::No(leaf_commitment) => commit::RawCommitmentBuilder::new("No Vote Commit")
-                .field("leaf_commitment", *leaf_commitment)
-                .finalize(),
+                .field("leaf_commitment", *leaf_commitment),
             VoteData::Timeout(view_number_commitment) => {
                 commit::RawCommitmentBuilder::new("Timeout View Number Commit")
                     .field("view_number_commitment", *view_number_commitment)
-                    .finalize()
             }
             VoteData::ViewSyncPreCommit(commitment) => {
                 commit::RawCommitmentBuilder::new("ViewSyncPreCommit")
                     .field("commitment", *commitment)
-                    .finalize()
             }
             VoteData::ViewSyncCommit(commitment) => {
-                commit::RawCommitmentBuilder::new("ViewSyncCommit")
-                    .field("commitment", *commitment)
-                    .finalize()
+                commit::RawCommitmentBuilder::new("ViewSyncCommit").field("commitment", *commitment)
             }
             VoteData::ViewSyncFinalize(commitment) => {
                 commit::RawCommitmentBuilder::new("ViewSyncFinalize")
                     .field("commitment", *commitment)
-                    .finalize()
             }
+            VoteData::Upgrade(upgrade_commitment) => {
+                commit::RawCommitmentBuilder::new("Upgrade Vote Commit")
+                    .field("upgrade_commitment", *upgrade_commitment)
+            }
+        };
+        builder.u64_field("chain_id", chain_id).finalize()
+    }
+
+    /// The kind of certificate votes with this data assemble into.
+    #[must_use]
+    pub fn expected_signature_kind(&self) -> AssembledSignatureKind {
+        match self {
+            VoteData::DA(_) => AssembledSignatureKind::DA,
+            VoteData::Yes(_) => AssembledSignatureKind::Yes,
+            VoteData::No(_) => AssembledSignatureKind::No,
+            VoteData::Timeout(_) => AssembledSignatureKind::Timeout,
+            VoteData::ViewSyncPreCommit(_) => AssembledSignatureKind::ViewSyncPreCommit,
+            VoteData::ViewSyncCommit(_) => AssembledSignatureKind::ViewSyncCommit,
+            VoteData::ViewSyncFinalize(_) => AssembledSignatureKind::ViewSyncFinalize,
+            VoteData::Upgrade(_) => AssembledSignatureKind::Upgrade,
         }
     }
 
-    fn tag() -> String {
-        ("VOTE_DATA_COMMIT").to_string()
+    /// Whether a certificate with signatures of kind `kind` may be checked against this vote
+    /// data, logging the mismatch if not.
+    #[must_use]
+    pub fn matches_signature_kind(&self, kind: AssembledSignatureKind) -> bool {
+        if self.expected_signature_kind() == kind {
+            true
+        } else {
+            error!(
+                "Certificate signatures of kind {:?} checked against {:?} vote data",
+                kind,
+                self.expected_signature_kind()
+            );
+            false
+        }
+    }
+
+    /// The commitment this vote data is on, whatever kind of vote it is.
+    #[must_use]
+    pub fn commitment(&self) -> Commitment<COMMITTABLE> {
+        match self {
+            VoteData::DA(commitment)
+            | VoteData::Yes(commitment)
+            | VoteData::No(commitment)
+            | VoteData::Timeout(commitment)
+            | VoteData::ViewSyncPreCommit(commitment)
+            | VoteData::ViewSyncCommit(commitment)
+            | VoteData::ViewSyncFinalize(commitment)
+            | VoteData::Upgrade(commitment) => *commitment,
+        }
     }
-}
 
-impl<COMMITTABLE: Committable + Serialize + Clone> VoteData<COMMITTABLE> {
     #[must_use]
     /// Convert vote data into bytes.
     ///
@@ -160,6 +294,23 @@ pub trait VoteToken:
 
     /// the count, which validation will confirm
     fn vote_count(&self) -> NonZeroU64;
+
+    /// The exact weight this token carries when votes are accumulated.
+    ///
+    /// Defaults to [`VoteToken::vote_count`]; tokens weighted by stake that may not fit in a
+    /// `u64` should override it.
+    fn vote_weight(&self) -> U256 {
+        U256::from(self.vote_count().get())
+    }
+
+    /// Whether this token carries no voting weight and must not be accumulated.
+    ///
+    /// `vote_count` is non-zero by construction, so the default only returns `true` if that
+    /// invariant is broken. Token types that can be structurally malformed (e.g. deserialized
+    /// from the wire without a proof) should override this to report them as zero weight.
+    fn is_zero_weight(&self) -> bool {
+        self.vote_count().get() == 0
+    }
 }
 
 /// election config
@@ -172,6 +323,13 @@ pub trait ElectionConfig:
     + Send
     + core::fmt::Debug
 {
+    /// The configuration the DA committee should be elected with.
+    ///
+    /// Defaults to this configuration, i.e. the DA committee is the full membership.
+    #[must_use]
+    fn da_committee_config(&self) -> Self {
+        self.clone()
+    }
 }
 
 /// A certificate of some property which has been signed by a quroum of nodes.
@@ -217,6 +375,13 @@ pub trait Membership<TYPES: NodeType>:
     /// generate a default election configuration
     fn default_election_config(num_nodes: u64) -> TYPES::ElectionConfigType;
 
+    /// generate an election configuration where node `i` holds `stakes[i]`
+    ///
+    /// Memberships that do not weight their election by stake ignore the distribution.
+    fn stake_weighted_election_config(stakes: &[u64]) -> TYPES::ElectionConfigType {
+        Self::default_election_config(stakes.len() as u64)
+    }
+
     /// create an election
     /// TODO may want to move this to a testableelection trait
     fn create_election(
@@ -225,14 +390,73 @@ pub trait Membership<TYPES: NodeType>:
         config: TYPES::ElectionConfigType,
     ) -> Self;
 
+    /// Serializable form of the computed election state, see [`Membership::snapshot`]
+    type Snapshot: Clone + Debug + Serialize + for<'de> Deserialize<'de> + Send + Sync;
+
+    /// Capture the computed election state, so a restarting node can [`Membership::restore`] it
+    /// instead of running [`Membership::create_election`] again.
+    fn snapshot(&self) -> Self::Snapshot;
+
+    /// Rebuild a membership from a [`Membership::snapshot`]. It elects the same leaders and
+    /// committees as the membership the snapshot was taken from.
+    fn restore(snapshot: Self::Snapshot) -> Self;
+
+    /// A digest of the canonical stake table and configuration of this membership.
+    ///
+    /// Memberships that elect the same leaders with the same thresholds have the same digest, so
+    /// nodes can gossip digests to catch misconfiguration early.
+    /// # Panics
+    /// If the snapshot cannot be serialized
+    fn committee_digest(&self) -> [u8; 32] {
+        let bytes = bincode_opts()
+            .serialize(&self.snapshot())
+            .expect("Membership snapshots should always serialize");
+        *blake3::hash(&bytes).as_bytes()
+    }
+
+    /// Whether a peer reporting `peer_digest` from [`Membership::committee_digest`] is configured
+    /// with the same committee as this node.
+    fn verify_committee_consistency(&self, peer_digest: &[u8; 32]) -> bool {
+        self.committee_digest() == *peer_digest
+    }
+
     /// Clone the public key and corresponding stake table for current elected committee
     fn get_committee_qc_stake_table(
         &self,
     ) -> Vec<<TYPES::SignatureKey as SignatureKey>::StakeTableEntry>;
 
+    /// The public key and stake table of the committee that was elected for view `view_number`.
+    ///
+    /// Memberships whose committee never changes return the current stake table.
+    fn get_committee_qc_stake_table_for_view(
+        &self,
+        _view_number: TYPES::Time,
+    ) -> Vec<<TYPES::SignatureKey as SignatureKey>::StakeTableEntry> {
+        self.get_committee_qc_stake_table()
+    }
+
     /// The leader of the committee for view `view_number`.
     fn get_leader(&self, view_number: TYPES::Time) -> TYPES::SignatureKey;
 
+    /// The distinct leaders of the `count` views starting at `from`, in the order they first lead.
+    /// Views past the largest view number are left out.
+    ///
+    /// The network can connect to them ahead of time, so votes sent to them go over a warm
+    /// connection.
+    fn upcoming_leaders(&self, from: TYPES::Time, count: usize) -> Vec<TYPES::SignatureKey> {
+        let mut leaders: Vec<TYPES::SignatureKey> = Vec::with_capacity(count);
+        for offset in 0..count as u64 {
+            let Some(view) = from.checked_add(offset) else {
+                break;
+            };
+            let leader = self.get_leader(view);
+            if !leaders.contains(&leader) {
+                leaders.push(leader);
+            }
+        }
+        leaders
+    }
+
     /// The members of the committee for view `view_number`.
     fn get_committee(&self, view_number: TYPES::Time) -> BTreeSet<TYPES::SignatureKey>;
 
@@ -263,8 +487,25 @@ pub trait Membership<TYPES: NodeType>:
     /// Returns the threshold for a specific `Membership` implementation
     fn success_threshold(&self) -> NonZeroU64;
 
+    /// Returns the success threshold of the committee that was elected for view `view_number`
+    fn success_threshold_for_view(&self, _view_number: TYPES::Time) -> NonZeroU64 {
+        self.success_threshold()
+    }
+
     /// Returns the threshold for a specific `Membership` implementation
     fn failure_threshold(&self) -> NonZeroU64;
+
+    /// The stake a view sync certificate for `phase` needs; see [`ViewSyncPhase::threshold`].
+    fn effective_threshold_for_phase(&self, phase: ViewSyncPhase) -> NonZeroU64 {
+        phase.threshold(self.success_threshold(), self.failure_threshold())
+    }
+
+    /// The chain this membership votes on, mixed into every vote signature.
+    ///
+    /// Memberships that are never deployed alongside another chain use [`DEFAULT_CHAIN_ID`].
+    fn chain_id(&self) -> u64 {
+        DEFAULT_CHAIN_ID
+    }
 }
 
 /// Protocol for exchanging proposals and votes to make decisions in a distributed network.
@@ -302,6 +543,24 @@ pub trait ConsensusExchange<TYPES: NodeType, M: NetworkMsg>: Send + Sync {
     /// The network being used by this exchange.
     fn network(&self) -> &Self::Networking;
 
+    /// Where this exchange runs signature checks.
+    fn verification_pool(&self) -> &VerificationPool;
+
+    /// Run this exchange's signature checks on `pool` from now on.
+    fn set_verification_pool(&mut self, pool: VerificationPool);
+
+    /// The cache of vote signature checks, if this exchange has one.
+    fn signature_cache(&self) -> Option<&SignatureCache>;
+
+    /// Remember vote signature checks in `cache` from now on, or stop caching if `None`.
+    fn set_signature_cache(&mut self, cache: Option<SignatureCache>);
+
+    /// The view the chain starts at, which is the only view a genesis certificate is valid for.
+    fn genesis_view(&self) -> TYPES::Time;
+
+    /// Accept genesis certificates for `view` instead from now on.
+    fn set_genesis_view(&mut self, view: TYPES::Time);
+
     /// The leader of the [`Membership`](Self::Membership) at time `view_number`.
     fn get_leader(&self, view_number: TYPES::Time) -> TYPES::SignatureKey {
         self.membership().get_leader(view_number)
@@ -339,12 +598,35 @@ pub trait ConsensusExchange<TYPES: NodeType, M: NetworkMsg>: Send + Sync {
             .make_vote_token(view_number, self.private_key())
     }
 
-    /// The contents of a vote on `commit`.
-    fn vote_data(&self, commit: Commitment<Self::Commitment>) -> VoteData<Self::Commitment>;
+    /// The contents of a vote on `commit`, or `None` if this exchange's votes depend on more than
+    /// the commitment, as view sync votes depend on their phase.
+    fn vote_data(&self, commit: Commitment<Self::Commitment>)
+        -> Option<VoteData<Self::Commitment>>;
+
+    /// Check that this participant's private key signs votes its public key accepts, by signing
+    /// and verifying a dummy vote.
+    ///
+    /// # Errors
+    /// [`ElectionError::InconsistentKeyPair`] if the signature does not verify
+    fn validate_keys(&self) -> Result<(), ElectionError> {
+        let dummy = commit::RawCommitmentBuilder::new("Key pair self-test").finalize();
+        // Any kind of vote exercises the key pair, so exchanges without a single kind of vote
+        // sign a view sync pre-commit.
+        let vote = self
+            .vote_data(dummy)
+            .unwrap_or(VoteData::ViewSyncPreCommit(dummy))
+            .commit_for_chain(self.membership().chain_id());
+        let signature = self.signer().sign(vote.as_ref());
+        if self.public_key().validate(&signature, vote.as_ref()) {
+            Ok(())
+        } else {
+            Err(ElectionError::InconsistentKeyPair)
+        }
+    }
 
     /// Validate a QC.
     fn is_valid_cert(&self, qc: &Self::Certificate, commit: Commitment<Self::Commitment>) -> bool {
-        if qc.is_genesis() && qc.view_number() == TYPES::Time::genesis() {
+        if qc.is_genesis() && qc.view_number() == self.genesis_view() {
             return true;
         }
         let leaf_commitment = qc.leaf_commitment();
@@ -354,39 +636,83 @@ pub trait ConsensusExchange<TYPES: NodeType, M: NetworkMsg>: Send + Sync {
             return false;
         }
 
-        match qc.signatures() {
-            AssembledSignature::DA(qc) => {
-                let real_commit = VoteData::DA(leaf_commitment).commit();
-                let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
-                    self.membership().get_committee_qc_stake_table(),
-                    U256::from(self.membership().success_threshold().get()),
-                );
-                <TYPES::SignatureKey as SignatureKey>::check(&real_qc_pp, real_commit.as_ref(), &qc)
-            }
-            AssembledSignature::Yes(qc) => {
-                let real_commit = VoteData::Yes(leaf_commitment).commit();
-                let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
-                    self.membership().get_committee_qc_stake_table(),
-                    U256::from(self.membership().success_threshold().get()),
-                );
-                <TYPES::SignatureKey as SignatureKey>::check(&real_qc_pp, real_commit.as_ref(), &qc)
-            }
-            AssembledSignature::No(qc) => {
-                let real_commit = VoteData::No(leaf_commitment).commit();
-                let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
-                    self.membership().get_committee_qc_stake_table(),
-                    U256::from(self.membership().success_threshold().get()),
-                );
-                <TYPES::SignatureKey as SignatureKey>::check(&real_qc_pp, real_commit.as_ref(), &qc)
-            }
-            AssembledSignature::Genesis() => true,
-            AssembledSignature::ViewSyncPreCommit(_)
-            | AssembledSignature::ViewSyncCommit(_)
-            | AssembledSignature::ViewSyncFinalize(_) => {
-                error!("QC should not be ViewSync type here");
-                false
-            }
+        // Check against the stake table in force when the certificate was formed, which may
+        // differ from the current one.
+        let view_number = qc.view_number();
+
+        let signatures = qc.signatures();
+        if signatures.is_genesis() {
+            error!("Genesis certificate is only valid at the genesis view");
+            return false;
         }
+        let Some(vote_data) = self.vote_data(leaf_commitment) else {
+            error!(
+                "This exchange has no single kind of vote to check {:?} signatures against",
+                signatures.kind()
+            );
+            return false;
+        };
+        if !vote_data.matches_signature_kind(signatures.kind()) {
+            return false;
+        }
+        let real_commit = vote_data.commit_for_chain(self.membership().chain_id());
+        self.is_valid_signatures(signatures, view_number, real_commit.as_ref())
+    }
+
+    /// Validate an upgrade certificate on `commit`, formed by the members of this exchange.
+    ///
+    /// Upgrade certificates are checked apart from [`is_valid_cert`](Self::is_valid_cert), so
+    /// that upgrade signatures never pass as this exchange's own certificates.
+    fn is_valid_upgrade_cert(
+        &self,
+        qc: &Self::Certificate,
+        commit: Commitment<Self::Commitment>,
+    ) -> bool {
+        if qc.leaf_commitment() != commit {
+            error!("Upgrade commitment does not equal the expected commitment");
+            return false;
+        }
+        let signatures = qc.signatures();
+        let vote_data = VoteData::Upgrade(commit);
+        if !vote_data.matches_signature_kind(signatures.kind()) {
+            return false;
+        }
+        let real_commit = vote_data.commit_for_chain(self.membership().chain_id());
+        self.is_valid_signatures(signatures, qc.view_number(), real_commit.as_ref())
+    }
+
+    /// Check that `signatures` sign `data` with at least the success threshold of the stake
+    /// table in force at `view_number`.
+    #[doc(hidden)]
+    fn is_valid_signatures(
+        &self,
+        signatures: AssembledSignature<TYPES>,
+        view_number: TYPES::Time,
+        data: &[u8],
+    ) -> bool {
+        let stake_table = self
+            .membership()
+            .get_committee_qc_stake_table_for_view(view_number);
+        let threshold = self.membership().success_threshold_for_view(view_number);
+        if let AssembledSignature::Individual(_, individual) = &signatures {
+            return check_individual_signatures::<TYPES>(
+                self.verification_pool(),
+                individual,
+                &stake_table,
+                threshold,
+                data,
+            );
+        }
+        let Some(qc) = signatures.into_qc() else {
+            error!("Certificate carries no assembled signatures to check");
+            return false;
+        };
+        let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
+            stake_table,
+            U256::from(threshold.get()),
+        );
+        self.verification_pool()
+            .run(|| <TYPES::SignatureKey as SignatureKey>::check(&real_qc_pp, data, &qc))
     }
 
     /// Validate a vote by checking its signature and token.
@@ -400,7 +726,16 @@ pub trait ConsensusExchange<TYPES: NodeType, M: NetworkMsg>: Send + Sync {
         let mut is_valid_vote_token = false;
         let mut is_valid_signature = false;
         if let Some(key) = <TYPES::SignatureKey as SignatureKey>::from_bytes(encoded_key) {
-            is_valid_signature = key.validate(encoded_signature, data.commit().as_ref());
+            let commit = data.commit_for_chain(self.membership().chain_id());
+            let data: &[u8] = commit.as_ref();
+            let check = || {
+                self.verification_pool()
+                    .run(|| key.validate(encoded_signature, data))
+            };
+            is_valid_signature = match self.signature_cache() {
+                Some(cache) => cache.get_or_check(encoded_key, data, encoded_signature, check),
+                None => check(),
+            };
             let valid_vote_token = self.membership().validate_vote_token(key, vote_token);
             is_valid_vote_token = match valid_vote_token {
                 Err(_) => {
@@ -420,6 +755,18 @@ pub trait ConsensusExchange<TYPES: NodeType, M: NetworkMsg>: Send + Sync {
         vota_meta: VoteMetaData<Self::Commitment, TYPES::VoteTokenType, TYPES::Time>,
         accumulator: VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>,
     ) -> Either<VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>, Self::Certificate> {
+        if vota_meta.vote_token.is_zero_weight() {
+            error!("Vote token carries no weight; not accumulating it");
+            return Either::Left(accumulator);
+        }
+
+        // Timeout votes sign their view rather than this exchange's commitment, so they are
+        // gathered separately, by `QuorumExchangeType::accumulate_timeout_vote`.
+        if matches!(vota_meta.data, VoteData::Timeout(_)) {
+            error!("Timeout votes are not accumulated with other votes");
+            return Either::Left(accumulator);
+        }
+
         if !self.is_valid_vote(
             &vota_meta.encoded_key,
             &vota_meta.encoded_signature,
@@ -433,13 +780,16 @@ pub trait ConsensusExchange<TYPES: NodeType, M: NetworkMsg>: Send + Sync {
 
         if let Some(key) = <TYPES::SignatureKey as SignatureKey>::from_bytes(&vota_meta.encoded_key)
         {
-            let stake_table_entry = key.get_stake_table_entry(1u64);
-            let append_node_id = self
+            // Find the voter by key, whatever stake its entry carries
+            let Some(append_node_id) = self
                 .membership()
                 .get_committee_qc_stake_table()
                 .iter()
-                .position(|x| *x == stake_table_entry.clone())
-                .unwrap();
+                .position(|entry| key.get_stake_from_entry(entry).is_some())
+            else {
+                error!("Vote from a key that is not in the committee stake table");
+                return Either::Left(accumulator);
+            };
 
             match accumulator.append((
                 vota_meta.commitment,
@@ -482,7 +832,54 @@ pub trait ConsensusExchange<TYPES: NodeType, M: NetworkMsg>: Send + Sync {
         view_number: TYPES::Time,
         accumlator: VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>,
         relay: Option<u64>,
-    ) -> Either<VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>, Self::Certificate>;
+    ) -> Either<VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>, Self::Certificate> {
+        let meta = VoteMetaData {
+            encoded_key: encoded_key.clone(),
+            encoded_signature: encoded_signature.clone(),
+            commitment: leaf_commitment,
+            data: vote_data,
+            vote_token,
+            view_number,
+            relay,
+        };
+        self.accumulate_internal(meta, accumlator)
+    }
+
+    /// Add `vote` to the accumulating signature, using the vote data, token and signature the
+    /// vote carries.  Return the certificate if the vote brings us over the threshold, else
+    /// return the accumulator.
+    ///
+    /// Votes that are never gathered into a certificate, such as timeout votes, leave the
+    /// accumulator unchanged.
+    fn accumulate<V: VoteType<TYPES, Commitment = Self::Commitment>>(
+        &self,
+        vote: &V,
+        accumulator: VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>,
+    ) -> Either<VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>, Self::Certificate> {
+        let Some(meta) = vote.vote_meta() else {
+            error!("Vote of this kind is not accumulated into a certificate");
+            return Either::Left(accumulator);
+        };
+        self.accumulate_vote(
+            &meta.encoded_key,
+            &meta.encoded_signature,
+            meta.commitment,
+            meta.data,
+            meta.vote_token,
+            meta.view_number,
+            accumulator,
+            meta.relay,
+        )
+    }
+
+    /// An accumulator with no votes yet, at this exchange's thresholds
+    fn empty_accumulator(&self) -> VoteAccumulator<TYPES::VoteTokenType, Self::Commitment> {
+        VoteAccumulator::new(
+            self.success_threshold(),
+            self.failure_threshold(),
+            self.total_nodes(),
+        )
+    }
 
     /// The committee which votes on proposals.
     fn membership(&self) -> &Self::Membership;
@@ -492,6 +889,13 @@ pub trait ConsensusExchange<TYPES: NodeType, M: NetworkMsg>: Send + Sync {
 
     /// This participant's private key.
     fn private_key(&self) -> &<TYPES::SignatureKey as SignatureKey>::PrivateKey;
+
+    /// What signs proposals and votes on this participant's behalf; by default an
+    /// [`InMemorySigner`] holding [`private_key`](Self::private_key).
+    fn signer(&self) -> &Arc<dyn Signer<TYPES::SignatureKey>>;
+
+    /// Sign with `signer` from now on, e.g. to keep the private key in a remote signer.
+    fn set_signer(&mut self, signer: Arc<dyn Signer<TYPES::SignatureKey>>);
 }
 
 /// A [`ConsensusExchange`] where participants vote to provide availability for blobs of data.
@@ -499,6 +903,9 @@ pub trait CommitteeExchangeType<TYPES: NodeType, M: NetworkMsg>:
     ConsensusExchange<TYPES, M>
 {
     /// Sign a DA proposal.
+    ///
+    /// The block commitment is signed as [`ProposalData::DA`], so the signature can't be mistaken
+    /// for a vote on the same block.
     fn sign_da_proposal(&self, block_commitment: &Commitment<TYPES::BlockType>)
         -> EncodedSignature;
 
@@ -518,6 +925,13 @@ pub trait CommitteeExchangeType<TYPES: NodeType, M: NetworkMsg>:
         current_view: TYPES::Time,
         vote_token: TYPES::VoteTokenType,
     ) -> CommitteeConsensusMessage<TYPES>;
+
+    /// Whether this participant is on the DA committee for view `view_number`.
+    fn is_da_member(&self, view_number: TYPES::Time) -> bool {
+        self.membership()
+            .get_committee(view_number)
+            .contains(self.public_key())
+    }
 }
 
 /// Standard implementation of [`CommitteeExchangeType`] utilizing a DA committee.
@@ -540,6 +954,15 @@ pub struct CommitteeExchange<
     /// This participant's private key.
     #[derivative(Debug = "ignore")]
     private_key: <TYPES::SignatureKey as SignatureKey>::PrivateKey,
+    /// Signs on this participant's behalf.
+    #[derivative(Debug = "ignore")]
+    signer: Arc<dyn Signer<TYPES::SignatureKey>>,
+    /// Where signature checks for this exchange run.
+    verification_pool: VerificationPool,
+    /// Cache of vote signature checks, if enabled.
+    signature_cache: Option<SignatureCache>,
+    /// The view the chain starts at
+    genesis_view: TYPES::Time,
     #[doc(hidden)]
     _pd: PhantomData<(TYPES, MEMBERSHIP, M)>,
 }
@@ -556,7 +979,9 @@ impl<
         &self,
         block_commitment: &Commitment<TYPES::BlockType>,
     ) -> EncodedSignature {
-        let signature = TYPES::SignatureKey::sign(&self.private_key, block_commitment.as_ref());
+        let signature = self
+            .signer
+            .sign(ProposalData::DA(*block_commitment).commit().as_ref());
         signature
     }
     /// Sign a vote on DA proposal.
@@ -567,10 +992,9 @@ impl<
         &self,
         block_commitment: Commitment<TYPES::BlockType>,
     ) -> (EncodedPublicKey, EncodedSignature) {
-        let signature = TYPES::SignatureKey::sign(
-            &self.private_key,
+        let signature = self.signer.sign(
             VoteData::<TYPES::BlockType>::DA(block_commitment)
-                .commit()
+                .commit_for_chain(self.membership.chain_id())
                 .as_ref(),
         );
         (self.public_key.to_bytes(), signature)
@@ -617,14 +1041,20 @@ impl<
         sk: <TYPES::SignatureKey as SignatureKey>::PrivateKey,
     ) -> Self {
         let membership = <Self as ConsensusExchange<TYPES, M>>::Membership::create_election(
-            entries, keys, config,
+            entries,
+            keys,
+            config.da_committee_config(),
         );
         Self {
             network,
             membership,
             public_key: pk,
             entry,
+            signer: Arc::new(InMemorySigner::<TYPES::SignatureKey>(sk.clone())),
             private_key: sk,
+            verification_pool: VerificationPool::inline(),
+            signature_cache: None,
+            genesis_view: TYPES::Time::genesis(),
             _pd: PhantomData,
         }
     }
@@ -639,34 +1069,13 @@ impl<
             .make_vote_token(view_number, &self.private_key)
     }
 
-    fn vote_data(&self, commit: Commitment<Self::Commitment>) -> VoteData<Self::Commitment> {
-        VoteData::DA(commit)
-    }
-
-    /// Add a vote to the accumulating signature.  Return The certificate if the vote
-    /// brings us over the threshould, Else return the accumulator.
-    fn accumulate_vote(
+    fn vote_data(
         &self,
-        encoded_key: &EncodedPublicKey,
-        encoded_signature: &EncodedSignature,
-        leaf_commitment: Commitment<Self::Commitment>,
-        vote_data: VoteData<Self::Commitment>,
-        vote_token: TYPES::VoteTokenType,
-        view_number: TYPES::Time,
-        accumlator: VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>,
-        _relay: Option<u64>,
-    ) -> Either<VoteAccumulator<TYPES::VoteTokenType, Self::Commitment>, Self::Certificate> {
-        let meta = VoteMetaData {
-            encoded_key: encoded_key.clone(),
-            encoded_signature: encoded_signature.clone(),
-            commitment: leaf_commitment,
-            data: vote_data,
-            vote_token,
-            view_number,
-            relay: None,
-        };
-        self.accumulate_internal(meta, accumlator)
+        commit: Commitment<Self::Commitment>,
+    ) -> Option<VoteData<Self::Commitment>> {
+        Some(VoteData::DA(commit))
     }
+
     fn membership(&self) -> &Self::Membership {
         &self.membership
     }
@@ -676,6 +1085,30 @@ impl<
     fn private_key(&self) -> &<<TYPES as NodeType>::SignatureKey as SignatureKey>::PrivateKey {
         &self.private_key
     }
+    fn signer(&self) -> &Arc<dyn Signer<TYPES::SignatureKey>> {
+        &self.signer
+    }
+    fn set_signer(&mut self, signer: Arc<dyn Signer<TYPES::SignatureKey>>) {
+        self.signer = signer;
+    }
+    fn verification_pool(&self) -> &VerificationPool {
+        &self.verification_pool
+    }
+    fn set_verification_pool(&mut self, pool: VerificationPool) {
+        self.verification_pool = pool;
+    }
+    fn signature_cache(&self) -> Option<&SignatureCache> {
+        self.signature_cache.as_ref()
+    }
+    fn set_signature_cache(&mut self, cache: Option<SignatureCache>) {
+        self.signature_cache = cache;
+    }
+    fn genesis_view(&self) -> TYPES::Time {
+        self.genesis_view
+    }
+    fn set_genesis_view(&mut self, view: TYPES::Time) {
+        self.genesis_view = view;
+    }
 }
 
 /// A [`ConsensusExchange`] where participants vote to append items to a log.
@@ -695,11 +1128,29 @@ pub trait QuorumExchangeType<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>,
         I::Exchanges: ExchangesType<TYPES, LEAF, Message<TYPES, I>>;
 
     /// Sign a validating or commitment proposal.
+    ///
+    /// The leaf commitment is signed as [`ProposalData::Quorum`], so the signature can't be
+    /// mistaken for a vote on the same leaf.
     fn sign_validating_or_commitment_proposal<I: NodeImplementation<TYPES>>(
         &self,
         leaf_commitment: &Commitment<LEAF>,
     ) -> EncodedSignature;
 
+    /// Check that `signature` was made by `proposer` over a proposal of the leaf with
+    /// `leaf_commitment`, as signed by
+    /// [`sign_validating_or_commitment_proposal`](Self::sign_validating_or_commitment_proposal).
+    fn is_valid_proposal_signature(
+        &self,
+        proposer: &TYPES::SignatureKey,
+        leaf_commitment: Commitment<LEAF>,
+        signature: &EncodedSignature,
+    ) -> bool {
+        let commit = ProposalData::Quorum(leaf_commitment).commit();
+        let data: &[u8] = commit.as_ref();
+        self.verification_pool()
+            .run(|| proposer.validate(signature, data))
+    }
+
     /// Sign a positive vote on validating or commitment proposal.
     ///
     /// The leaf commitment and the type of the vote (yes) are signed, which is the minimum amount
@@ -750,6 +1201,101 @@ pub trait QuorumExchangeType<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>,
     ) -> GeneralConsensusMessage<TYPES, I>
     where
         I::Exchanges: ExchangesType<TYPES, I::Leaf, Message<TYPES, I>>;
+
+    /// An accumulator with no timeout votes yet, at this exchange's thresholds
+    fn empty_timeout_accumulator(&self) -> VoteAccumulator<TYPES::VoteTokenType, TYPES::Time> {
+        VoteAccumulator::new(
+            self.success_threshold(),
+            self.failure_threshold(),
+            self.total_nodes(),
+        )
+    }
+
+    /// Add the timeout vote `vote` to `accumulator` after checking its signature and token.
+    /// Return the timeout certificate for the vote's view once timeout votes reach the success
+    /// threshold, else the accumulator.
+    fn accumulate_timeout_vote(
+        &self,
+        vote: &TimeoutVote<TYPES, LEAF>,
+        accumulator: VoteAccumulator<TYPES::VoteTokenType, TYPES::Time>,
+    ) -> Either<VoteAccumulator<TYPES::VoteTokenType, TYPES::Time>, TimeoutCertificate<TYPES>> {
+        if vote.vote_token.is_zero_weight() {
+            error!("Vote token carries no weight; not accumulating it");
+            return Either::Left(accumulator);
+        }
+        if vote.vote_data != VoteData::Timeout(vote.current_view.commit()) {
+            error!("Timeout vote is not on the view it was cast in");
+            return Either::Left(accumulator);
+        }
+
+        let (encoded_key, encoded_signature) = &vote.signature;
+        let Some(key) = <TYPES::SignatureKey as SignatureKey>::from_bytes(encoded_key) else {
+            error!("Timeout vote from a key that cannot be decoded");
+            return Either::Left(accumulator);
+        };
+        let commit = vote
+            .vote_data
+            .commit_for_chain(self.membership().chain_id());
+        let data: &[u8] = commit.as_ref();
+        if !self
+            .verification_pool()
+            .run(|| key.validate(encoded_signature, data))
+        {
+            error!("Invalid timeout vote signature");
+            return Either::Left(accumulator);
+        }
+        if !matches!(
+            self.membership()
+                .validate_vote_token(key.clone(), Checked::Unchecked(vote.vote_token.clone())),
+            Ok(Checked::Valid(_))
+        ) {
+            error!("Invalid timeout vote token");
+            return Either::Left(accumulator);
+        }
+
+        let stake_table = self.membership().get_committee_qc_stake_table();
+        let Some(node_id) = stake_table
+            .iter()
+            .position(|entry| key.get_stake_from_entry(entry).is_some())
+        else {
+            error!("Vote from a key that is not in the committee stake table");
+            return Either::Left(accumulator);
+        };
+        match accumulator.append((
+            vote.current_view.commit(),
+            (
+                encoded_key.clone(),
+                (
+                    encoded_signature.clone(),
+                    stake_table,
+                    node_id,
+                    vote.vote_data.clone(),
+                    vote.vote_token.clone(),
+                ),
+            ),
+        )) {
+            Either::Left(accumulator) => Either::Left(accumulator),
+            Either::Right(signatures) => Either::Right(TimeoutCertificate {
+                view_number: vote.current_view,
+                signatures,
+            }),
+        }
+    }
+
+    /// Validate a timeout certificate: it must carry timeout signatures over its view from at
+    /// least the success threshold of that view's stake.
+    fn is_valid_timeout_cert(&self, certificate: &TimeoutCertificate<TYPES>) -> bool {
+        let vote_data = VoteData::<TYPES::Time>::Timeout(certificate.view_number.commit());
+        if !vote_data.matches_signature_kind(certificate.signatures.kind()) {
+            return false;
+        }
+        let real_commit = vote_data.commit_for_chain(self.membership().chain_id());
+        self.is_valid_signatures(
+            certificate.signatures.clone(),
+            certificate.view_number,
+            real_commit.as_ref(),
+        )
+    }
 }
 
 /// Standard implementation of [`QuroumExchangeType`] based on Hot Stuff consensus.
@@ -774,6 +1320,15 @@ pub struct QuorumExchange<
     /// This participant's private key.
     #[derivative(Debug = "ignore")]
     private_key: <TYPES::SignatureKey as SignatureKey>::PrivateKey,
+    /// Signs on this participant's behalf.
+    #[derivative(Debug = "ignore")]
+    signer: Arc<dyn Signer<TYPES::SignatureKey>>,
+    /// Where signature checks for this exchange run.
+    verification_pool: VerificationPool,
+    /// Cache of vote signature checks, if enabled.
+    signature_cache: Option<SignatureCache>,
+    /// The view the chain starts at
+    genesis_view: TYPES::Time,
     #[doc(hidden)]
     _pd: PhantomData<(LEAF, PROPOSAL, MEMBERSHIP, M)>,
 }
@@ -814,7 +1369,9 @@ impl<
         &self,
         leaf_commitment: &Commitment<LEAF>,
     ) -> EncodedSignature {
-        let signature = TYPES::SignatureKey::sign(&self.private_key, leaf_commitment.as_ref());
+        let signature = self
+            .signer
+            .sign(ProposalData::Quorum(*leaf_commitment).commit().as_ref());
         signature
     }
 
@@ -828,9 +1385,10 @@ impl<
         &self,
         leaf_commitment: Commitment<LEAF>,
     ) -> (EncodedPublicKey, EncodedSignature) {
-        let signature = TYPES::SignatureKey::sign(
-            &self.private_key,
-            VoteData::<LEAF>::Yes(leaf_commitment).commit().as_ref(),
+        let signature = self.signer.sign(
+            VoteData::<LEAF>::Yes(leaf_commitment)
+                .commit_for_chain(self.membership.chain_id())
+                .as_ref(),
         );
         (self.public_key.to_bytes(), signature)
     }
@@ -844,9 +1402,10 @@ impl<
         &self,
         leaf_commitment: Commitment<LEAF>,
     ) -> (EncodedPublicKey, EncodedSignature) {
-        let signature = TYPES::SignatureKey::sign(
-            &self.private_key,
-            VoteData::<LEAF>::No(leaf_commitment).commit().as_ref(),
+        let signature = self.signer.sign(
+            VoteData::<LEAF>::No(leaf_commitment)
+                .commit_for_chain(self.membership.chain_id())
+                .as_ref(),
         );
         (self.public_key.to_bytes(), signature)
     }
@@ -859,10 +1418,9 @@ impl<
     /// This also allows for the high QC included with the vote to be spoofed in a MITM scenario,
     /// but it is outside our threat model.
     fn sign_timeout_vote(&self, view_number: TYPES::Time) -> (EncodedPublicKey, EncodedSignature) {
-        let signature = TYPES::SignatureKey::sign(
-            &self.private_key,
+        let signature = self.signer.sign(
             VoteData::<TYPES::Time>::Timeout(view_number.commit())
-                .commit()
+                .commit_for_chain(self.membership.chain_id())
                 .as_ref(),
         );
         (self.public_key.to_bytes(), signature)
@@ -944,7 +1502,11 @@ impl<
             membership,
             public_key: pk,
             entry,
+            signer: Arc::new(InMemorySigner::<TYPES::SignatureKey>(sk.clone())),
             private_key: sk,
+            verification_pool: VerificationPool::inline(),
+            signature_cache: None,
+            genesis_view: TYPES::Time::genesis(),
             _pd: PhantomData,
         }
     }
@@ -953,34 +1515,13 @@ impl<
         &self.network
     }
 
-    fn vote_data(&self, commit: Commitment<Self::Commitment>) -> VoteData<Self::Commitment> {
-        VoteData::Yes(commit)
-    }
-
-    /// Add a vote to the accumulating signature.  Return The certificate if the vote
-    /// brings us over the threshould, Else return the accumulator.
-    fn accumulate_vote(
+    fn vote_data(
         &self,
-        encoded_key: &EncodedPublicKey,
-        encoded_signature: &EncodedSignature,
-        leaf_commitment: Commitment<LEAF>,
-        vote_data: VoteData<Self::Commitment>,
-        vote_token: TYPES::VoteTokenType,
-        view_number: TYPES::Time,
-        accumlator: VoteAccumulator<TYPES::VoteTokenType, LEAF>,
-        _relay: Option<u64>,
-    ) -> Either<VoteAccumulator<TYPES::VoteTokenType, LEAF>, Self::Certificate> {
-        let meta = VoteMetaData {
-            encoded_key: encoded_key.clone(),
-            encoded_signature: encoded_signature.clone(),
-            commitment: leaf_commitment,
-            data: vote_data,
-            vote_token,
-            view_number,
-            relay: None,
-        };
-        self.accumulate_internal(meta, accumlator)
+        commit: Commitment<Self::Commitment>,
+    ) -> Option<VoteData<Self::Commitment>> {
+        Some(VoteData::Yes(commit))
     }
+
     fn membership(&self) -> &Self::Membership {
         &self.membership
     }
@@ -990,6 +1531,30 @@ impl<
     fn private_key(&self) -> &<<TYPES as NodeType>::SignatureKey as SignatureKey>::PrivateKey {
         &self.private_key
     }
+    fn signer(&self) -> &Arc<dyn Signer<TYPES::SignatureKey>> {
+        &self.signer
+    }
+    fn set_signer(&mut self, signer: Arc<dyn Signer<TYPES::SignatureKey>>) {
+        self.signer = signer;
+    }
+    fn verification_pool(&self) -> &VerificationPool {
+        &self.verification_pool
+    }
+    fn set_verification_pool(&mut self, pool: VerificationPool) {
+        self.verification_pool = pool;
+    }
+    fn signature_cache(&self) -> Option<&SignatureCache> {
+        self.signature_cache.as_ref()
+    }
+    fn set_signature_cache(&mut self, cache: Option<SignatureCache>) {
+        self.signature_cache = cache;
+    }
+    fn genesis_view(&self) -> TYPES::Time {
+        self.genesis_view
+    }
+    fn set_genesis_view(&mut self, view: TYPES::Time) {
+        self.genesis_view = view;
+    }
 }
 
 /// A [`ConsensusExchange`] where participants synchronize which view the network should be in.
@@ -997,12 +1562,14 @@ pub trait ViewSyncExchangeType<TYPES: NodeType, M: NetworkMsg>:
     ConsensusExchange<TYPES, M>
 {
     /// Creates a precommit vote
+    ///
+    /// Returns `None` if `round + relay` overflows.
     fn create_precommit_message<I: NodeImplementation<TYPES>>(
         &self,
         round: TYPES::Time,
         relay: u64,
         vote_token: TYPES::VoteTokenType,
-    ) -> GeneralConsensusMessage<TYPES, I>;
+    ) -> Option<GeneralConsensusMessage<TYPES, I>>;
 
     /// Signs a precommit vote
     fn sign_precommit_message(
@@ -1011,12 +1578,14 @@ pub trait ViewSyncExchangeType<TYPES: NodeType, M: NetworkMsg>:
     ) -> (EncodedPublicKey, EncodedSignature);
 
     /// Creates a commit vote
+    ///
+    /// Returns `None` if `round + relay` overflows.
     fn create_commit_message<I: NodeImplementation<TYPES>>(
         &self,
         round: TYPES::Time,
         relay: u64,
         vote_token: TYPES::VoteTokenType,
-    ) -> GeneralConsensusMessage<TYPES, I>;
+    ) -> Option<GeneralConsensusMessage<TYPES, I>>;
 
     /// Signs a commit vote
     fn sign_commit_message(
@@ -1025,12 +1594,14 @@ pub trait ViewSyncExchangeType<TYPES: NodeType, M: NetworkMsg>:
     ) -> (EncodedPublicKey, EncodedSignature);
 
     /// Creates a finalize vote
+    ///
+    /// Returns `None` if `round + relay` overflows.
     fn create_finalize_message<I: NodeImplementation<TYPES>>(
         &self,
         round: TYPES::Time,
         relay: u64,
         vote_token: TYPES::VoteTokenType,
-    ) -> GeneralConsensusMessage<TYPES, I>;
+    ) -> Option<GeneralConsensusMessage<TYPES, I>>;
 
     /// Sings a finalize vote
     fn sign_finalize_message(
@@ -1043,6 +1614,12 @@ pub trait ViewSyncExchangeType<TYPES: NodeType, M: NetworkMsg>:
 
     /// Sign a certificate.
     fn sign_certificate_proposal(&self, certificate: Self::Certificate) -> EncodedSignature;
+
+    /// The metrics this exchange reports view sync participation to.
+    fn metrics(&self) -> &ViewSyncMetrics;
+
+    /// Report view sync participation to `metrics` from now on.
+    fn set_metrics(&mut self, metrics: Arc<ViewSyncMetrics>);
 }
 
 /// Standard implementation of [`ViewSyncExchangeType`] based on Hot Stuff consensus.
@@ -1066,6 +1643,18 @@ pub struct ViewSyncExchange<
     /// This participant's private key.
     #[derivative(Debug = "ignore")]
     private_key: <TYPES::SignatureKey as SignatureKey>::PrivateKey,
+    /// Signs on this participant's behalf.
+    #[derivative(Debug = "ignore")]
+    signer: Arc<dyn Signer<TYPES::SignatureKey>>,
+    /// Counters for view sync participation.
+    #[derivative(Debug = "ignore")]
+    metrics: Arc<ViewSyncMetrics>,
+    /// Where signature checks for this exchange run.
+    verification_pool: VerificationPool,
+    /// Cache of vote signature checks, if enabled.
+    signature_cache: Option<SignatureCache>,
+    /// The view the chain starts at
+    genesis_view: TYPES::Time,
     #[doc(hidden)]
     _pd: PhantomData<(PROPOSAL, MEMBERSHIP, M)>,
 }
@@ -1083,8 +1672,8 @@ impl<
         round: TYPES::Time,
         relay: u64,
         vote_token: TYPES::VoteTokenType,
-    ) -> GeneralConsensusMessage<TYPES, I> {
-        let relay_pub_key = self.get_leader(round + relay).to_bytes();
+    ) -> Option<GeneralConsensusMessage<TYPES, I>> {
+        let relay_pub_key = self.get_leader(round.checked_add(relay)?).to_bytes();
 
         let vote_data_internal: ViewSyncData<TYPES> = ViewSyncData {
             relay: relay_pub_key.clone(),
@@ -1094,16 +1683,17 @@ impl<
         let vote_data_internal_commitment = vote_data_internal.commit();
 
         let signature = self.sign_precommit_message(vote_data_internal_commitment);
+        self.metrics.precommit_votes_sent.add(1);
 
-        GeneralConsensusMessage::<TYPES, I>::ViewSyncVote(ViewSyncVote::PreCommit(
-            ViewSyncVoteInternal {
+        Some(GeneralConsensusMessage::<TYPES, I>::ViewSyncVote(
+            ViewSyncVote::PreCommit(ViewSyncVoteInternal {
                 relay_pub_key,
                 relay,
                 round,
                 signature,
                 vote_token,
                 vote_data: VoteData::ViewSyncPreCommit(vote_data_internal_commitment),
-            },
+            }),
         ))
     }
 
@@ -1111,9 +1701,10 @@ impl<
         &self,
         commitment: Commitment<ViewSyncData<TYPES>>,
     ) -> (EncodedPublicKey, EncodedSignature) {
-        let signature = TYPES::SignatureKey::sign(
-            &self.private_key,
-            VoteData::ViewSyncPreCommit(commitment).commit().as_ref(),
+        let signature = self.signer.sign(
+            VoteData::ViewSyncPreCommit(commitment)
+                .commit_for_chain(self.membership.chain_id())
+                .as_ref(),
         );
 
         (self.public_key.to_bytes(), signature)
@@ -1124,8 +1715,8 @@ impl<
         round: TYPES::Time,
         relay: u64,
         vote_token: TYPES::VoteTokenType,
-    ) -> GeneralConsensusMessage<TYPES, I> {
-        let relay_pub_key = self.get_leader(round + relay).to_bytes();
+    ) -> Option<GeneralConsensusMessage<TYPES, I>> {
+        let relay_pub_key = self.get_leader(round.checked_add(relay)?).to_bytes();
 
         let vote_data_internal: ViewSyncData<TYPES> = ViewSyncData {
             relay: relay_pub_key.clone(),
@@ -1135,16 +1726,17 @@ impl<
         let vote_data_internal_commitment = vote_data_internal.commit();
 
         let signature = self.sign_commit_message(vote_data_internal_commitment);
+        self.metrics.commit_votes_sent.add(1);
 
-        GeneralConsensusMessage::<TYPES, I>::ViewSyncVote(ViewSyncVote::Commit(
-            ViewSyncVoteInternal {
+        Some(GeneralConsensusMessage::<TYPES, I>::ViewSyncVote(
+            ViewSyncVote::Commit(ViewSyncVoteInternal {
                 relay_pub_key,
                 relay,
                 round,
                 signature,
                 vote_token,
                 vote_data: VoteData::ViewSyncCommit(vote_data_internal_commitment),
-            },
+            }),
         ))
     }
 
@@ -1152,9 +1744,10 @@ impl<
         &self,
         commitment: Commitment<ViewSyncData<TYPES>>,
     ) -> (EncodedPublicKey, EncodedSignature) {
-        let signature = TYPES::SignatureKey::sign(
-            &self.private_key,
-            VoteData::ViewSyncCommit(commitment).commit().as_ref(),
+        let signature = self.signer.sign(
+            VoteData::ViewSyncCommit(commitment)
+                .commit_for_chain(self.membership.chain_id())
+                .as_ref(),
         );
 
         (self.public_key.to_bytes(), signature)
@@ -1165,8 +1758,8 @@ impl<
         round: TYPES::Time,
         relay: u64,
         vote_token: TYPES::VoteTokenType,
-    ) -> GeneralConsensusMessage<TYPES, I> {
-        let relay_pub_key = self.get_leader(round + relay).to_bytes();
+    ) -> Option<GeneralConsensusMessage<TYPES, I>> {
+        let relay_pub_key = self.get_leader(round.checked_add(relay)?).to_bytes();
 
         let vote_data_internal: ViewSyncData<TYPES> = ViewSyncData {
             relay: relay_pub_key.clone(),
@@ -1176,16 +1769,17 @@ impl<
         let vote_data_internal_commitment = vote_data_internal.commit();
 
         let signature = self.sign_finalize_message(vote_data_internal_commitment);
+        self.metrics.finalize_votes_sent.add(1);
 
-        GeneralConsensusMessage::<TYPES, I>::ViewSyncVote(ViewSyncVote::Finalize(
-            ViewSyncVoteInternal {
+        Some(GeneralConsensusMessage::<TYPES, I>::ViewSyncVote(
+            ViewSyncVote::Finalize(ViewSyncVoteInternal {
                 relay_pub_key,
                 relay,
                 round,
                 signature,
                 vote_token,
                 vote_data: VoteData::ViewSyncFinalize(vote_data_internal_commitment),
-            },
+            }),
         ))
     }
 
@@ -1193,9 +1787,10 @@ impl<
         &self,
         commitment: Commitment<ViewSyncData<TYPES>>,
     ) -> (EncodedPublicKey, EncodedSignature) {
-        let signature = TYPES::SignatureKey::sign(
-            &self.private_key,
-            VoteData::ViewSyncFinalize(commitment).commit().as_ref(),
+        let signature = self.signer.sign(
+            VoteData::ViewSyncFinalize(commitment)
+                .commit_for_chain(self.membership.chain_id())
+                .as_ref(),
         );
 
         (self.public_key.to_bytes(), signature)
@@ -1203,72 +1798,87 @@ impl<
 
     fn is_valid_view_sync_cert(&self, certificate: Self::Certificate, round: TYPES::Time) -> bool {
         // Sishan NOTE TODO: would be better to test this, looks like this func is never called.
-        let (certificate_internal, _threshold, vote_data) = match certificate.clone() {
-            ViewSyncCertificate::PreCommit(certificate_internal) => {
-                let vote_data = ViewSyncData::<TYPES> {
-                    relay: self
-                        .get_leader(round + certificate_internal.relay)
-                        .to_bytes(),
-                    round,
-                };
-                (certificate_internal, self.failure_threshold(), vote_data)
-            }
-            ViewSyncCertificate::Commit(certificate_internal)
-            | ViewSyncCertificate::Finalize(certificate_internal) => {
-                let vote_data = ViewSyncData::<TYPES> {
-                    relay: self
-                        .get_leader(round + certificate_internal.relay)
-                        .to_bytes(),
-                    round,
-                };
-                (certificate_internal, self.success_threshold(), vote_data)
+        // The certificate's own phase decides what its signatures must be over.
+        let (phase, certificate_internal, phase_vote_data): (
+            _,
+            _,
+            fn(Commitment<ViewSyncData<TYPES>>) -> VoteData<ViewSyncData<TYPES>>,
+        ) = match certificate {
+            ViewSyncCertificate::PreCommit(internal) => (
+                ViewSyncPhase::PreCommit,
+                internal,
+                VoteData::ViewSyncPreCommit,
+            ),
+            ViewSyncCertificate::Commit(internal) => {
+                (ViewSyncPhase::Commit, internal, VoteData::ViewSyncCommit)
             }
+            ViewSyncCertificate::Finalize(internal) => (
+                ViewSyncPhase::Finalize,
+                internal,
+                VoteData::ViewSyncFinalize,
+            ),
         };
-        match certificate_internal.signatures {
-            AssembledSignature::ViewSyncPreCommit(raw_signatures) => {
-                let real_commit = VoteData::ViewSyncPreCommit(vote_data.commit()).commit();
-                let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
-                    self.membership().get_committee_qc_stake_table(),
-                    U256::from(self.membership().failure_threshold().get()),
-                );
-                <TYPES::SignatureKey as SignatureKey>::check(
-                    &real_qc_pp,
-                    real_commit.as_ref(),
-                    &raw_signatures,
-                )
-            }
-            AssembledSignature::ViewSyncCommit(raw_signatures) => {
-                let real_commit = VoteData::ViewSyncCommit(vote_data.commit()).commit();
-                let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
-                    self.membership().get_committee_qc_stake_table(),
-                    U256::from(self.membership().success_threshold().get()),
-                );
-                <TYPES::SignatureKey as SignatureKey>::check(
-                    &real_qc_pp,
-                    real_commit.as_ref(),
-                    &raw_signatures,
-                )
-            }
-            AssembledSignature::ViewSyncFinalize(raw_signatures) => {
-                let real_commit = VoteData::ViewSyncFinalize(vote_data.commit()).commit();
-                let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
-                    self.membership().get_committee_qc_stake_table(),
-                    U256::from(self.membership().success_threshold().get()),
-                );
-                <TYPES::SignatureKey as SignatureKey>::check(
-                    &real_qc_pp,
-                    real_commit.as_ref(),
-                    &raw_signatures,
-                )
-            }
-            _ => true,
+        if certificate_internal.relay > MAX_VIEW_SYNC_RELAY {
+            error!(
+                "Relay {} is beyond the largest allowed relay {}",
+                certificate_internal.relay, MAX_VIEW_SYNC_RELAY
+            );
+            return false;
+        }
+        let Some(relay_view) = round.checked_add(certificate_internal.relay) else {
+            error!(
+                "Relay {} overflows round {:?}",
+                certificate_internal.relay, round
+            );
+            return false;
+        };
+        let vote_data = ViewSyncData::<TYPES> {
+            relay: self.get_leader(relay_view).to_bytes(),
+            round,
+        };
+        let signatures = certificate_internal.signatures;
+        let chain_id = self.membership().chain_id();
+        let vote_data = phase_vote_data(vote_data.commit());
+        let threshold = self.membership().effective_threshold_for_phase(phase);
+        if !vote_data.matches_signature_kind(signatures.kind()) {
+            return false;
         }
+        let real_commit = vote_data.commit_for_chain(chain_id);
+        let data: &[u8] = real_commit.as_ref();
+        if let AssembledSignature::Individual(_, individual) = &signatures {
+            let stake_table = self.membership().get_committee_qc_stake_table();
+            return check_individual_signatures::<TYPES>(
+                self.verification_pool(),
+                individual,
+                &stake_table,
+                threshold,
+                data,
+            );
+        }
+        let Some(raw_signatures) = signatures.into_qc() else {
+            error!("View sync certificate carries no assembled signatures to check");
+            return false;
+        };
+        let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
+            self.membership().get_committee_qc_stake_table(),
+            U256::from(threshold.get()),
+        );
+        self.verification_pool().run(|| {
+            <TYPES::SignatureKey as SignatureKey>::check(&real_qc_pp, data, &raw_signatures)
+        })
     }
 
     fn sign_certificate_proposal(&self, certificate: Self::Certificate) -> EncodedSignature {
-        let signature = TYPES::SignatureKey::sign(&self.private_key, certificate.commit().as_ref());
+        let signature = self.signer.sign(certificate.commit().as_ref());
         signature
     }
+    fn metrics(&self) -> &ViewSyncMetrics {
+        &self.metrics
+    }
+
+    fn set_metrics(&mut self, metrics: Arc<ViewSyncMetrics>) {
+        self.metrics = metrics;
+    }
 }
 
 impl<
@@ -1303,7 +1913,12 @@ impl<
             membership,
             public_key: pk,
             entry,
+            signer: Arc::new(InMemorySigner::<TYPES::SignatureKey>(sk.clone())),
             private_key: sk,
+            metrics: Arc::new(ViewSyncMetrics::new(&NoMetrics)),
+            verification_pool: VerificationPool::inline(),
+            signature_cache: None,
+            genesis_view: TYPES::Time::genesis(),
             _pd: PhantomData,
         }
     }
@@ -1312,8 +1927,12 @@ impl<
         &self.network
     }
 
-    fn vote_data(&self, _commit: Commitment<Self::Commitment>) -> VoteData<Self::Commitment> {
-        unimplemented!()
+    /// View sync votes depend on the phase they are cast in as well as the commitment.
+    fn vote_data(
+        &self,
+        _commit: Commitment<Self::Commitment>,
+    ) -> Option<VoteData<Self::Commitment>> {
+        None
     }
 
     fn accumulate_vote(
@@ -1336,7 +1955,11 @@ impl<
             view_number,
             relay,
         };
-        self.accumulate_internal(meta, accumlator)
+        let result = self.accumulate_internal(meta, accumlator);
+        if result.is_right() {
+            self.metrics.certificates_formed.add(1);
+        }
+        result
     }
 
     fn membership(&self) -> &Self::Membership {
@@ -1348,6 +1971,30 @@ impl<
     fn private_key(&self) -> &<<TYPES as NodeType>::SignatureKey as SignatureKey>::PrivateKey {
         &self.private_key
     }
+    fn signer(&self) -> &Arc<dyn Signer<TYPES::SignatureKey>> {
+        &self.signer
+    }
+    fn set_signer(&mut self, signer: Arc<dyn Signer<TYPES::SignatureKey>>) {
+        self.signer = signer;
+    }
+    fn verification_pool(&self) -> &VerificationPool {
+        &self.verification_pool
+    }
+    fn set_verification_pool(&mut self, pool: VerificationPool) {
+        self.verification_pool = pool;
+    }
+    fn signature_cache(&self) -> Option<&SignatureCache> {
+        self.signature_cache.as_ref()
+    }
+    fn set_signature_cache(&mut self, cache: Option<SignatureCache>) {
+        self.signature_cache = cache;
+    }
+    fn genesis_view(&self) -> TYPES::Time {
+        self.genesis_view
+    }
+    fn set_genesis_view(&mut self, view: TYPES::Time) {
+        self.genesis_view = view;
+    }
 }
 
 /// Testable implementation of a [`Membership`]. Will expose a method to generate a vote token used for testing.
@@ -70,6 +70,10 @@ pub enum TransmitType {
     Direct,
     /// broadcast the message to all
     Broadcast,
+    /// directly transmit, broadcasting instead if the recipient
+    /// [cannot be reached](NetworkError::is_recipient_unreachable); received like
+    /// [`TransmitType::Direct`]
+    DirectWithFallbackBroadcast,
 }
 
 /// Error type for networking
@@ -103,6 +107,8 @@ pub enum NetworkError {
     CouldNotDeliver,
     /// Attempted to deliver a message to an unknown node
     NoSuchNode,
+    /// The recipient of a direct message could not be reached
+    Unreachable,
     /// Failed to serialize a network message
     FailedToSerialize {
         /// Originating bincode error
@@ -127,6 +133,33 @@ pub enum NetworkError {
     UnableToCancel,
 }
 
+impl NetworkError {
+    /// Whether this error means a direct message could not reach its recipient, so another
+    /// route, e.g. a broadcast, may still get it there
+    #[must_use]
+    pub fn is_recipient_unreachable(&self) -> bool {
+        matches!(
+            self,
+            NetworkError::Unreachable | NetworkError::NoSuchNode | NetworkError::CouldNotDeliver
+        )
+    }
+
+    /// Whether sending the same message again later may succeed, e.g. after a timeout or once an
+    /// unreachable recipient comes back
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            NetworkError::UnimplementedFeature
+                | NetworkError::NoSuchNode
+                | NetworkError::FailedToSerialize { .. }
+                | NetworkError::FailedToDeserialize { .. }
+                | NetworkError::ShutDown
+                | NetworkError::UnableToCancel
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 // Storing view number as a u64 to avoid the need TYPES generic
 /// Events to poll or cancel consensus processes.
@@ -206,6 +239,10 @@ pub trait CommunicationChannel<
 {
     /// Underlying Network implementation's type
     type NETWORK;
+
+    /// The public key this node uses on the channel
+    fn local_public_key(&self) -> TYPES::SignatureKey;
+
     /// Blocks until node is successfully initialized
     /// into the network
     async fn wait_for_ready(&self);
@@ -238,6 +275,24 @@ pub trait CommunicationChannel<
         recipient: TYPES::SignatureKey,
     ) -> Result<(), NetworkError>;
 
+    /// Sends each message directly to its recipient, returning how many were sent.
+    ///
+    /// The default sends the messages one after another; transports that can send several at
+    /// once should override it.
+    /// blocking
+    /// # Errors
+    /// The last send error, if none of the messages could be sent
+    async fn direct_message_batch(
+        &self,
+        messages: Vec<(M, TYPES::SignatureKey)>,
+    ) -> Result<usize, NetworkError> {
+        let mut results = Vec::with_capacity(messages.len());
+        for (message, recipient) in messages {
+            results.push(self.direct_message(message, recipient).await);
+        }
+        count_sent(results)
+    }
+
     /// Moves out the entire queue of received messages of 'transmit_type`
     ///
     /// Will unwrap the underlying `NetworkMessage`
@@ -259,6 +314,27 @@ pub trait CommunicationChannel<
     async fn inject_consensus_info(&self, event: ConsensusIntentEvent);
 }
 
+/// Counts the successful sends of a [`CommunicationChannel::direct_message_batch`].
+///
+/// # Errors
+/// The last error in `results`, if none of the sends succeeded
+pub fn count_sent(
+    results: impl IntoIterator<Item = Result<(), NetworkError>>,
+) -> Result<usize, NetworkError> {
+    let mut sent = 0;
+    let mut last_error = None;
+    for result in results {
+        match result {
+            Ok(()) => sent += 1,
+            Err(e) => last_error = Some(e),
+        }
+    }
+    match last_error {
+        Some(e) if sent == 0 => Err(e),
+        _ => Ok(sent),
+    }
+}
+
 /// represents a networking implmentration
 /// exposes low level API for interacting with a network
 /// intended to be implemented for libp2p, the centralized server,
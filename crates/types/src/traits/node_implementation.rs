@@ -6,8 +6,8 @@
 use super::{
     block_contents::Transaction,
     election::{
-        CommitteeExchangeType, ConsensusExchange, ElectionConfig, QuorumExchangeType,
-        ViewSyncExchangeType, VoteToken,
+        CommitteeExchangeType, ConsensusExchange, ElectionConfig, ElectionError,
+        QuorumExchangeType, ViewSyncExchangeType, VoteToken,
     },
     network::{CommunicationChannel, NetworkMsg, TestableNetworkingImplementation},
     state::{ConsensusTime, TestableBlock, TestableState},
@@ -18,9 +18,13 @@ use crate::{
     data::{LeafType, SequencingLeaf, TestableLeaf},
     message::{ConsensusMessageType, Message, SequencingMessage},
     traits::{
-        election::Membership, network::TestableChannelImplementation, signature_key::SignatureKey,
-        storage::Storage, Block,
+        election::Membership,
+        network::TestableChannelImplementation,
+        signature_key::{SignatureKey, Signer},
+        storage::Storage,
+        Block,
     },
+    verification::{SignatureCache, VerificationPool},
 };
 use async_compatibility_layer::channel::{unbounded, UnboundedReceiver, UnboundedSender};
 use async_lock::{Mutex, RwLock};
@@ -184,6 +188,29 @@ pub trait ExchangesType<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>, MESSA
     /// Get the view sync exchange.
     fn view_sync_exchange(&self) -> &Self::ViewSyncExchange;
 
+    /// Get the view sync exchange mutably.
+    fn view_sync_exchange_mut(&mut self) -> &mut Self::ViewSyncExchange;
+
+    /// Run the signature checks of every exchange on `pool`.
+    fn set_verification_pool(&mut self, pool: VerificationPool);
+
+    /// Share `cache` of vote signature checks between every exchange, or disable caching if
+    /// `None`.
+    fn set_signature_cache(&mut self, cache: Option<SignatureCache>);
+
+    /// Accept genesis certificates for `view` in every exchange.
+    fn set_genesis_view(&mut self, view: TYPES::Time);
+
+    /// Sign proposals and votes of every exchange with `signer`.
+    fn set_signer(&mut self, signer: Arc<dyn Signer<TYPES::SignatureKey>>);
+
+    /// Check that every exchange's key pair is consistent, see
+    /// [`ConsensusExchange::validate_keys`].
+    ///
+    /// # Errors
+    /// The first exchange whose private key does not match its public key
+    fn validate_keys(&self) -> Result<(), ElectionError>;
+
     /// Block the underlying networking interfaces until node is successfully initialized into the
     /// networks.
     async fn wait_for_networks_ready(&self);
@@ -305,6 +332,40 @@ where
         &self.view_sync_exchange
     }
 
+    fn view_sync_exchange_mut(&mut self) -> &mut Self::ViewSyncExchange {
+        &mut self.view_sync_exchange
+    }
+
+    fn set_verification_pool(&mut self, pool: VerificationPool) {
+        self.quorum_exchange.set_verification_pool(pool.clone());
+        self.committee_exchange.set_verification_pool(pool.clone());
+        self.view_sync_exchange.set_verification_pool(pool);
+    }
+
+    fn set_signature_cache(&mut self, cache: Option<SignatureCache>) {
+        self.quorum_exchange.set_signature_cache(cache.clone());
+        self.committee_exchange.set_signature_cache(cache.clone());
+        self.view_sync_exchange.set_signature_cache(cache);
+    }
+
+    fn set_genesis_view(&mut self, view: TYPES::Time) {
+        self.quorum_exchange.set_genesis_view(view);
+        self.committee_exchange.set_genesis_view(view);
+        self.view_sync_exchange.set_genesis_view(view);
+    }
+
+    fn set_signer(&mut self, signer: Arc<dyn Signer<TYPES::SignatureKey>>) {
+        self.quorum_exchange.set_signer(signer.clone());
+        self.committee_exchange.set_signer(signer.clone());
+        self.view_sync_exchange.set_signer(signer);
+    }
+
+    fn validate_keys(&self) -> Result<(), ElectionError> {
+        self.quorum_exchange.validate_keys()?;
+        self.committee_exchange.validate_keys()?;
+        self.view_sync_exchange.validate_keys()
+    }
+
     async fn wait_for_networks_ready(&self) {
         self.quorum_exchange.network().wait_for_ready().await;
         self.committee_exchange.network().wait_for_ready().await;
@@ -579,4 +640,9 @@ pub trait NodeType:
 
     /// The state type that this hotshot setup is using.
     type StateType: State<BlockType = Self::BlockType, Time = Self::Time>;
+
+    /// The genesis block and the state obtained by appending it to the default state.
+    ///
+    /// Every bootstrap path should start from this pair so that all nodes agree on genesis.
+    fn genesis() -> (Self::BlockType, Self::StateType);
 }
@@ -101,6 +101,17 @@ pub trait SignatureKey:
     /// get the stake table entry from the public key and stake value
     fn get_stake_table_entry(&self, stake: u64) -> Self::StakeTableEntry;
 
+    /// get the stake that `entry` gives this key, or `None` if `entry` belongs to another key
+    fn get_stake_from_entry(&self, entry: &Self::StakeTableEntry) -> Option<U256>;
+
+    /// Whether signatures of this scheme can be aggregated into one.
+    ///
+    /// Certificates of schemes that cannot aggregate carry each signer's signature instead, and
+    /// [`get_sig_proof`](Self::get_sig_proof) and [`assemble`](Self::assemble) are never called.
+    fn supports_aggregation() -> bool {
+        true
+    }
+
     /// get the public parameter for the assembled signature checking
     fn get_public_parameter(
         stake_entries: Vec<Self::StakeTableEntry>,
@@ -125,3 +136,19 @@ pub trait SignatureKey:
         sigs: &[<BLSOverBN254CurveSignatureScheme as SignatureScheme>::Signature],
     ) -> Self::QCType;
 }
+
+/// Signs data for a key pair whose private key may live outside this process, e.g. in an HSM or
+/// a remote signing service.
+pub trait Signer<KEY: SignatureKey>: Send + Sync {
+    /// Sign `data` with the private key of the key pair
+    fn sign(&self, data: &[u8]) -> EncodedSignature;
+}
+
+/// A [`Signer`] that holds the private key in process memory
+pub struct InMemorySigner<KEY: SignatureKey>(pub KEY::PrivateKey);
+
+impl<KEY: SignatureKey> Signer<KEY> for InMemorySigner<KEY> {
+    fn sign(&self, data: &[u8]) -> EncodedSignature {
+        KEY::sign(&self.0, data)
+    }
+}
@@ -95,6 +95,16 @@ pub trait ConsensusTime:
     }
     /// Create a new instance of this time unit
     fn new(val: u64) -> Self;
+    /// Add `rhs` to this time unit, returning `None` on overflow instead of wrapping
+    #[must_use]
+    fn checked_add(self, rhs: u64) -> Option<Self> {
+        (*self).checked_add(rhs).map(Self::new)
+    }
+    /// The number of views from `earlier` to this one, or `None` if `earlier` is actually later
+    #[must_use]
+    fn checked_sub(self, earlier: Self) -> Option<u64> {
+        (*self).checked_sub(*earlier)
+    }
 }
 
 /// extra functions required on state to be usable by hotshot-testing
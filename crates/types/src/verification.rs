@@ -0,0 +1,184 @@
+//! A thread pool for CPU-bound signature verification.
+//!
+//! Checking vote signatures and assembled certificates is expensive enough that a busy node
+//! should not run it on the async executor threads. A [`VerificationPool`] is configured once
+//! per node and shared by all of its exchanges, as is the optional [`SignatureCache`].
+
+use crate::traits::signature_key::{EncodedPublicKey, EncodedSignature};
+use futures::channel::oneshot;
+use lru::LruCache;
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::error;
+
+/// Where signature checks run: either inline on the calling thread, or on a dedicated pool.
+#[derive(Clone, Debug, Default)]
+pub struct VerificationPool {
+    /// The dedicated pool, or `None` to verify inline
+    pool: Option<Arc<ThreadPool>>,
+}
+
+impl VerificationPool {
+    /// A pool that verifies on the calling thread
+    #[must_use]
+    pub fn inline() -> Self {
+        Self { pool: None }
+    }
+
+    /// A dedicated pool of `num_threads` verification threads
+    /// # Errors
+    /// If the threads cannot be spawned
+    pub fn new(num_threads: NonZeroUsize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads.get())
+            .thread_name(|i| format!("hotshot-verify-{i}"))
+            .build()?;
+        Ok(Self {
+            pool: Some(Arc::new(pool)),
+        })
+    }
+
+    /// Build the pool described by `HotShotConfig::verification_threads`, falling back to inline
+    /// verification if the pool cannot be built
+    #[must_use]
+    pub fn from_config(num_threads: Option<NonZeroUsize>) -> Self {
+        match num_threads.map(Self::new) {
+            Some(Ok(pool)) => pool,
+            Some(Err(e)) => {
+                error!("Failed to build signature verification pool, verifying inline: {e}");
+                Self::inline()
+            }
+            None => Self::inline(),
+        }
+    }
+
+    /// Whether checks run on a dedicated pool
+    #[must_use]
+    pub fn is_pooled(&self) -> bool {
+        self.pool.is_some()
+    }
+
+    /// Run a single check, blocking the calling thread until it completes. Async code should
+    /// use [`Self::offload`] instead, so it does not hold up an executor thread.
+    pub fn run<R: Send>(&self, check: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(check),
+            None => check(),
+        }
+    }
+
+    /// Run a single check on the dedicated pool while the calling task waits for the result,
+    /// leaving the executor thread free. Inline pools run the check on the calling thread.
+    ///
+    /// Checks started from within the pool, e.g. by [`Self::run`] inside `check`, run on the
+    /// same pool thread.
+    /// # Panics
+    /// If `check` panics
+    pub async fn offload<R: Send + 'static>(
+        &self,
+        check: impl FnOnce() -> R + Send + 'static,
+    ) -> R {
+        match &self.pool {
+            Some(pool) => {
+                let (sender, receiver) = oneshot::channel();
+                pool.spawn(move || {
+                    // The caller may have stopped waiting; the result is then unused.
+                    let _ = sender.send(check());
+                });
+                receiver
+                    .await
+                    .expect("Signature check panicked on the verification pool")
+            }
+            None => check(),
+        }
+    }
+
+    /// Run `check` on every item, in parallel if this is a dedicated pool, returning the results
+    /// in order
+    pub fn run_batch<T: Send, R: Send>(
+        &self,
+        items: Vec<T>,
+        check: impl Fn(T) -> R + Send + Sync,
+    ) -> Vec<R> {
+        match &self.pool {
+            Some(pool) => pool.install(|| items.into_par_iter().map(check).collect()),
+            None => items.into_iter().map(check).collect(),
+        }
+    }
+}
+
+/// Key of a [`SignatureCache`] entry: the signer, a digest of the signed data, and the signature
+type SignatureCacheKey = (EncodedPublicKey, [u8; 32], EncodedSignature);
+
+/// A bounded cache of signature check results, so that a vote processed more than once (for
+/// instance when it is resent after a reconnection) is only checked once.
+///
+/// Entries are keyed on the key, the data and the signature together, so a different signature
+/// over the same data is always checked afresh.
+///
+/// Only the signature itself is cached. Whether the signer is on the committee is checked against
+/// the current stake table on every vote, so a cached result stays correct when the committee
+/// changes.
+#[derive(Clone, Debug)]
+pub struct SignatureCache {
+    /// Results of past checks, least recently used evicted first
+    results: Arc<Mutex<LruCache<SignatureCacheKey, bool>>>,
+    /// Number of checks answered from the cache
+    hits: Arc<AtomicU64>,
+}
+
+impl SignatureCache {
+    /// A cache holding the results of up to `capacity` checks
+    #[must_use]
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            results: Arc::new(Mutex::new(LruCache::new(capacity))),
+            hits: Arc::default(),
+        }
+    }
+
+    /// The result of checking `signature` by `key` over `data`, running `check` only if that
+    /// exact check is not cached
+    /// # Panics
+    /// If another thread panicked while holding the cache lock
+    pub fn get_or_check(
+        &self,
+        key: &EncodedPublicKey,
+        data: &[u8],
+        signature: &EncodedSignature,
+        check: impl FnOnce() -> bool,
+    ) -> bool {
+        let cache_key = (
+            key.clone(),
+            *blake3::hash(data).as_bytes(),
+            signature.clone(),
+        );
+        if let Some(valid) = self.results.lock().unwrap().get(&cache_key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return *valid;
+        }
+        let valid = check();
+        self.results.lock().unwrap().put(cache_key, valid);
+        valid
+    }
+
+    /// Drop every cached result, e.g. after a committee change, so that the cache is not spent on
+    /// signers that have left
+    /// # Panics
+    /// If another thread panicked while holding the cache lock
+    pub fn clear(&self) {
+        self.results.lock().unwrap().clear();
+    }
+
+    /// Number of checks answered from the cache so far
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+}
@@ -4,7 +4,7 @@
 //! can send, and vote accumulator that converts votes into certificates.
 
 use crate::{
-    certificate::{AssembledSignature, QuorumCertificate},
+    certificate::{AssembledSignature, AssembledSignatureKind, QuorumCertificate, VoteMetaData},
     data::LeafType,
     traits::{
         election::{VoteData, VoteToken},
@@ -33,8 +33,17 @@ use tracing::error;
 pub trait VoteType<TYPES: NodeType>:
     Debug + Clone + 'static + Serialize + for<'a> Deserialize<'a> + Send + Sync + PartialEq
 {
+    /// The type of data this vote is cast on.
+    type Commitment: Committable + Serialize + Clone;
+
     /// The view this vote was cast for.
     fn current_view(&self) -> TYPES::Time;
+
+    /// The signature, vote data and token this vote carries, or `None` if votes of this kind are
+    /// never gathered into a certificate.
+    fn vote_meta(
+        &self,
+    ) -> Option<VoteMetaData<Self::Commitment, TYPES::VoteTokenType, TYPES::Time>>;
 }
 
 /// A vote on DA proposal.
@@ -128,6 +137,62 @@ impl<TYPES: NodeType> Committable for ViewSyncData<TYPES> {
     }
 }
 
+#[derive(PartialEq, PartialOrd, Ord, Clone, Debug, Eq, Hash)]
+/// Phases of view sync, ordered by protocol progression
+pub enum ViewSyncPhase {
+    /// No phase; before the protocol has begun
+    None,
+    /// PreCommit phase
+    PreCommit,
+    /// Commit phase
+    Commit,
+    /// Finalize phase
+    Finalize,
+}
+
+impl ViewSyncPhase {
+    /// The phase that follows this one in a round, if any
+    #[must_use]
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            ViewSyncPhase::None => Some(ViewSyncPhase::PreCommit),
+            ViewSyncPhase::PreCommit => Some(ViewSyncPhase::Commit),
+            ViewSyncPhase::Commit => Some(ViewSyncPhase::Finalize),
+            ViewSyncPhase::Finalize => None,
+        }
+    }
+
+    /// Whether a relay that has formed the certificate for this phase may accumulate a vote for
+    /// `phase`.
+    ///
+    /// Once a certificate forms, the round only moves on to the next phase: votes for a phase that
+    /// already has a certificate are stale, and votes that skip ahead are premature. Before any
+    /// certificate forms, a vote for any phase is accepted, since replicas resend their latest
+    /// vote when the round moves to a new relay.
+    #[must_use]
+    pub fn accepts_vote_for(&self, phase: &ViewSyncPhase) -> bool {
+        *self == ViewSyncPhase::None || self.next().as_ref() == Some(phase)
+    }
+
+    /// Which of `success_threshold` and `failure_threshold` a view sync certificate for this phase
+    /// needs.
+    ///
+    /// A precommit certificate only shows that enough nodes want to sync that at least one is
+    /// honest, so it needs the failure threshold; commit and finalize certificates need a quorum.
+    /// [`ViewSyncPhase::None`] leads to a precommit certificate, so it uses the same threshold.
+    #[must_use]
+    pub fn threshold(
+        &self,
+        success_threshold: NonZeroU64,
+        failure_threshold: NonZeroU64,
+    ) -> NonZeroU64 {
+        match self {
+            ViewSyncPhase::None | ViewSyncPhase::PreCommit => failure_threshold,
+            ViewSyncPhase::Commit | ViewSyncPhase::Finalize => success_threshold,
+        }
+    }
+}
+
 /// Votes to synchronize the network on a single view
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(bound(deserialize = ""))]
@@ -176,6 +241,20 @@ impl<TYPES: NodeType> ViewSyncVote<TYPES> {
             | ViewSyncVote::Finalize(vote_internal) => vote_internal.round,
         }
     }
+    /// Whether this vote claims to be from `sender` and carries `sender`'s signature over its
+    /// vote data on the chain `chain_id`.
+    #[must_use]
+    pub fn is_signed_by(&self, sender: &TYPES::SignatureKey, chain_id: u64) -> bool {
+        let (ViewSyncVote::PreCommit(vote_internal)
+        | ViewSyncVote::Commit(vote_internal)
+        | ViewSyncVote::Finalize(vote_internal)) = self;
+        let (encoded_key, encoded_signature) = &vote_internal.signature;
+        *encoded_key == sender.to_bytes()
+            && sender.validate(
+                encoded_signature,
+                vote_internal.vote_data.commit_for_chain(chain_id).as_ref(),
+            )
+    }
 }
 
 /// Votes on validating or commitment proposal.
@@ -191,9 +270,25 @@ pub enum QuorumVote<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> {
 }
 
 impl<TYPES: NodeType> VoteType<TYPES> for DAVote<TYPES> {
+    type Commitment = TYPES::BlockType;
+
     fn current_view(&self) -> TYPES::Time {
         self.current_view
     }
+
+    fn vote_meta(
+        &self,
+    ) -> Option<VoteMetaData<TYPES::BlockType, TYPES::VoteTokenType, TYPES::Time>> {
+        Some(VoteMetaData {
+            encoded_key: self.signature.0.clone(),
+            encoded_signature: self.signature.1.clone(),
+            commitment: self.block_commitment,
+            data: self.vote_data.clone(),
+            vote_token: self.vote_token.clone(),
+            view_number: self.current_view,
+            relay: None,
+        })
+    }
 }
 
 impl<TYPES: NodeType> DAVote<TYPES> {
@@ -208,12 +303,29 @@ impl<TYPES: NodeType> DAVote<TYPES> {
 impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> VoteType<TYPES>
     for QuorumVote<TYPES, LEAF>
 {
+    type Commitment = LEAF;
+
     fn current_view(&self) -> TYPES::Time {
         match self {
             QuorumVote::Yes(v) | QuorumVote::No(v) => v.current_view,
             QuorumVote::Timeout(v) => v.current_view,
         }
     }
+
+    fn vote_meta(&self) -> Option<VoteMetaData<LEAF, TYPES::VoteTokenType, TYPES::Time>> {
+        match self {
+            QuorumVote::Yes(v) | QuorumVote::No(v) => Some(VoteMetaData {
+                encoded_key: v.signature.0.clone(),
+                encoded_signature: v.signature.1.clone(),
+                commitment: v.leaf_commitment,
+                data: v.vote_data.clone(),
+                vote_token: v.vote_token.clone(),
+                view_number: v.current_view,
+                relay: None,
+            }),
+            QuorumVote::Timeout(_) => None,
+        }
+    }
 }
 
 impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> QuorumVote<TYPES, LEAF> {
@@ -237,6 +349,8 @@ impl<TYPES: NodeType, LEAF: LeafType<NodeType = TYPES>> QuorumVote<TYPES, LEAF>
 }
 
 impl<TYPES: NodeType> VoteType<TYPES> for ViewSyncVote<TYPES> {
+    type Commitment = ViewSyncData<TYPES>;
+
     fn current_view(&self) -> TYPES::Time {
         match self {
             ViewSyncVote::PreCommit(v) | ViewSyncVote::Commit(v) | ViewSyncVote::Finalize(v) => {
@@ -244,6 +358,22 @@ impl<TYPES: NodeType> VoteType<TYPES> for ViewSyncVote<TYPES> {
             }
         }
     }
+
+    fn vote_meta(
+        &self,
+    ) -> Option<VoteMetaData<ViewSyncData<TYPES>, TYPES::VoteTokenType, TYPES::Time>> {
+        let (ViewSyncVote::PreCommit(v) | ViewSyncVote::Commit(v) | ViewSyncVote::Finalize(v)) =
+            self;
+        Some(VoteMetaData {
+            encoded_key: v.signature.0.clone(),
+            encoded_signature: v.signature.1.clone(),
+            commitment: v.vote_data.commitment(),
+            data: v.vote_data.clone(),
+            vote_token: v.vote_token.clone(),
+            view_number: v.round,
+            relay: Some(v.relay),
+        })
+    }
 }
 
 /// The aggreation of votes, implemented by `VoteAccumulator`.
@@ -259,13 +389,49 @@ pub trait Accumulator<T, U>: Sized {
 type VoteMap<C, TOKEN> = HashMap<
     Commitment<C>,
     (
-        u64,
+        U256,
         BTreeMap<EncodedPublicKey, (EncodedSignature, VoteData<C>, TOKEN)>,
     ),
 >;
 
+/// Record that `key` signed votes on two different commitments in the same vote accumulator.
+///
+/// [`EquivocationEvidence::verify`] checks both signatures on their own, so the record can be
+/// logged for audit. It is not grounds for slashing: votes do not sign over their view, so the
+/// signatures cannot show that both votes were cast in the same view, and honest votes from two
+/// different views would look the same.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(bound(deserialize = ""))]
+pub struct EquivocationEvidence<COMMITMENT: Committable + Serialize + Clone> {
+    /// The equivocating voter
+    pub key: EncodedPublicKey,
+    /// The vote that was accumulated
+    pub vote_a: VoteData<COMMITMENT>,
+    /// The voter's signature on `vote_a`
+    pub sig_a: EncodedSignature,
+    /// The conflicting vote that was rejected
+    pub vote_b: VoteData<COMMITMENT>,
+    /// The voter's signature on `vote_b`
+    pub sig_b: EncodedSignature,
+}
+
+impl<COMMITMENT: Committable + Serialize + Clone> EquivocationEvidence<COMMITMENT> {
+    /// Whether both votes differ and are validly signed by `key` for chain `chain_id`. This does
+    /// not show that the votes were cast in the same view.
+    #[must_use]
+    pub fn verify<KEY: SignatureKey>(&self, chain_id: u64) -> bool {
+        let Some(key) = KEY::from_bytes(&self.key) else {
+            return false;
+        };
+        self.vote_a != self.vote_b
+            && key.validate(&self.sig_a, self.vote_a.commit_for_chain(chain_id).as_ref())
+            && key.validate(&self.sig_b, self.vote_b.commit_for_chain(chain_id).as_ref())
+    }
+}
+
 /// Describe the process of collecting signatures on block or leaf commitment, to form a DAC or QC,
 /// respectively.
+#[derive(Clone)]
 pub struct VoteAccumulator<TOKEN, COMMITMENT: Committable + Serialize + Clone> {
     /// Map of all signatures accumlated so far
     pub total_vote_outcomes: VoteMap<COMMITMENT, TOKEN>,
@@ -281,6 +447,10 @@ pub struct VoteAccumulator<TOKEN, COMMITMENT: Committable + Serialize + Clone> {
     pub viewsync_commit_vote_outcomes: VoteMap<COMMITMENT, TOKEN>,
     /// Map of all view sync finalize votes accumulated thus far
     pub viewsync_finalize_vote_outcomes: VoteMap<COMMITMENT, TOKEN>,
+    /// Map of all upgrade votes accumulated thus far
+    pub upgrade_vote_outcomes: VoteMap<COMMITMENT, TOKEN>,
+    /// Map of all timeout votes accumulated thus far
+    pub timeout_vote_outcomes: VoteMap<COMMITMENT, TOKEN>,
     /// A quorum's worth of stake, generall 2f + 1
     pub success_threshold: NonZeroU64,
     /// Enough stake to know that we cannot possibly get a quorum, generally f + 1
@@ -289,6 +459,212 @@ pub struct VoteAccumulator<TOKEN, COMMITMENT: Committable + Serialize + Clone> {
     pub sig_lists: Vec<<BLSOverBN254CurveSignatureScheme as SignatureScheme>::Signature>,
     /// A bitvec to indicate which node is active and send out a valid signature for certificate aggregation, this automatically do uniqueness check
     pub signers: BitVec,
+    /// Voters caught signing votes on two different commitments, with both signatures
+    pub equivocations: Vec<EquivocationEvidence<COMMITMENT>>,
+}
+
+impl<TOKEN, COMMITMENT: Committable + Serialize + Clone> VoteAccumulator<TOKEN, COMMITMENT> {
+    /// An accumulator with no votes yet, for a committee of `total_nodes` nodes
+    #[must_use]
+    pub fn new(
+        success_threshold: NonZeroU64,
+        failure_threshold: NonZeroU64,
+        total_nodes: usize,
+    ) -> Self {
+        Self {
+            total_vote_outcomes: HashMap::new(),
+            da_vote_outcomes: HashMap::new(),
+            yes_vote_outcomes: HashMap::new(),
+            no_vote_outcomes: HashMap::new(),
+            viewsync_precommit_vote_outcomes: HashMap::new(),
+            viewsync_commit_vote_outcomes: HashMap::new(),
+            viewsync_finalize_vote_outcomes: HashMap::new(),
+            upgrade_vote_outcomes: HashMap::new(),
+            timeout_vote_outcomes: HashMap::new(),
+            success_threshold,
+            failure_threshold,
+            sig_lists: Vec::new(),
+            signers: bitvec![0; total_nodes],
+            equivocations: Vec::new(),
+        }
+    }
+
+    /// The most stake cast so far for any single commitment.
+    #[must_use]
+    pub fn current_stake(&self) -> U256 {
+        self.total_vote_outcomes
+            .values()
+            .map(|(stake, _)| *stake)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// How much more stake the leading commitment needs to reach `threshold`, or 0 if it
+    /// already has.
+    #[must_use]
+    pub fn remaining_stake_needed(&self, threshold: NonZeroU64) -> U256 {
+        U256::from(threshold.get()).saturating_sub(self.current_stake())
+    }
+
+    /// Assemble a certificate from the votes already accumulated for `commitment`, without
+    /// appending another vote.
+    ///
+    /// Uses the accumulator's own thresholds, e.g. after restoring a checkpointed accumulator.
+    /// # Errors
+    /// Returns the accumulator unchanged if no threshold has been reached for `commitment`.
+    pub fn try_into_certificate<TYPES: NodeType>(
+        mut self,
+        commitment: Commitment<COMMITMENT>,
+        entries: Vec<<TYPES::SignatureKey as SignatureKey>::StakeTableEntry>,
+    ) -> Result<AssembledSignature<TYPES>, Self> {
+        match self.assemble_if_complete(commitment, entries) {
+            Some(signature) => Ok(signature),
+            None => Err(self),
+        }
+    }
+
+    /// Assemble a certificate for `commitment` if any threshold has been reached, dropping the
+    /// votes it consumed.
+    fn assemble_if_complete<TYPES: NodeType>(
+        &mut self,
+        commitment: Commitment<COMMITMENT>,
+        entries: Vec<<TYPES::SignatureKey as SignatureKey>::StakeTableEntry>,
+    ) -> Option<AssembledSignature<TYPES>> {
+        let stake_casted = |outcomes: &VoteMap<COMMITMENT, TOKEN>| {
+            outcomes
+                .get(&commitment)
+                .map_or_else(U256::zero, |(stake, _)| *stake)
+        };
+        let success_threshold = U256::from(self.success_threshold.get());
+        let failure_threshold = U256::from(self.failure_threshold.get());
+
+        // This is a messy way of accounting for the different vote types, but we will be replacing this code very soon
+        if stake_casted(&self.total_vote_outcomes) >= success_threshold {
+            let (kind, votes) = if stake_casted(&self.yes_vote_outcomes) >= success_threshold {
+                (
+                    AssembledSignatureKind::Yes,
+                    self.yes_vote_outcomes.remove(&commitment),
+                )
+            } else if stake_casted(&self.no_vote_outcomes) >= failure_threshold {
+                self.total_vote_outcomes.remove(&commitment);
+                (
+                    AssembledSignatureKind::No,
+                    self.no_vote_outcomes.remove(&commitment),
+                )
+            } else if stake_casted(&self.da_vote_outcomes) >= success_threshold {
+                (
+                    AssembledSignatureKind::DA,
+                    self.da_vote_outcomes.remove(&commitment),
+                )
+            } else if stake_casted(&self.upgrade_vote_outcomes) >= success_threshold {
+                (
+                    AssembledSignatureKind::Upgrade,
+                    self.upgrade_vote_outcomes.remove(&commitment),
+                )
+            } else if stake_casted(&self.timeout_vote_outcomes) >= success_threshold {
+                (
+                    AssembledSignatureKind::Timeout,
+                    self.timeout_vote_outcomes.remove(&commitment),
+                )
+            } else {
+                return self.assemble_view_sync_if_complete(commitment, entries);
+            };
+            return Some(self.assemble(
+                kind,
+                self.success_threshold,
+                entries,
+                votes.map(|(_, votes)| votes),
+            ));
+        }
+        self.assemble_view_sync_if_complete(commitment, entries)
+    }
+
+    /// The certificate signature of kind `kind` over `votes`: one aggregated signature if the
+    /// signature scheme supports it, otherwise each voter's own signature.
+    fn assemble<TYPES: NodeType>(
+        &self,
+        kind: AssembledSignatureKind,
+        threshold: NonZeroU64,
+        entries: Vec<<TYPES::SignatureKey as SignatureKey>::StakeTableEntry>,
+        votes: Option<BTreeMap<EncodedPublicKey, (EncodedSignature, VoteData<COMMITMENT>, TOKEN)>>,
+    ) -> AssembledSignature<TYPES> {
+        if !<TYPES::SignatureKey as SignatureKey>::supports_aggregation() {
+            let signatures = votes
+                .into_iter()
+                .flatten()
+                .map(|(key, (signature, _, _))| (key, signature))
+                .collect();
+            return AssembledSignature::Individual(kind, signatures);
+        }
+
+        let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
+            entries,
+            U256::from(threshold.get()),
+        );
+        let real_qc_sig = <TYPES::SignatureKey as SignatureKey>::assemble(
+            &real_qc_pp,
+            self.signers.as_bitslice(),
+            &self.sig_lists[..],
+        );
+        match kind {
+            AssembledSignatureKind::Yes => AssembledSignature::Yes(real_qc_sig),
+            AssembledSignatureKind::No => AssembledSignature::No(real_qc_sig),
+            AssembledSignatureKind::DA => AssembledSignature::DA(real_qc_sig),
+            AssembledSignatureKind::Genesis => AssembledSignature::Genesis(),
+            AssembledSignatureKind::ViewSyncPreCommit => {
+                AssembledSignature::ViewSyncPreCommit(real_qc_sig)
+            }
+            AssembledSignatureKind::ViewSyncCommit => {
+                AssembledSignature::ViewSyncCommit(real_qc_sig)
+            }
+            AssembledSignatureKind::ViewSyncFinalize => {
+                AssembledSignature::ViewSyncFinalize(real_qc_sig)
+            }
+            AssembledSignatureKind::Upgrade => AssembledSignature::Upgrade(real_qc_sig),
+            AssembledSignatureKind::Timeout => AssembledSignature::Timeout(real_qc_sig),
+        }
+    }
+
+    /// Assemble a view sync certificate for `commitment` if the votes for some phase have
+    /// reached that phase's threshold, dropping the votes it consumed.
+    fn assemble_view_sync_if_complete<TYPES: NodeType>(
+        &mut self,
+        commitment: Commitment<COMMITMENT>,
+        entries: Vec<<TYPES::SignatureKey as SignatureKey>::StakeTableEntry>,
+    ) -> Option<AssembledSignature<TYPES>> {
+        for (phase, kind) in [
+            (
+                ViewSyncPhase::PreCommit,
+                AssembledSignatureKind::ViewSyncPreCommit,
+            ),
+            (
+                ViewSyncPhase::Commit,
+                AssembledSignatureKind::ViewSyncCommit,
+            ),
+            (
+                ViewSyncPhase::Finalize,
+                AssembledSignatureKind::ViewSyncFinalize,
+            ),
+        ] {
+            let threshold = phase.threshold(self.success_threshold, self.failure_threshold);
+            let outcomes = match phase {
+                ViewSyncPhase::PreCommit => &mut self.viewsync_precommit_vote_outcomes,
+                ViewSyncPhase::Commit => &mut self.viewsync_commit_vote_outcomes,
+                ViewSyncPhase::Finalize | ViewSyncPhase::None => {
+                    &mut self.viewsync_finalize_vote_outcomes
+                }
+            };
+            let stake = outcomes
+                .get(&commitment)
+                .map_or_else(U256::zero, |(stake, _)| *stake);
+            if stake < U256::from(threshold.get()) {
+                continue;
+            }
+            let votes = outcomes.remove(&commitment).map(|(_, votes)| votes);
+            return Some(self.assemble(kind, threshold, entries, votes));
+        }
+        None
+    }
 }
 
 impl<TOKEN, LEAF: Committable + Serialize + Clone, TYPES: NodeType>
@@ -331,15 +707,17 @@ where
         let (commitment, (key, (sig, entries, node_id, vote_data, token))) = val;
 
         // Desereialize the sig so that it can be assembeld into a QC
-        let origianl_sig: <BLSOverBN254CurveSignatureScheme as SignatureScheme>::Signature =
-            bincode_opts()
-                .deserialize(&sig.0)
-                .expect("Deserialization on the signature shouldn't be able to fail.");
+        let origianl_sig: Option<<BLSOverBN254CurveSignatureScheme as SignatureScheme>::Signature> =
+            <TYPES::SignatureKey as SignatureKey>::supports_aggregation().then(|| {
+                bincode_opts()
+                    .deserialize(&sig.0)
+                    .expect("Deserialization on the signature shouldn't be able to fail.")
+            });
 
         let (total_stake_casted, total_vote_map) = self
             .total_vote_outcomes
             .entry(commitment)
-            .or_insert_with(|| (0, BTreeMap::new()));
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
 
         // Check for duplicate vote
         if total_vote_map.contains_key(&key) {
@@ -348,32 +726,42 @@ where
         let (da_stake_casted, da_vote_map) = self
             .da_vote_outcomes
             .entry(commitment)
-            .or_insert_with(|| (0, BTreeMap::new()));
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
 
         let (yes_stake_casted, yes_vote_map) = self
             .yes_vote_outcomes
             .entry(commitment)
-            .or_insert_with(|| (0, BTreeMap::new()));
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
 
         let (no_stake_casted, no_vote_map) = self
             .no_vote_outcomes
             .entry(commitment)
-            .or_insert_with(|| (0, BTreeMap::new()));
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
 
         let (viewsync_precommit_stake_casted, viewsync_precommit_vote_map) = self
             .viewsync_precommit_vote_outcomes
             .entry(commitment)
-            .or_insert_with(|| (0, BTreeMap::new()));
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
 
         let (viewsync_commit_stake_casted, viewsync_commit_vote_map) = self
             .viewsync_commit_vote_outcomes
             .entry(commitment)
-            .or_insert_with(|| (0, BTreeMap::new()));
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
 
         let (viewsync_finalize_stake_casted, viewsync_finalize_vote_map) = self
             .viewsync_finalize_vote_outcomes
             .entry(commitment)
-            .or_insert_with(|| (0, BTreeMap::new()));
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
+
+        let (upgrade_stake_casted, upgrade_vote_map) = self
+            .upgrade_vote_outcomes
+            .entry(commitment)
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
+
+        let (timeout_stake_casted, timeout_vote_map) = self
+            .timeout_vote_outcomes
+            .entry(commitment)
+            .or_insert_with(|| (U256::zero(), BTreeMap::new()));
 
         // Accumulate the stake for each leaf commitment rather than the total
         // stake of all votes, in case they correspond to inconsistent
@@ -382,95 +770,70 @@ where
         // update the active_keys and sig_lists
         if self.signers.get(node_id).as_deref() == Some(&true) {
             error!("node id already in signers");
+            let earlier = self
+                .total_vote_outcomes
+                .iter()
+                .filter(|(other, _)| **other != commitment)
+                .find_map(|(_, (_, votes))| votes.get(&key));
+            if let Some((sig_a, vote_a, _)) = earlier {
+                let evidence = EquivocationEvidence {
+                    key,
+                    vote_a: vote_a.clone(),
+                    sig_a: sig_a.clone(),
+                    vote_b: vote_data,
+                    sig_b: sig,
+                };
+                error!("Voter equivocated: {:?}", evidence);
+                self.equivocations.push(evidence);
+            }
             return Either::Left(self);
         }
         self.signers.set(node_id, true);
-        self.sig_lists.push(origianl_sig);
+        self.sig_lists.extend(origianl_sig);
 
-        *total_stake_casted += u64::from(token.vote_count());
+        let weight = token.vote_weight();
+        *total_stake_casted = total_stake_casted.saturating_add(weight);
         total_vote_map.insert(key.clone(), (sig.clone(), vote_data.clone(), token.clone()));
 
         match vote_data {
             VoteData::DA(_) => {
-                *da_stake_casted += u64::from(token.vote_count());
+                *da_stake_casted = da_stake_casted.saturating_add(weight);
                 da_vote_map.insert(key, (sig, vote_data, token));
             }
             VoteData::Yes(_) => {
-                *yes_stake_casted += u64::from(token.vote_count());
+                *yes_stake_casted = yes_stake_casted.saturating_add(weight);
                 yes_vote_map.insert(key, (sig, vote_data, token));
             }
             VoteData::No(_) => {
-                *no_stake_casted += u64::from(token.vote_count());
+                *no_stake_casted = no_stake_casted.saturating_add(weight);
                 no_vote_map.insert(key, (sig, vote_data, token));
             }
             VoteData::ViewSyncPreCommit(_) => {
-                *viewsync_precommit_stake_casted += u64::from(token.vote_count());
+                *viewsync_precommit_stake_casted =
+                    viewsync_precommit_stake_casted.saturating_add(weight);
                 viewsync_precommit_vote_map.insert(key, (sig, vote_data, token));
             }
             VoteData::ViewSyncCommit(_) => {
-                *viewsync_commit_stake_casted += u64::from(token.vote_count());
+                *viewsync_commit_stake_casted = viewsync_commit_stake_casted.saturating_add(weight);
                 viewsync_commit_vote_map.insert(key, (sig, vote_data, token));
             }
             VoteData::ViewSyncFinalize(_) => {
-                *viewsync_finalize_stake_casted += u64::from(token.vote_count());
+                *viewsync_finalize_stake_casted =
+                    viewsync_finalize_stake_casted.saturating_add(weight);
                 viewsync_finalize_vote_map.insert(key, (sig, vote_data, token));
             }
+            VoteData::Upgrade(_) => {
+                *upgrade_stake_casted = upgrade_stake_casted.saturating_add(weight);
+                upgrade_vote_map.insert(key, (sig, vote_data, token));
+            }
             VoteData::Timeout(_) => {
-                unimplemented!()
+                *timeout_stake_casted = timeout_stake_casted.saturating_add(weight);
+                timeout_vote_map.insert(key, (sig, vote_data, token));
             }
         }
 
-        // This is a messy way of accounting for the different vote types, but we will be replacing this code very soon
-        if *total_stake_casted >= u64::from(self.success_threshold) {
-            // Do assemble for QC here
-            let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
-                entries.clone(),
-                U256::from(self.success_threshold.get()),
-            );
-
-            let real_qc_sig = <TYPES::SignatureKey as SignatureKey>::assemble(
-                &real_qc_pp,
-                self.signers.as_bitslice(),
-                &self.sig_lists[..],
-            );
-
-            if *yes_stake_casted >= u64::from(self.success_threshold) {
-                self.yes_vote_outcomes.remove(&commitment);
-                return Either::Right(AssembledSignature::Yes(real_qc_sig));
-            } else if *no_stake_casted >= u64::from(self.failure_threshold) {
-                self.total_vote_outcomes.remove(&commitment);
-                return Either::Right(AssembledSignature::No(real_qc_sig));
-            } else if *da_stake_casted >= u64::from(self.success_threshold) {
-                self.da_vote_outcomes.remove(&commitment);
-                return Either::Right(AssembledSignature::DA(real_qc_sig));
-            } else if *viewsync_commit_stake_casted >= u64::from(self.success_threshold) {
-                self.viewsync_commit_vote_outcomes
-                    .remove(&commitment)
-                    .unwrap();
-                return Either::Right(AssembledSignature::ViewSyncCommit(real_qc_sig));
-            } else if *viewsync_finalize_stake_casted >= u64::from(self.success_threshold) {
-                self.viewsync_finalize_vote_outcomes
-                    .remove(&commitment)
-                    .unwrap();
-                return Either::Right(AssembledSignature::ViewSyncFinalize(real_qc_sig));
-            }
-        }
-        if *viewsync_precommit_stake_casted >= u64::from(self.failure_threshold) {
-            let real_qc_pp = <TYPES::SignatureKey as SignatureKey>::get_public_parameter(
-                entries,
-                U256::from(self.failure_threshold.get()),
-            );
-
-            let real_qc_sig = <TYPES::SignatureKey as SignatureKey>::assemble(
-                &real_qc_pp,
-                self.signers.as_bitslice(),
-                &self.sig_lists[..],
-            );
-
-            self.viewsync_precommit_vote_outcomes
-                .remove(&commitment)
-                .unwrap();
-            return Either::Right(AssembledSignature::ViewSyncPreCommit(real_qc_sig));
+        if let Some(signature) = self.assemble_if_complete(commitment, entries) {
+            return Either::Right(signature);
         }
         Either::Left(self)
     }
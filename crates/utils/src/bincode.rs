@@ -7,19 +7,29 @@ use bincode::{
     DefaultOptions, Options,
 };
 
+/// The bincode options used for the wire format, as returned by [`bincode_opts`]
+pub type BincodeOpts = WithOtherTrailing<
+    WithOtherIntEncoding<
+        WithOtherEndian<WithOtherLimit<DefaultOptions, bincode::config::Infinite>, LittleEndian>,
+        VarintEncoding,
+    >,
+    RejectTrailing,
+>;
+
+// The options are type-level configuration only, so building them costs nothing and there is no
+// value worth caching. This fails to compile if that ever stops being true.
+const _: () = assert!(std::mem::size_of::<BincodeOpts>() == 0);
+
 /// For the wire format, we use bincode with the following options:
 ///   - No upper size limit
 ///   - Litte endian encoding
 ///   - Varint encoding
 ///   - Reject trailing bytes
+///
+/// The options are zero-sized, so calling this on every (de)serialization is free; see the
+/// `bincode_opts` benchmark in `hotshot-testing`.
 #[must_use]
-pub fn bincode_opts() -> WithOtherTrailing<
-    WithOtherIntEncoding<
-        WithOtherEndian<WithOtherLimit<DefaultOptions, bincode::config::Infinite>, LittleEndian>,
-        VarintEncoding,
-    >,
-    RejectTrailing,
-> {
+pub fn bincode_opts() -> BincodeOpts {
     bincode::DefaultOptions::new()
         .with_no_limit()
         .with_little_endian()